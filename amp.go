@@ -0,0 +1,79 @@
+package main
+
+import (
+	"net/url"
+	"slices"
+	"strings"
+)
+
+// Get ICANN public suffix of a lowercase domain name, e.g. "com.au" for
+// "google.com.au", or "" if it has none. Set with the etld build tag, without
+// it isGoogleHost approximates the public suffix list.
+var icannPublicSuffix func(host string) string
+
+// Check if host is a Google search domain, "google" followed by a public
+// suffix, e.g. "www.google.com", "google.fi" or "google.com.au"
+func isGoogleHost(host string) bool {
+	host = strings.TrimSuffix(strings.TrimPrefix(strings.ToLower(host), "www."), ".")
+	suffix, ok := strings.CutPrefix(host, "google.")
+	if !ok || suffix == "" {
+		return false
+	}
+	if icannPublicSuffix != nil {
+		return icannPublicSuffix(host) == suffix
+	}
+	// A top-level domain, or a common second level label of a country code
+	// TLD, such as "co.uk", as in splitApexDomain
+	labels := strings.Split(suffix, ".")
+	switch len(labels) {
+	case 1:
+		return true
+	case 2:
+		return len(labels[1]) == 2 && slices.Contains(countryCodeSecondLevelLabels, labels[0])
+	}
+	return false
+}
+
+// Convert AMP URL back to the canonical URL of the page.
+//
+// Google AMP proxy URLs, e.g. "https://www.google.com/amp/s/example.com%2Fpage",
+// are decoded into the proxied URL, "https://example.com/page". The "s/"
+// prefix stands for HTTPS, without it the page is served over HTTP. With
+// stripAMPPrefix, the "/amp/" path prefix used by many sites for AMP
+// versions of pages is removed too, so "https://example.com/amp/page" becomes
+// "https://example.com/page". Other URLs are returned as-is.
+func urlCleanAMP(rawURL string, stripAMPPrefix bool) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+
+	escaped := u.EscapedPath()
+	if proxied, ok := strings.CutPrefix(escaped, "/amp/"); ok && isGoogleHost(u.Hostname()) {
+		scheme := "http://"
+		if rest, ok := strings.CutPrefix(proxied, "s/"); ok {
+			scheme, proxied = "https://", rest
+		}
+		target, err := url.PathUnescape(proxied)
+		if err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+		if u.RawQuery != "" {
+			target += "?" + u.RawQuery
+		}
+		proxiedURL, err := parseURL(scheme + target)
+		if err != nil {
+			return "", err
+		}
+		return proxiedURL.String(), nil
+	}
+
+	if !stripAMPPrefix || escaped != "/amp" && !strings.HasPrefix(escaped, "/amp/") {
+		return rawURL, nil
+	}
+	escaped = "/" + strings.TrimPrefix(strings.TrimPrefix(escaped, "/amp"), "/")
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+	return u.String(), nil
+}
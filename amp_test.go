@@ -0,0 +1,45 @@
+package main
+
+import "testing"
+
+func TestURLCleanAMP(t *testing.T) {
+	tests := map[string]string{
+		// Google AMP proxy
+		"https://www.google.com/amp/s/example.com%2Fpage":          "https://example.com/page",
+		"https://www.google.com/amp/s/example.com/news/story.html": "https://example.com/news/story.html",
+		"https://google.fi/amp/s/www.iltalehti.fi%2Fkotimaa%2Fa":   "https://www.iltalehti.fi/kotimaa/a",
+		"https://www.google.co.uk/amp/example.com/page?id=1":       "http://example.com/page?id=1",
+		// Not AMP
+		"https://example.com/page":        "https://example.com/page",
+		"https://example.com/amp/page":    "https://example.com/amp/page",
+		"https://www.google.com/search":   "https://www.google.com/search",
+		"https://google.example.com/amp/": "https://google.example.com/amp/",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlCleanAMP(input, false); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Site specific "/amp/" prefix is only stripped on request
+	prefixed := map[string]string{
+		"https://example.com/amp/page":          "https://example.com/page",
+		"https://example.com/amp/a/b?x=1#top":   "https://example.com/a/b?x=1#top",
+		"https://example.com/amp":               "https://example.com/",
+		"https://example.com/amplifier":         "https://example.com/amplifier",
+		"https://example.com/page/amp/":         "https://example.com/page/amp/",
+		"https://www.google.com/amp/s/x.com%2F": "https://x.com/",
+	}
+	for input, expected := range prefixed {
+		if got, err := urlCleanAMP(input, true); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q with prefix stripping, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	for _, input := range []string{"://invalid", "https://www.google.com/amp/s/%zz"} {
+		if _, err := urlCleanAMP(input, false); err == nil {
+			t.Errorf("❌ Expected error for %q", input)
+		}
+	}
+}
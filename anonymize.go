@@ -0,0 +1,151 @@
+package main
+
+import (
+	"net/url"
+	"regexp"
+	"strings"
+)
+
+// Length of truncated hashes replacing anonymized URL parts
+const anonymizedHashLength = 8
+
+// AnonymizationOptions selects which parts of a URL urlAnonymize hashes.
+type AnonymizationOptions struct {
+	HashPathParams  bool // Hash path segments containing digits, such as IDs
+	HashQueryValues bool // Hash query values, keeping the keys
+	RemoveFragment  bool // Remove the fragment
+}
+
+// Hash identifying value into a short hex string
+func anonymizeValue(value string) string {
+	return generateSignature(value)[:anonymizedHashLength]
+}
+
+// Normalize URL and replace identifying parts of it with truncated SHA-256
+// hashes, keeping the structure.
+//
+// Path segments are considered parameters if they contain a digit, so
+// "/products/12345/reviews" becomes "/products/5994471a/reviews". Log
+// analysis can still group URLs by template, without storing the IDs.
+// Hashes are unsalted, so short or guessable values can be recovered by
+// brute force.
+func urlAnonymize(rawURL string, opts AnonymizationOptions) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+
+	if opts.HashPathParams {
+		segments := strings.Split(u.EscapedPath(), "/")
+		for i, segment := range segments {
+			if strings.ContainsAny(segment, "0123456789") {
+				segments[i] = anonymizeValue(segment)
+			}
+		}
+		u.RawPath = strings.Join(segments, "/")
+		if u.Path, err = url.PathUnescape(u.RawPath); err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+	}
+	if opts.HashQueryValues && u.RawQuery != "" {
+		pairs := queryStringToSortedPairs(u.RawQuery)
+		for i := range pairs {
+			pairs[i][1] = anonymizeValue(pairs[i][1])
+		}
+		u.RawQuery = mapToQueryString(pairs)
+	}
+	if opts.RemoveFragment {
+		u.Fragment = ""
+		u.RawFragment = ""
+	}
+	return u.String(), nil
+}
+
+// Placeholders of redacted URL parts, left unescaped to be readable in logs
+const (
+	redactedValue     = "[REDACTED]"
+	redactedPathParam = "[ID]"
+)
+
+// RedactionPolicy selects which parts of a URL urlRedact replaces.
+type RedactionPolicy struct {
+	RedactQueryValues bool // Replace query values with [REDACTED], keeping the keys
+	RedactPathParams  bool // Replace numeric and UUID path segments with [ID]
+	RedactCredentials bool // Remove user name and password
+}
+
+// Normalize URL and replace sensitive parts of it with placeholders, for
+// writing into logs:
+//
+//	urlRedact("https://example.com/?user=alice&token=secret123", RedactionPolicy{RedactQueryValues: true})
+//	// "https://example.com/?token=[REDACTED]&user=[REDACTED]"
+//
+// Unlike urlAnonymize, redacted values can't be told apart.
+func urlRedact(rawURL string, policy RedactionPolicy) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+
+	if policy.RedactPathParams {
+		segments := strings.Split(u.EscapedPath(), "/")
+		for i, segment := range segments {
+			if pathVariablePlaceholder(segment) != "" {
+				segments[i] = redactedPathParam
+			}
+		}
+		u.RawPath = strings.Join(segments, "/")
+		if u.Path, err = url.PathUnescape(u.RawPath); err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+	}
+	if policy.RedactQueryValues && u.RawQuery != "" {
+		pairs := queryStringToSortedPairs(u.RawQuery)
+		params := make([]string, 0, len(pairs))
+		for _, kv := range pairs {
+			params = append(params, url.QueryEscape(kv[0])+"="+redactedValue)
+		}
+		u.RawQuery = strings.Join(params, "&")
+	}
+	if policy.RedactCredentials {
+		u.User = nil
+	}
+	return u.String(), nil
+}
+
+// URLMask replaces parts of URL components matching a pattern, for
+// anonymizing URLs in ways RedactionPolicy doesn't cover. Nil patterns leave
+// their component as-is. Replacements can refer to submatches, as in
+// regexp.Regexp.ReplaceAllString:
+//
+//	mask := URLMask{PathPattern: regexp.MustCompile(`/users/\d+`), PathReplacement: "/users/USER_ID"}
+type URLMask struct {
+	HostPattern      *regexp.Regexp // Matched against the host, with port
+	HostReplacement  string
+	PathPattern      *regexp.Regexp // Matched against the escaped path
+	PathReplacement  string
+	QueryPattern     *regexp.Regexp // Matched against the raw query, without "?"
+	QueryReplacement string
+}
+
+// Normalize URL and replace matches of the mask patterns in its components.
+func urlMask(rawURL string, mask URLMask) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+
+	if mask.HostPattern != nil {
+		u.Host = mask.HostPattern.ReplaceAllString(u.Host, mask.HostReplacement)
+	}
+	if mask.PathPattern != nil {
+		u.RawPath = mask.PathPattern.ReplaceAllString(u.EscapedPath(), mask.PathReplacement)
+		if u.Path, err = url.PathUnescape(u.RawPath); err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+	}
+	if mask.QueryPattern != nil {
+		u.RawQuery = mask.QueryPattern.ReplaceAllString(u.RawQuery, mask.QueryReplacement)
+	}
+	return u.String(), nil
+}
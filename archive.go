@@ -0,0 +1,64 @@
+package main
+
+import (
+	"regexp"
+	"strings"
+)
+
+// Path of Wayback Machine snapshots: "/web/<timestamp>[modifier]/<url>". The
+// timestamp can be partial, e.g. just the year, and the optional modifier,
+// such as "id_" for the original content, selects how the snapshot is served.
+var waybackPathPattern = regexp.MustCompile(`^/web/[0-9]{1,14}(?:[a-z]{2}_)?/(.+)$`)
+
+// Get the archived URL of a Wayback Machine snapshot URL, or false if URL is
+// not one.
+func waybackTarget(rawURL string) (string, bool) {
+	u, err := parseURL(rawURL)
+	if err != nil || !strings.EqualFold(u.Hostname(), "web.archive.org") {
+		return "", false
+	}
+	m := waybackPathPattern.FindStringSubmatch(u.EscapedPath())
+	if m == nil {
+		return "", false
+	}
+	target := m[1]
+	// Slashes of the archived URL are sometimes merged, as in "https:/example.com/"
+	if scheme, rest, ok := strings.Cut(target, ":/"); ok && !strings.HasPrefix(rest, "/") {
+		target = scheme + "://" + rest
+	}
+	if !strings.Contains(target, "://") {
+		target = "http://" + target
+	}
+	if u.RawQuery != "" {
+		target += "?" + u.RawQuery
+	}
+	if u.Fragment != "" {
+		target += "#" + u.EscapedFragment()
+	}
+	return target, true
+}
+
+// Check if URL is a Wayback Machine snapshot, such as
+// "https://web.archive.org/web/20230101120000/https://example.com/page".
+func urlIsArchiveURL(rawURL string) bool {
+	_, ok := waybackTarget(rawURL)
+	return ok
+}
+
+// Convert Wayback Machine snapshot URL to the archived URL, so
+// "https://web.archive.org/web/20230101120000/https://example.com/page"
+// becomes "https://example.com/page". Other URLs are returned as-is.
+func urlNormalizeArchiveURL(rawURL string) (string, error) {
+	target, ok := waybackTarget(rawURL)
+	if !ok {
+		if _, err := parseURL(rawURL); err != nil {
+			return "", err
+		}
+		return rawURL, nil
+	}
+	u, err := parseURL(target)
+	if err != nil {
+		return "", err
+	}
+	return u.String(), nil
+}
@@ -0,0 +1,42 @@
+package main
+
+import "testing"
+
+func TestURLNormalizeArchiveURL(t *testing.T) {
+	tests := map[string]string{
+		"https://web.archive.org/web/20230101120000/https://example.com/page":     "https://example.com/page",
+		"https://web.archive.org/web/2023/https://example.com/page":               "https://example.com/page",
+		"http://Web.Archive.org/web/20230101120000id_/http://example.com/a?x=1#b": "http://example.com/a?x=1#b",
+		"https://web.archive.org/web/20230101/https:/example.com/a%20b":           "https://example.com/a%20b",
+		"https://web.archive.org/web/20230101120000/example.com/page":             "http://example.com/page",
+		"https://web.archive.org/web/*/https://example.com/page":                  "https://web.archive.org/web/*/https://example.com/page",
+		"https://web.archive.org/about/":                                          "https://web.archive.org/about/",
+		"https://example.com/web/2023/https://other.example/":                     "https://example.com/web/2023/https://other.example/",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlNormalizeArchiveURL(input); err != nil || got != expected {
+			t.Errorf("❌ urlNormalizeArchiveURL(%q): expected %q, got %q (error: %v)", input, expected, got, err)
+		}
+	}
+
+	if _, err := urlNormalizeArchiveURL("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLIsArchiveURL(t *testing.T) {
+	tests := map[string]bool{
+		"https://web.archive.org/web/20230101120000/https://example.com/page": true,
+		"https://web.archive.org/web/2023im_/https://example.com/image.png":   true,
+		"https://web.archive.org/web/https://example.com/page":                false,
+		"https://example.com/page":                                            false,
+		"://invalid":                                                          false,
+	}
+
+	for input, expected := range tests {
+		if got := urlIsArchiveURL(input); got != expected {
+			t.Errorf("❌ urlIsArchiveURL(%q): expected %v, got %v", input, expected, got)
+		}
+	}
+}
@@ -0,0 +1,46 @@
+package main
+
+import (
+	"sync"
+	"unsafe"
+)
+
+// Prevent excessive allocations
+const maxAllocSize = 1024 * 1024 // 1MB
+
+// Memory arena keeping buffers shared with the WASM host alive, so they
+// aren't garbage collected while the host uses them. Buffers are keyed by
+// address, using a sync.Map for better concurrent performance.
+type memoryArena struct {
+	buffers sync.Map // map[uintptr][]byte
+}
+
+// Allocate a zeroed, 8-byte aligned buffer of size bytes and keep it alive
+// until freed. Returns its address, or 0 if size is 0 or larger than
+// maxAllocSize.
+func (a *memoryArena) alloc(size uint32) uintptr {
+	if size == 0 || size > maxAllocSize {
+		return 0
+	}
+	// Allocate as 64-bit words so the buffer is 8-byte aligned
+	words := make([]uint64, (size+7)/8)
+	return a.keep(unsafe.Slice((*byte)(unsafe.Pointer(&words[0])), size))
+}
+
+// Keep buf alive until freed, returning its address, or 0 if buf is empty
+func (a *memoryArena) keep(buf []byte) uintptr {
+	if len(buf) == 0 {
+		return 0
+	}
+	ptr := uintptr(unsafe.Pointer(&buf[0]))
+	a.buffers.Store(ptr, buf)
+	return ptr
+}
+
+// Release the buffer at ptr, so it can be garbage collected. Reports whether
+// ptr was kept by the arena: freeing twice, or freeing an unknown pointer, is
+// a no-op.
+func (a *memoryArena) free(ptr uintptr) bool {
+	_, ok := a.buffers.LoadAndDelete(ptr)
+	return ok
+}
@@ -0,0 +1,108 @@
+package main
+
+import (
+	"sync"
+	"testing"
+	"unsafe"
+)
+
+func TestMemoryArenaAlloc(t *testing.T) {
+	var arena memoryArena
+	for _, size := range []uint32{1, 7, 8, 9, 64, 1000, maxAllocSize} {
+		ptr := arena.alloc(size)
+		if ptr == 0 {
+			t.Fatalf("❌ Expected allocation of %d bytes", size)
+		}
+		if ptr%8 != 0 {
+			t.Errorf("❌ Expected %d byte buffer to be 8-byte aligned, got address %#x", size, ptr)
+		}
+		value, ok := arena.buffers.Load(ptr)
+		if !ok {
+			t.Fatalf("❌ Expected %d byte buffer to be kept", size)
+		}
+		buf := value.([]byte)
+		if uint32(len(buf)) != size || uintptr(unsafe.Pointer(&buf[0])) != ptr {
+			t.Errorf("❌ Expected %d byte buffer at %#x, got %d bytes", size, ptr, len(buf))
+		}
+		for i, b := range buf {
+			if b != 0 {
+				t.Fatalf("❌ Expected zeroed buffer, got %#x at %d", b, i)
+			}
+		}
+	}
+
+	for _, size := range []uint32{0, maxAllocSize + 1} {
+		if ptr := arena.alloc(size); ptr != 0 {
+			t.Errorf("❌ Expected no allocation of %d bytes, got %#x", size, ptr)
+		}
+	}
+}
+
+func TestMemoryArenaFree(t *testing.T) {
+	var arena memoryArena
+	ptr := arena.alloc(16)
+	other := arena.alloc(16)
+	if ptr == other {
+		t.Fatalf("❌ Expected distinct allocations, got %#x twice", ptr)
+	}
+
+	if !arena.free(ptr) {
+		t.Error("❌ Expected allocated buffer to be freed")
+	}
+	if _, ok := arena.buffers.Load(ptr); ok {
+		t.Error("❌ Expected freed buffer to be released")
+	}
+	if arena.free(ptr) {
+		t.Error("❌ Expected double free to be a no-op")
+	}
+	if _, ok := arena.buffers.Load(other); !ok {
+		t.Error("❌ Expected other buffer to be kept after freeing")
+	}
+
+	for _, unknown := range []uintptr{0, other + 1, 0xdead0000} {
+		if arena.free(unknown) {
+			t.Errorf("❌ Expected free of unknown pointer %#x to be a no-op", unknown)
+		}
+	}
+	if !arena.free(other) {
+		t.Error("❌ Expected other buffer to be freed")
+	}
+}
+
+func TestMemoryArenaKeep(t *testing.T) {
+	var arena memoryArena
+	if ptr := arena.keep(nil); ptr != 0 {
+		t.Errorf("❌ Expected nothing kept for empty buffer, got %#x", ptr)
+	}
+	buf := []byte("result")
+	ptr := arena.keep(buf)
+	if ptr != uintptr(unsafe.Pointer(&buf[0])) {
+		t.Errorf("❌ Expected buffer address %p, got %#x", &buf[0], ptr)
+	}
+	if !arena.free(ptr) {
+		t.Error("❌ Expected kept buffer to be freed")
+	}
+}
+
+func TestMemoryArenaConcurrent(t *testing.T) {
+	var arena memoryArena
+	var wg sync.WaitGroup
+	for range 10 {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for range 100 {
+				ptr := arena.alloc(32)
+				if ptr == 0 || !arena.free(ptr) {
+					t.Error("❌ Expected concurrent allocation to be freed once")
+					return
+				}
+			}
+		}()
+	}
+	wg.Wait()
+	arena.buffers.Range(func(key, _ any) bool {
+		t.Errorf("❌ Expected no buffers left, got %#x", key)
+		return true
+	})
+}
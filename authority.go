@@ -0,0 +1,61 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"net/url"
+	"strconv"
+	"strings"
+)
+
+var errInvalidAuthority = errors.New("authority must not contain path, query or fragment")
+
+// Authority component of a URL, "user:pass@host:port", on its own. Authorities
+// of other protocols, e.g. the target of HTTP CONNECT requests, have the same
+// syntax.
+type Authority struct {
+	User *url.Userinfo // Nil if absent
+	Host string        // Without IPv6 brackets
+	Port uint16        // Zero if absent
+}
+
+// Parse authority, such as "user:pass@example.com:8080" or "[::1]:443".
+// Userinfo is percent-decoded, and the host must not be empty.
+func urlParseAuthority(authority string) (Authority, error) {
+	if strings.ContainsAny(authority, "/?#") {
+		return Authority{}, newURLParseError(authority, errInvalidAuthority)
+	}
+	u, err := url.Parse("//" + authority)
+	if err != nil {
+		return Authority{}, newURLParseError(authority, err)
+	}
+	a := Authority{User: u.User, Host: u.Hostname()}
+	if a.Host == "" {
+		return Authority{}, newURLParseError(authority, errors.New("missing host"))
+	}
+	if port := u.Port(); port != "" {
+		n, err := strconv.ParseUint(port, 10, 16)
+		if err != nil || n == 0 {
+			return Authority{}, newURLParseError(authority, fmt.Errorf("invalid port %q", port))
+		}
+		a.Port = uint16(n)
+	}
+	return a, nil
+}
+
+// Format authority, escaping userinfo and bracketing IPv6 hosts.
+func (a Authority) String() string {
+	var b strings.Builder
+	if a.User != nil {
+		b.WriteString(a.User.String() + "@")
+	}
+	if strings.Contains(a.Host, ":") {
+		b.WriteString("[" + a.Host + "]")
+	} else {
+		b.WriteString(a.Host)
+	}
+	if a.Port != 0 {
+		b.WriteString(":" + strconv.Itoa(int(a.Port)))
+	}
+	return b.String()
+}
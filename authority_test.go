@@ -0,0 +1,48 @@
+package main
+
+import "testing"
+
+func TestURLParseAuthority(t *testing.T) {
+	tests := []struct {
+		authority string
+		username  string
+		password  string
+		host      string
+		port      uint16
+		formatted string
+	}{
+		{"user:pass@example.com:8080", "user", "pass", "example.com", 8080, "user:pass@example.com:8080"},
+		{"j%C3%B6rg@example.com", "jörg", "", "example.com", 0, "j%C3%B6rg@example.com"},
+		{"example.com:443", "", "", "example.com", 443, "example.com:443"},
+		{"example.com", "", "", "example.com", 0, "example.com"},
+		{"example.com:", "", "", "example.com", 0, "example.com"},
+		{"192.0.2.1:8080", "", "", "192.0.2.1", 8080, "192.0.2.1:8080"},
+		{"[2001:db8::1]:443", "", "", "2001:db8::1", 443, "[2001:db8::1]:443"},
+		{"[::1]", "", "", "::1", 0, "[::1]"},
+	}
+
+	for _, test := range tests {
+		a, err := urlParseAuthority(test.authority)
+		if err != nil {
+			t.Errorf("❌ Failed to parse %q: %v", test.authority, err)
+			continue
+		}
+		var username, password string
+		if a.User != nil {
+			username = a.User.Username()
+			password, _ = a.User.Password()
+		}
+		if username != test.username || password != test.password || a.Host != test.host || a.Port != test.port {
+			t.Errorf("❌ urlParseAuthority(%q): expected %q:%q@%q port %d, got %q:%q@%q port %d", test.authority, test.username, test.password, test.host, test.port, username, password, a.Host, a.Port)
+		}
+		if got := a.String(); got != test.formatted {
+			t.Errorf("❌ Expected %q formatted as %q, got %q", test.authority, test.formatted, got)
+		}
+	}
+
+	for _, authority := range []string{"", "user@", ":8080", "example.com:http", "example.com:65536", "example.com:0", "example.com/path", "example.com?x=1", "[::1"} {
+		if _, err := urlParseAuthority(authority); err == nil {
+			t.Errorf("❌ Expected error for %q", authority)
+		}
+	}
+}
@@ -0,0 +1,48 @@
+//go:build browser && !js && !wasip1
+// +build browser,!js,!wasip1
+
+package main
+
+import (
+	"errors"
+	"os"
+	"os/exec"
+	"runtime"
+)
+
+var errNotBrowserURL = errors.New("only http and https URLs can be opened in a browser")
+
+func init() {
+	browserOpener = openInBrowser
+}
+
+// Get command opening URL in the default browser of the platform. The
+// BROWSER environment variable overrides it, as on many Unix systems.
+func browserCommand(rawURL string) *exec.Cmd {
+	if browser := os.Getenv("BROWSER"); browser != "" {
+		return exec.Command(browser, rawURL)
+	}
+	switch runtime.GOOS {
+	case "darwin":
+		return exec.Command("open", rawURL)
+	case "windows":
+		return exec.Command("rundll32", "url.dll,FileProtocolHandler", rawURL)
+	}
+	return exec.Command("xdg-open", rawURL)
+}
+
+// Normalize URL and open it in the default browser, for previewing canonical
+// URLs in development tools. Only available in builds with the browser tag,
+// and not meant for production use.
+//
+// Only http and https URLs are opened, others fail with errNotBrowserURL.
+func openInBrowser(rawURL string) error {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return err
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return newURLParseError(rawURL, errNotBrowserURL)
+	}
+	return browserCommand(u.String()).Run()
+}
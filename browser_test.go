@@ -0,0 +1,63 @@
+//go:build browser && !js && !wasip1
+// +build browser,!js,!wasip1
+
+package main
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"testing"
+)
+
+// Set BROWSER to a script writing the opened URL into the returned file
+func fakeBrowser(t *testing.T) string {
+	t.Helper()
+	if runtime.GOOS == "windows" {
+		t.Skip("fake browser is a shell script")
+	}
+	dir := t.TempDir()
+	opened := filepath.Join(dir, "opened.txt")
+	script := filepath.Join(dir, "browser.sh")
+	if err := os.WriteFile(script, []byte("#!/bin/sh\necho \"$1\" > '"+opened+"'\n"), 0o755); err != nil {
+		t.Fatalf("❌ Failed to write fake browser: %v", err)
+	}
+	t.Setenv("BROWSER", script)
+	return opened
+}
+
+func TestOpenInBrowser(t *testing.T) {
+	opened := fakeBrowser(t)
+
+	if err := openInBrowser("HTTPS://Example.com:443/a/./b?z=1&a=2"); err != nil {
+		t.Fatalf("❌ Failed to open URL: %v", err)
+	}
+	data, err := os.ReadFile(opened)
+	if expected := "https://example.com/a/b?a=2&z=1"; err != nil || strings.TrimSpace(string(data)) != expected {
+		t.Errorf("❌ Expected normalized %q to be opened, got %q (error: %v)", expected, data, err)
+	}
+
+	for _, input := range []string{"file:///etc/passwd", "javascript:alert(1)"} {
+		if err := openInBrowser(input); !errors.Is(err, errNotBrowserURL) {
+			t.Errorf("❌ Expected errNotBrowserURL for %q, got: %v", input, err)
+		}
+	}
+	if err := openInBrowser("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestCLIOpen(t *testing.T) {
+	opened := fakeBrowser(t)
+
+	code, _, stderr := runCLI("-normalize-only", "-open", "-url", testCLIURL)
+	if code != 0 {
+		t.Fatalf("❌ Expected exit code 0, got %d: %s", code, stderr)
+	}
+	data, err := os.ReadFile(opened)
+	if expected, _ := processURL(testCLIURL); err != nil || strings.TrimSpace(string(data)) != expected {
+		t.Errorf("❌ Expected %q to be opened, got %q (error: %v)", expected, data, err)
+	}
+}
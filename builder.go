@@ -0,0 +1,98 @@
+package main
+
+import (
+	"errors"
+	"net"
+	"net/url"
+	"strconv"
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// URLBuilder constructs URLs from components without string concatenation.
+//
+// Each setter returns the builder so calls can be chained:
+//
+//	u, err := NewURLBuilder().Scheme("https").Host("example.com").Path("/a").QueryParam("id", "1").Build()
+type URLBuilder struct {
+	scheme   string
+	host     string
+	port     uint16
+	path     string
+	query    [][2]string // Query parameters in insertion order
+	fragment string
+}
+
+func NewURLBuilder() *URLBuilder {
+	return &URLBuilder{}
+}
+
+func (b *URLBuilder) Scheme(s string) *URLBuilder {
+	b.scheme = s
+	return b
+}
+
+func (b *URLBuilder) Host(h string) *URLBuilder {
+	b.host = h
+	return b
+}
+
+// Port sets the port, zero leaves the port out of the URL.
+func (b *URLBuilder) Port(p uint16) *URLBuilder {
+	b.port = p
+	return b
+}
+
+// Path sets the unescaped path, a leading slash is added if missing.
+func (b *URLBuilder) Path(p string) *URLBuilder {
+	b.path = p
+	return b
+}
+
+// QueryParam appends a query parameter. Repeated keys are kept in order.
+func (b *URLBuilder) QueryParam(k, v string) *URLBuilder {
+	b.query = append(b.query, [2]string{k, v})
+	return b
+}
+
+func (b *URLBuilder) Fragment(f string) *URLBuilder {
+	b.fragment = f
+	return b
+}
+
+// Build assembles the URL from the components set so far.
+func (b *URLBuilder) Build() (string, error) {
+	if b.scheme == "" {
+		return "", errors.New("building URL: missing scheme")
+	}
+	if b.host == "" {
+		return "", errors.New("building URL: missing host")
+	}
+
+	u := url.URL{
+		Scheme:   b.scheme,
+		Host:     b.host,
+		Path:     b.path,
+		Fragment: b.fragment,
+	}
+	if b.port != 0 {
+		u.Host = net.JoinHostPort(strings.Trim(b.host, "[]"), strconv.Itoa(int(b.port)))
+	}
+	if u.Path != "" && !strings.HasPrefix(u.Path, "/") {
+		u.Path = "/" + u.Path
+	}
+
+	u.RawQuery = mapToQueryString(b.query)
+
+	return u.String(), nil
+}
+
+// BuildNormalized assembles the URL and normalizes it with the given purell flags.
+func (b *URLBuilder) BuildNormalized(flags purell.NormalizationFlags) (string, error) {
+	built, err := b.Build()
+	if err != nil {
+		return "", err
+	}
+	return normalizeURLWithFlags(built, flags)
+}
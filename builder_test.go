@@ -0,0 +1,80 @@
+package main
+
+import (
+	"net/url"
+	"strconv"
+	"strings"
+	"testing"
+)
+
+func TestURLBuilderRoundTrip(t *testing.T) {
+	tests := []string{
+		"https://example.com",
+		"https://example.com/path/to/page",
+		"http://example.com:8080/a?x=1&y=2",
+		"https://example.com/search?q=hello+world&q=again#results",
+		"http://[::1]:8080/ipv6",
+	}
+
+	for _, rawURL := range tests {
+		t.Run(rawURL, func(t *testing.T) {
+			parsed, err := url.Parse(rawURL)
+			if err != nil {
+				t.Fatalf("❌ Failed to parse %s: %v", rawURL, err)
+			}
+
+			b := NewURLBuilder().
+				Scheme(parsed.Scheme).
+				Host(parsed.Hostname()).
+				Path(parsed.Path).
+				Fragment(parsed.Fragment)
+			if port := parsed.Port(); port != "" {
+				p, _ := strconv.Atoi(port)
+				b.Port(uint16(p))
+			}
+			if parsed.RawQuery != "" {
+				for _, pair := range strings.Split(parsed.RawQuery, "&") {
+					k, v, _ := strings.Cut(pair, "=")
+					k, _ = url.QueryUnescape(k)
+					v, _ = url.QueryUnescape(v)
+					b.QueryParam(k, v)
+				}
+			}
+
+			built, err := b.Build()
+			if err != nil {
+				t.Fatalf("❌ Build failed for %s: %v", rawURL, err)
+			}
+			if built != rawURL {
+				t.Fatalf("❌ Round trip mismatch\nExpected: %s\nGot: %s", rawURL, built)
+			}
+		})
+	}
+}
+
+func TestURLBuilderBuildNormalized(t *testing.T) {
+	got, err := NewURLBuilder().
+		Scheme("HTTPS").
+		Host("Example.COM").
+		Port(443).
+		Path("a/./b/../c").
+		QueryParam("b", "2").
+		QueryParam("a", "1").
+		BuildNormalized(defaultNormalizationFlags)
+	if err != nil {
+		t.Fatalf("❌ BuildNormalized failed: %v", err)
+	}
+	expected := "https://example.com/a/c?a=1&b=2"
+	if got != expected {
+		t.Fatalf("❌ Expected: %s\nGot: %s", expected, got)
+	}
+}
+
+func TestURLBuilderMissingComponents(t *testing.T) {
+	if _, err := NewURLBuilder().Host("example.com").Build(); err == nil {
+		t.Error("❌ Expected error for missing scheme")
+	}
+	if _, err := NewURLBuilder().Scheme("https").Build(); err == nil {
+		t.Error("❌ Expected error for missing host")
+	}
+}
@@ -0,0 +1,137 @@
+//go:build cache
+// +build cache
+
+package main
+
+import (
+	"container/list"
+	"sync"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// CachePolicy selects when NormalizationCache evicts entries. The least
+// recently used entry is evicted when the cache is full.
+type CachePolicy struct {
+	Capacity int           // Maximum number of cached URLs, at least 1
+	TTL      time.Duration // Time until cached URLs expire, zero for never
+}
+
+// Policy evicting the least recently used entries beyond capacity
+func LRUPolicy(capacity int) CachePolicy {
+	return CachePolicy{Capacity: capacity}
+}
+
+// Policy also expiring entries after ttl, so changes to normalization, such
+// as new tracking parameters to remove, eventually apply to cached URLs.
+func TTLPolicy(capacity int, ttl time.Duration) CachePolicy {
+	return CachePolicy{Capacity: capacity, TTL: ttl}
+}
+
+// Hit and miss counts of a NormalizationCache
+type CacheStats struct {
+	Hits   uint64
+	Misses uint64
+}
+
+type cacheEntry struct {
+	rawURL     string
+	normalized string
+	expires    time.Time // Zero if the entry never expires
+}
+
+// NormalizationCache caches URLs normalized with the same flags, for
+// services normalizing the same URLs repeatedly. Errors are not cached. It
+// is safe for concurrent use.
+type NormalizationCache struct {
+	flags  purell.NormalizationFlags
+	policy CachePolicy
+	now    func() time.Time
+
+	mu      sync.Mutex
+	entries map[string]*list.Element
+	order   *list.List // Entries by use, most recent first
+	stats   CacheStats
+}
+
+// Create cache of URLs normalized with flags. Capacity below 1 is treated
+// as 1.
+func NewNormalizationCache(flags purell.NormalizationFlags, policy CachePolicy) *NormalizationCache {
+	policy.Capacity = max(policy.Capacity, 1)
+	return &NormalizationCache{
+		flags:   flags,
+		policy:  policy,
+		now:     time.Now,
+		entries: make(map[string]*list.Element),
+		order:   list.New(),
+	}
+}
+
+// Normalize URL, or get it from the cache if it was normalized before.
+func (c *NormalizationCache) Normalize(rawURL string) (string, error) {
+	if normalized, ok := c.get(rawURL); ok {
+		return normalized, nil
+	}
+	// Normalize without holding the lock, so slow URLs don't block others
+	normalized, err := normalizeURLWithFlags(rawURL, c.flags)
+	if err != nil {
+		return "", err
+	}
+	c.put(rawURL, normalized)
+	return normalized, nil
+}
+
+// Stats returns the hit and miss counts since the cache was created.
+func (c *NormalizationCache) Stats() CacheStats {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.stats
+}
+
+// Len returns the number of cached URLs, including expired ones not yet
+// evicted.
+func (c *NormalizationCache) Len() int {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.order.Len()
+}
+
+func (c *NormalizationCache) get(rawURL string) (string, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if elem, ok := c.entries[rawURL]; ok {
+		entry := elem.Value.(*cacheEntry)
+		if entry.expires.IsZero() || c.now().Before(entry.expires) {
+			c.order.MoveToFront(elem)
+			c.stats.Hits++
+			return entry.normalized, true
+		}
+		c.order.Remove(elem)
+		delete(c.entries, rawURL)
+	}
+	c.stats.Misses++
+	return "", false
+}
+
+func (c *NormalizationCache) put(rawURL, normalized string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	entry := &cacheEntry{rawURL: rawURL, normalized: normalized}
+	if c.policy.TTL > 0 {
+		entry.expires = c.now().Add(c.policy.TTL)
+	}
+
+	// Another goroutine may have cached the URL while it was normalized
+	if elem, ok := c.entries[rawURL]; ok {
+		elem.Value = entry
+		c.order.MoveToFront(elem)
+		return
+	}
+	c.entries[rawURL] = c.order.PushFront(entry)
+	if c.order.Len() > c.policy.Capacity {
+		oldest := c.order.Back()
+		c.order.Remove(oldest)
+		delete(c.entries, oldest.Value.(*cacheEntry).rawURL)
+	}
+}
@@ -0,0 +1,60 @@
+//go:build cache
+// +build cache
+
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestNormalizationCacheLRU(t *testing.T) {
+	cache := NewNormalizationCache(defaultNormalizationFlags, LRUPolicy(2))
+	a, b, c := "HTTPS://Example.com/a?z=1&y=2", "https://example.com/b", "https://example.com/c"
+
+	for _, rawURL := range []string{a, b, a, c, a, b} {
+		expected, _ := normalizeURLWithFlags(rawURL, defaultNormalizationFlags)
+		if got, err := cache.Normalize(rawURL); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, rawURL, got, err)
+		}
+	}
+
+	// c evicted b, as a was used after it, and b evicted c in turn
+	if stats, expected := cache.Stats(), (CacheStats{Hits: 2, Misses: 4}); stats != expected {
+		t.Errorf("❌ Expected stats %+v, got %+v", expected, stats)
+	}
+	if cache.Len() != 2 {
+		t.Errorf("❌ Expected 2 cached URLs, got %d", cache.Len())
+	}
+}
+
+func TestNormalizationCacheTTL(t *testing.T) {
+	now := time.Date(2025, 1, 1, 0, 0, 0, 0, time.UTC)
+	cache := NewNormalizationCache(defaultNormalizationFlags, TTLPolicy(10, time.Minute))
+	cache.now = func() time.Time { return now }
+
+	const rawURL = "https://Example.com/?b=2&a=1"
+	for _, advance := range []time.Duration{0, 30 * time.Second, 30 * time.Second, 0} {
+		now = now.Add(advance)
+		if got, err := cache.Normalize(rawURL); err != nil || got != "https://example.com/?a=1&b=2" {
+			t.Errorf("❌ Expected normalized URL, got %q (error: %v)", got, err)
+		}
+	}
+
+	// The entry expired a minute after it was cached, and was cached again
+	if stats, expected := cache.Stats(), (CacheStats{Hits: 2, Misses: 2}); stats != expected {
+		t.Errorf("❌ Expected stats %+v, got %+v", expected, stats)
+	}
+}
+
+func TestNormalizationCacheErrors(t *testing.T) {
+	cache := NewNormalizationCache(defaultNormalizationFlags, LRUPolicy(0))
+	for range 2 {
+		if _, err := cache.Normalize("://invalid"); err == nil {
+			t.Error("❌ Expected error for invalid URL")
+		}
+	}
+	if stats, expected := cache.Stats(), (CacheStats{Misses: 2}); stats != expected || cache.Len() != 0 {
+		t.Errorf("❌ Expected stats %+v and empty cache, got %+v with %d URLs", expected, stats, cache.Len())
+	}
+}
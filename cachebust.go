@@ -0,0 +1,45 @@
+package main
+
+import (
+	"net/url"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+// Values of cache-busting parameters: content hashes, e.g. "?v=abc123de",
+// and Unix timestamps in seconds, e.g. "?_=1700000000"
+var (
+	contentHashPattern   = regexp.MustCompile(`^[0-9a-fA-F]{8,}$`)
+	unixTimestampPattern = regexp.MustCompile(`^[0-9]{10}$`)
+)
+
+// Remove cache-busting query parameter paramName from URL, if its value looks
+// like a content hash or a timestamp. CDNs and build tools append these to
+// force cache invalidation, so URLs differing by them point to the same
+// resource:
+//
+//	urlNormalizeContentHashQueryParam("https://example.com/app.js?v=abc123de", "v") // "https://example.com/app.js"
+//	urlNormalizeContentHashQueryParam("https://example.com/page?v=hello", "v")      // unchanged
+//
+// Other parameters keep their order and encoding.
+func urlNormalizeContentHashQueryParam(rawURL, paramName string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	if u.RawQuery == "" {
+		return u.String(), nil
+	}
+
+	params := strings.Split(u.RawQuery, "&")
+	params = slices.DeleteFunc(params, func(param string) bool {
+		key, value, _ := strings.Cut(param, "=")
+		key, keyErr := url.QueryUnescape(key)
+		value, valueErr := url.QueryUnescape(value)
+		return keyErr == nil && valueErr == nil && key == paramName &&
+			(contentHashPattern.MatchString(value) || unixTimestampPattern.MatchString(value))
+	})
+	u.RawQuery = strings.Join(params, "&")
+	return u.String(), nil
+}
@@ -0,0 +1,35 @@
+package main
+
+import "testing"
+
+func TestURLNormalizeContentHashQueryParam(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/app.js?v=abc123de":          "https://example.com/app.js",
+		"https://example.com/app.js?v=1234567890":        "https://example.com/app.js",
+		"https://example.com/app.js?v=ABC123DEF456&x=1":  "https://example.com/app.js?x=1",
+		"https://example.com/app.js?x=a%20b&v=abc123de":  "https://example.com/app.js?x=a%20b",
+		"https://example.com/app.js?v=hello":             "https://example.com/app.js?v=hello",
+		"https://example.com/app.js?v=abc123":            "https://example.com/app.js?v=abc123",
+		"https://example.com/app.js?v=123456789x":        "https://example.com/app.js?v=123456789x",
+		"https://example.com/app.js?version=abc123de":    "https://example.com/app.js?version=abc123de",
+		"https://example.com/app.js?x=1":                 "https://example.com/app.js?x=1",
+		"https://example.com/app.js#v=abc123de":          "https://example.com/app.js#v=abc123de",
+		"https://example.com/app.js?v=abc123de&v=latest": "https://example.com/app.js?v=latest",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlNormalizeContentHashQueryParam(input, "v"); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Timestamps of jQuery's cache: false
+	got, err := urlNormalizeContentHashQueryParam("https://example.com/api?q=1&_=1700000000", "_")
+	if expected := "https://example.com/api?q=1"; err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	if _, err := urlNormalizeContentHashQueryParam("://invalid", "v"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,167 @@
+package main
+
+import (
+	"fmt"
+	"html"
+	"path"
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Escape angle brackets ending the URL reference of a Link header value
+var linkHeaderURLEscaper = strings.NewReplacer("<", "%3C", ">", "%3E")
+
+// Normalize URL and format it as a value of the HTTP Link header pointing to
+// the canonical URL of a page, as in RFC 8288:
+//
+//	urlCanonicalLinkHeaderValue("https://Example.com/a?b=2&a=1", defaultNormalizationFlags)
+//	// `<https://example.com/a?a=1&b=2>; rel="canonical"`
+func urlCanonicalLinkHeaderValue(rawURL string, flags purell.NormalizationFlags) (string, error) {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return "", err
+	}
+	return "<" + linkHeaderURLEscaper.Replace(normalized) + `>; rel="canonical"`, nil
+}
+
+// Normalize URL and escape it for the content attribute of an og:url meta
+// tag, so "&" becomes "&amp;".
+func urlOpenGraphURL(rawURL string) (string, error) {
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	return html.EscapeString(normalized), nil
+}
+
+// Relation of a URL to the target of its canonical link, see
+// urlApplyCanonicalTagRules
+type CanonicalRelation int
+
+const (
+	CanonicalIsSelf CanonicalRelation = iota
+	CanonicalSameAfterNormalization
+	CanonicalIsParent
+	CanonicalIsDifferentOrigin
+	CanonicalConflict
+)
+
+func (r CanonicalRelation) String() string {
+	switch r {
+	case CanonicalIsSelf:
+		return "self"
+	case CanonicalSameAfterNormalization:
+		return "same after normalization"
+	case CanonicalIsParent:
+		return "parent"
+	case CanonicalIsDifferentOrigin:
+		return "different origin"
+	case CanonicalConflict:
+		return "conflict"
+	}
+	return fmt.Sprintf("CanonicalRelation(%d)", int(r))
+}
+
+// Determine how URL relates to the canonical URL declared by its page with
+// <link rel="canonical">, for finding misconfigured canonical tags.
+// Relative canonical URLs are resolved against URL, which must be absolute.
+//
+// The canonical URL is a parent when URL is under it, see urlIsSubsetOf,
+// such as when it drops tracking parameters or points to a section front.
+// Canonical URLs on another origin may be intended, e.g. for syndicated
+// articles, others are conflicts.
+func urlApplyCanonicalTagRules(rawURL, canonical string) (CanonicalRelation, error) {
+	if !urlIsAbsolute(rawURL) {
+		return CanonicalConflict, newURLParseError(rawURL, errMissingOrigin)
+	}
+	base, err := parseURL(rawURL)
+	if err != nil {
+		return CanonicalConflict, err
+	}
+	ref, err := parseURL(canonical)
+	if err != nil {
+		return CanonicalConflict, err
+	}
+	target := base.ResolveReference(ref).String()
+	if target == rawURL {
+		return CanonicalIsSelf, nil
+	}
+
+	// URL parsed already, so only normalizing the target can fail
+	samePage, err := urlIsSamePage(rawURL, target)
+	if err != nil {
+		return CanonicalConflict, err
+	}
+	sameOrigin, _ := urlIsSameDomain(rawURL, target)
+	parent, _ := urlIsSubsetOf(rawURL, target, true)
+	switch {
+	case samePage:
+		return CanonicalSameAfterNormalization, nil
+	case !sameOrigin:
+		return CanonicalIsDifferentOrigin, nil
+	case parent:
+		return CanonicalIsParent, nil
+	}
+	return CanonicalConflict, nil
+}
+
+// Normalization function registered for hosts matching a glob pattern
+type hostNormalizer struct {
+	pattern   string
+	normalize func(rawURL string) (string, error)
+}
+
+// Number of non-wildcard characters in host pattern, more is more specific
+func hostPatternSpecificity(pattern string) int {
+	return len(pattern) - strings.Count(pattern, "*") - strings.Count(pattern, "?")
+}
+
+// CanonicalURLResolver normalizes URLs with a normalization function selected
+// by their host, as sites may need different rules:
+//
+//	resolver := NewCanonicalURLResolver(normalizeURL)
+//	resolver.RegisterHostConfig("*.example.com", NewNormalizationPipeline().LowercaseHost().RemoveFragment().Build())
+//	resolver.RegisterHostConfig("legacy.example.com", NewURLNormalizer(purell.FlagsUsuallySafeGreedy).Normalize)
+//
+// Host patterns use path.Match syntax, where "*" also matches dots. If several
+// patterns match, the one with the most non-wildcard characters wins, and the
+// earliest registered of equally specific ones. URLs of other hosts use the
+// default normalization function.
+type CanonicalURLResolver struct {
+	fallback func(rawURL string) (string, error)
+	hosts    []hostNormalizer
+}
+
+func NewCanonicalURLResolver(fallback func(rawURL string) (string, error)) *CanonicalURLResolver {
+	return &CanonicalURLResolver{fallback: fallback}
+}
+
+// RegisterHostConfig registers normalization function for hosts matching the
+// pattern, which is matched against the lowercase host without port.
+func (r *CanonicalURLResolver) RegisterHostConfig(hostPattern string, normalize func(rawURL string) (string, error)) error {
+	hostPattern = strings.ToLower(hostPattern)
+	if _, err := path.Match(hostPattern, ""); err != nil {
+		return fmt.Errorf("host pattern %q: %w", hostPattern, err)
+	}
+	r.hosts = append(r.hosts, hostNormalizer{hostPattern, normalize})
+	return nil
+}
+
+// Resolve normalizes URL with the function registered for its host.
+func (r *CanonicalURLResolver) Resolve(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+
+	normalize, specificity := r.fallback, -1
+	for _, h := range r.hosts {
+		// Patterns were validated when registered
+		if matched, _ := path.Match(h.pattern, host); matched && hostPatternSpecificity(h.pattern) > specificity {
+			normalize, specificity = h.normalize, hostPatternSpecificity(h.pattern)
+		}
+	}
+	return normalize(rawURL)
+}
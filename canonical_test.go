@@ -0,0 +1,112 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+func TestURLCanonicalLinkHeaderValue(t *testing.T) {
+	tests := map[string]string{
+		"https://canonical.example.com":         `<https://canonical.example.com>; rel="canonical"`,
+		"HTTPS://Example.com:443/a/./b?z=1&a=2": `<https://example.com/a/b?a=2&z=1>; rel="canonical"`,
+		"https://example.com/?q=<b>":            `<https://example.com/?q=%3Cb%3E>; rel="canonical"`,
+	}
+
+	for input, expected := range tests {
+		if got, err := urlCanonicalLinkHeaderValue(input, defaultNormalizationFlags); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Flags select the normalization steps
+	got, err := urlCanonicalLinkHeaderValue("https://example.com/?b=2&a=1#top", purell.FlagLowercaseHost|purell.FlagRemoveFragment)
+	if expected := `<https://example.com/?b=2&a=1>; rel="canonical"`; err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	if _, err := urlCanonicalLinkHeaderValue("://invalid", defaultNormalizationFlags); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLOpenGraphURL(t *testing.T) {
+	tests := map[string]string{
+		"https://Example.com/article":      "https://example.com/article",
+		"https://example.com/?b=2&a=1":     "https://example.com/?a=1&amp;b=2",
+		"https://example.com/?q=%22x%22":   "https://example.com/?q=%22x%22",
+		"https://example.com/?a=1&b=2&c=3": "https://example.com/?a=1&amp;b=2&amp;c=3",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlOpenGraphURL(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	if _, err := urlOpenGraphURL("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestCanonicalURLResolver(t *testing.T) {
+	resolver := NewCanonicalURLResolver(normalizeURL)
+	if err := resolver.RegisterHostConfig("*.example.com", NewNormalizationPipeline().LowercaseHost().RemoveFragment().Build()); err != nil {
+		t.Fatalf("❌ Registering host config failed: %v", err)
+	}
+	if err := resolver.RegisterHostConfig("Legacy.example.com", NewNormalizationPipeline().LowercaseHost().RemoveMatrixParams().Build()); err != nil {
+		t.Fatalf("❌ Registering host config failed: %v", err)
+	}
+
+	tests := map[string]string{
+		"https://WWW.Example.com/a?b=2&a=1#top":                 "https://www.example.com/a?b=2&a=1",
+		"https://legacy.example.com/a;jsessionid=1?b=2&a=1#top": "https://legacy.example.com/a?b=2&a=1#top",
+		"https://example.com/?b=2&a=1#top":                      "https://example.com/?a=1&b=2#top",
+		"https://other.com:443/a?b=2&a=1":                       "https://other.com/a?a=1&b=2",
+	}
+
+	for input, expected := range tests {
+		if got, err := resolver.Resolve(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	if err := resolver.RegisterHostConfig("[example.com", normalizeURL); err == nil {
+		t.Error("❌ Expected error for malformed host pattern")
+	}
+	if _, err := resolver.Resolve("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLApplyCanonicalTagRules(t *testing.T) {
+	const rawURL = "https://example.com/news/article?id=42&utm_source=feed"
+	tests := []struct {
+		canonical string
+		expected  CanonicalRelation
+	}{
+		{rawURL, CanonicalIsSelf},
+		{"HTTPS://Example.com/news/article?utm_source=feed&id=42", CanonicalSameAfterNormalization},
+		{"https://example.com:443/news/./article?id=42&utm_source=feed#top", CanonicalSameAfterNormalization},
+		{"https://example.com/news/article?id=42", CanonicalIsParent},
+		{"/news/article", CanonicalIsParent},
+		{"https://example.com/", CanonicalIsParent},
+		{"https://cdn.example.com/news/article?id=42", CanonicalIsDifferentOrigin},
+		{"http://example.com/news/article?id=42&utm_source=feed", CanonicalIsDifferentOrigin},
+		{"https://example.com/news/other", CanonicalConflict},
+		{"https://example.com/news/article?id=43", CanonicalConflict},
+	}
+
+	for _, test := range tests {
+		if got, err := urlApplyCanonicalTagRules(rawURL, test.canonical); err != nil || got != test.expected {
+			t.Errorf("❌ Expected %v for canonical %q, got %v (error: %v)", test.expected, test.canonical, got, err)
+		}
+	}
+
+	if _, err := urlApplyCanonicalTagRules("/relative", "https://example.com/"); err == nil {
+		t.Error("❌ Expected error for relative URL")
+	}
+	if _, err := urlApplyCanonicalTagRules(rawURL, "://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid canonical URL")
+	}
+}
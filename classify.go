@@ -0,0 +1,141 @@
+package main
+
+import (
+	"fmt"
+	"path"
+	"slices"
+	"strings"
+)
+
+// Content type of a URL guessed by urlClassify
+type URLClass int
+
+const (
+	ClassHTMLPage URLClass = iota
+	ClassImage
+	ClassVideo
+	ClassAudio
+	ClassScript
+	ClassStylesheet
+	ClassFeed
+	ClassAPI
+	ClassOther
+)
+
+func (c URLClass) String() string {
+	switch c {
+	case ClassHTMLPage:
+		return "html page"
+	case ClassImage:
+		return "image"
+	case ClassVideo:
+		return "video"
+	case ClassAudio:
+		return "audio"
+	case ClassScript:
+		return "script"
+	case ClassStylesheet:
+		return "stylesheet"
+	case ClassFeed:
+		return "feed"
+	case ClassAPI:
+		return "api"
+	case ClassOther:
+		return "other"
+	}
+	return fmt.Sprintf("URLClass(%d)", int(c))
+}
+
+// Class and MIME type by lowercase path extension
+var urlClassExtensions = map[string]struct {
+	class URLClass
+	mime  string
+}{
+	".html":  {ClassHTMLPage, "text/html"},
+	".htm":   {ClassHTMLPage, "text/html"},
+	".xhtml": {ClassHTMLPage, "application/xhtml+xml"},
+	".php":   {ClassHTMLPage, "text/html"},
+	".asp":   {ClassHTMLPage, "text/html"},
+	".aspx":  {ClassHTMLPage, "text/html"},
+	".jsp":   {ClassHTMLPage, "text/html"},
+	".jpg":   {ClassImage, "image/jpeg"},
+	".jpeg":  {ClassImage, "image/jpeg"},
+	".png":   {ClassImage, "image/png"},
+	".gif":   {ClassImage, "image/gif"},
+	".webp":  {ClassImage, "image/webp"},
+	".avif":  {ClassImage, "image/avif"},
+	".svg":   {ClassImage, "image/svg+xml"},
+	".ico":   {ClassImage, "image/vnd.microsoft.icon"},
+	".mp4":   {ClassVideo, "video/mp4"},
+	".webm":  {ClassVideo, "video/webm"},
+	".mov":   {ClassVideo, "video/quicktime"},
+	".m3u8":  {ClassVideo, "application/vnd.apple.mpegurl"},
+	".mp3":   {ClassAudio, "audio/mpeg"},
+	".m4a":   {ClassAudio, "audio/mp4"},
+	".ogg":   {ClassAudio, "audio/ogg"},
+	".wav":   {ClassAudio, "audio/wav"},
+	".flac":  {ClassAudio, "audio/flac"},
+	".js":    {ClassScript, "text/javascript"},
+	".mjs":   {ClassScript, "text/javascript"},
+	".css":   {ClassStylesheet, "text/css"},
+	".rss":   {ClassFeed, "application/rss+xml"},
+	".atom":  {ClassFeed, "application/atom+xml"},
+	".xml":   {ClassFeed, "application/xml"},
+	".json":  {ClassAPI, "application/json"},
+	".pdf":   {ClassOther, "application/pdf"},
+	".zip":   {ClassOther, "application/zip"},
+	".txt":   {ClassOther, "text/plain"},
+}
+
+// Path segments and host labels hinting at API endpoints and feeds, for paths
+// without an extension
+var (
+	apiHints  = []string{"api", "graphql"}
+	feedHints = []string{"feed", "rss", "atom"}
+)
+
+// Guess content type of URL from its path extension and known patterns.
+//
+// This is a heuristic for crawlers skipping media URLs, not a definitive
+// classification. Paths without an extension are HTML pages, unless they look
+// like API endpoints or feeds, and unknown extensions are ClassOther.
+func urlClassify(rawURL string) (URLClass, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return ClassOther, err
+	}
+	if ext := strings.ToLower(path.Ext(u.Path)); ext != "" {
+		if entry, ok := urlClassExtensions[ext]; ok {
+			return entry.class, nil
+		}
+		return ClassOther, nil
+	}
+
+	segments := splitPathSegments(strings.ToLower(u.Path))
+	hostLabel, _, _ := strings.Cut(strings.ToLower(u.Hostname()), ".")
+	switch {
+	case slices.Contains(apiHints, hostLabel) || slices.ContainsFunc(segments, func(s string) bool { return slices.Contains(apiHints, s) }):
+		return ClassAPI, nil
+	case len(segments) > 0 && slices.Contains(feedHints, segments[len(segments)-1]):
+		return ClassFeed, nil
+	}
+	return ClassHTMLPage, nil
+}
+
+// Guess MIME type of URL from its path extension. Paths without an extension
+// are text/html when urlClassify considers them HTML pages. Returns false if
+// there is no hint, or the URL can't be parsed.
+func mimeTypeHint(rawURL string) (string, bool) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", false
+	}
+	if ext := strings.ToLower(path.Ext(u.Path)); ext != "" {
+		entry, ok := urlClassExtensions[ext]
+		return entry.mime, ok
+	}
+	if class, _ := urlClassify(rawURL); class == ClassHTMLPage {
+		return "text/html", true
+	}
+	return "", false
+}
@@ -0,0 +1,64 @@
+package main
+
+import (
+	"testing"
+)
+
+func TestURLClassify(t *testing.T) {
+	tests := map[string]URLClass{
+		"https://example.com/":                      ClassHTMLPage,
+		"https://example.com/news/article-123":      ClassHTMLPage,
+		"https://example.com/news/article-123/":     ClassHTMLPage,
+		"https://example.com/index.html?x=1":        ClassHTMLPage,
+		"https://example.com/v1.2/page":             ClassHTMLPage,
+		"https://example.com/images/photo.jpg":      ClassImage,
+		"https://example.com/images/PHOTO.JPEG#x":   ClassImage,
+		"https://example.com/logo.svg":              ClassImage,
+		"https://example.com/videos/clip.mp4":       ClassVideo,
+		"https://example.com/podcast/episode.mp3":   ClassAudio,
+		"https://example.com/static/app.js":         ClassScript,
+		"https://example.com/static/style.css":      ClassStylesheet,
+		"https://example.com/sitemap.xml":           ClassFeed,
+		"https://example.com/news/feed":             ClassFeed,
+		"https://example.com/feed.rss":              ClassFeed,
+		"https://example.com/data.json":             ClassAPI,
+		"https://example.com/api/v1/users":          ClassAPI,
+		"https://api.example.com/users":             ClassAPI,
+		"https://example.com/graphql":               ClassAPI,
+		"https://example.com/files/report.pdf":      ClassOther,
+		"https://example.com/files/archive.unknown": ClassOther,
+	}
+
+	for input, expected := range tests {
+		if class, err := urlClassify(input); err != nil || class != expected {
+			t.Errorf("❌ Expected %v for %q, got %v (error: %v)", expected, input, class, err)
+		}
+	}
+
+	if _, err := urlClassify("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestMimeTypeHint(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/":              "text/html",
+		"https://example.com/a/page.HTML":   "text/html",
+		"https://example.com/photo.jpg":     "image/jpeg",
+		"https://example.com/clip.mp4":      "video/mp4",
+		"https://example.com/data.json":     "application/json",
+		"https://example.com/sitemap.xml":   "application/xml",
+		"https://example.com/files/doc.pdf": "application/pdf",
+	}
+	for input, expected := range tests {
+		if mime, ok := mimeTypeHint(input); !ok || mime != expected {
+			t.Errorf("❌ Expected %q for %q, got %q", expected, input, mime)
+		}
+	}
+
+	for _, input := range []string{"https://example.com/archive.unknown", "https://example.com/api/users", "://invalid"} {
+		if mime, ok := mimeTypeHint(input); ok {
+			t.Errorf("❌ Expected no hint for %q, got %q", input, mime)
+		}
+	}
+}
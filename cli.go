@@ -5,43 +5,414 @@
 package main
 
 import (
+	"bufio"
+	"bytes"
+	"errors"
 	"flag"
 	"fmt"
+	"io"
 	"os"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
 )
 
+// Buffer size for writing results into output file
+const outputBufferSize = 64 * 1024 // 64KB
+
+// Opens URL in the default browser for -open, set in builds with the browser
+// tag
+var browserOpener func(rawURL string) error
+
 func main() {
-	configPath := flag.String("config", "", "Path to YAML configuration file")
-	urlInput := flag.String("url", "", "URL to process")
-	signFlag := flag.Bool("sign", false, "Generate signature of the final URL")
-	flag.Parse()
-
-	if *urlInput == "" {
-		fmt.Println("URL input is required")
-		flag.PrintDefaults()
-		os.Exit(1)
-	}
-	if configPath != nil && *configPath != "" {
-		DefaultCfgData = mustReadConfig(*configPath)
-		fmt.Printf("Loaded config from %s\n", *configPath)
+	os.Exit(run(os.Args[1:], os.Stdin, os.Stdout, os.Stderr))
+}
+
+// Escape characters that would break TSV columns or rows. URLs can't
+// normally contain these unescaped, but input isn't trusted to be valid.
+var tsvEscaper = strings.NewReplacer("\t", "%09", "\n", "%0A", "\r", "%0D")
+
+// Exit codes and WASI status codes documented in usage text
+const usageExitCodes = `
+Exit codes:
+  0  All URLs were processed successfully
+  1  Invalid arguments or configuration, or processing of some URL failed.
+     Failed URLs from -file or -stdin are only skipped, unless -strict, -exit-on-error
+     or -dry-run is given. Failure of -url always exits 1.
+
+Status codes of the WASI module exports writing into an output buffer:
+  >= 0  Length of the result written into the output buffer
+  -1    Invalid pointer or input, such as an unknown hash algorithm
+  -2    URL can't be parsed or normalized, or component can't be decoded
+  -3    Output buffer is too small for the result
+  -10   SanitizeURL: URL is longer than 8192 bytes
+  -11   SanitizeURL: URL contains a null byte
+  -12   SanitizeURL: URL uses javascript: scheme
+  -13   SanitizeURL: URL uses data: scheme
+  -14   SanitizeURL: URL path is nested deeper than 50 segments
+  GetSignature, GetSignatureWith and NormalizeAndHashURL set bit 31 of the
+  returned length on error, the result is then the error message.
+`
+
+// Run the CLI with given arguments, returns the exit code
+func run(args []string, stdin io.Reader, stdout, stderr io.Writer) int {
+	flags := flag.NewFlagSet("suola", flag.ContinueOnError)
+	flags.Usage = func() {
+		fmt.Fprintln(flags.Output(), "Usage: suola -url <url> [flags]")
+		fmt.Fprintln(flags.Output(), "       suola -file <path> [flags]")
+		fmt.Fprintln(flags.Output(), "\nNormalize URLs using site rules, and optionally sign them.\n\nFlags:")
+		flags.PrintDefaults()
+		fmt.Fprint(flags.Output(), usageExitCodes)
+	}
+	configPath := flags.String("config", "", "Path to YAML configuration file")
+	urlInput := flags.String("url", "", "URL to process")
+	fileInput := flags.String("file", "", "Path to file with URLs to process, one per line")
+	stdinInput := flags.Bool("stdin", false, "Read URLs to process from stdin, one per line")
+	signFlag := flags.Bool("sign", false, "Generate signature of the final URL, same as -hash-algorithm="+defaultHashAlgorithm)
+	hashAlgorithm := flags.String("hash-algorithm", defaultHashAlgorithm, "Hash algorithm for the signature, see -list-algorithms")
+	listAlgorithms := flags.Bool("list-algorithms", false, "Print supported hash algorithms and exit")
+	normalizeOnly := flags.Bool("normalize-only", false, "Only output the formatted URL, without signature")
+	hashOnly := flags.Bool("hash-only", false, "Only output the signature of the URL as-is, without normalization")
+	outputFile := flags.String("output-file", "", "Write results to file instead of stdout")
+	appendFlag := flags.Bool("append", false, "Append to output file instead of truncating it")
+	dryRun := flags.Bool("dry-run", false, "Only validate URLs without writing output, exit 1 if any is invalid")
+	exitOnError := flags.Bool("exit-on-error", false, "Stop processing at the first failed URL and exit 1")
+	continueOnError := flags.Bool("continue-on-error", false, "Skip failed URLs and report them to stderr (default)")
+	strict := flags.Bool("strict", false, "Exit 1 if any URL failed, also with -continue-on-error")
+	verifyHash := flags.String("verify", "", "Check that -url signs to the given signature using -hash-algorithm, exit 1 if not")
+	profile := flags.Bool("profile", false, "Print timing of each normalization step of -url to stderr")
+	benchmark := flags.Bool("benchmark", false, "Normalize -url repeatedly and print throughput to stderr")
+	iterations := flags.Int("iterations", 10000, "Number of normalizations with -benchmark")
+	tsv := flags.Bool("tsv", false, "Output tab-separated columns: input, formatted URL and signature if any")
+	openFlag := flags.Bool("open", false, "Open the formatted -url in the default browser, requires the browser build tag")
+	verbose := flags.Bool("verbose", false, "Print details such as the hash algorithm used to stderr")
+
+	// Print help to stdout, but parse errors to stderr
+	var parseOutput bytes.Buffer
+	flags.SetOutput(&parseOutput)
+	parseErr := flags.Parse(args)
+	flags.SetOutput(stderr)
+	if errors.Is(parseErr, flag.ErrHelp) {
+		stdout.Write(parseOutput.Bytes())
+		return 0
+	} else if parseErr != nil {
+		stderr.Write(parseOutput.Bytes())
+		return 1
+	}
+
+	if *listAlgorithms {
+		for _, name := range hashAlgorithmNames() {
+			fmt.Fprintln(stdout, name)
+		}
+		return 0
+	}
+
+	// Setting the algorithm explicitly implies signing
+	flags.Visit(func(f *flag.Flag) {
+		if f.Name == "hash-algorithm" {
+			*signFlag = true
+		}
+	})
+	if _, ok := hashAlgorithms[*hashAlgorithm]; !ok {
+		fmt.Fprintf(stderr, "Unknown hash algorithm: %s\n", *hashAlgorithm)
+		return 1
+	}
+	if *normalizeOnly && (*signFlag || *hashOnly) {
+		fmt.Fprintln(stderr, "-normalize-only can't be combined with -sign, -hash-algorithm or -hash-only")
+		return 1
+	}
+	if *exitOnError && *continueOnError {
+		fmt.Fprintln(stderr, "-exit-on-error can't be combined with -continue-on-error")
+		return 1
+	}
+	if *verifyHash != "" {
+		if err := validateHash(*verifyHash, hashAlgorithms[*hashAlgorithm]().Size()); err != nil {
+			fmt.Fprintf(stderr, "Invalid -verify signature for %s: %v\n", *hashAlgorithm, err)
+			return 1
+		}
+		if *urlInput == "" || *fileInput != "" || *stdinInput {
+			fmt.Fprintln(stderr, "-verify requires a single -url")
+			return 1
+		}
+	}
+	if *profile && (*urlInput == "" || *fileInput != "" || *stdinInput) {
+		fmt.Fprintln(stderr, "-profile requires a single -url")
+		return 1
+	}
+	if *openFlag {
+		if browserOpener == nil {
+			fmt.Fprintln(stderr, "-open requires building with the browser tag")
+			return 1
+		}
+		if *urlInput == "" || *fileInput != "" || *stdinInput {
+			fmt.Fprintln(stderr, "-open requires a single -url")
+			return 1
+		}
+	}
+	if *benchmark {
+		if *urlInput == "" || *fileInput != "" || *stdinInput {
+			fmt.Fprintln(stderr, "-benchmark requires a single -url")
+			return 1
+		}
+		if *iterations <= 0 {
+			fmt.Fprintln(stderr, "-iterations must be positive")
+			return 1
+		}
+	}
+	if !*normalizeOnly && !*hashOnly && !*signFlag && *verifyHash == "" && !*benchmark {
+		fmt.Fprintln(stderr, "Deprecated: implicit normalization mode will be removed in 2.0, use -normalize-only instead")
+	}
+
+	if *urlInput == "" && *fileInput == "" && !*stdinInput {
+		fmt.Fprintln(stderr, "URL input is required")
+		flags.PrintDefaults()
+		return 1
+	}
+	cfgData := DefaultCfgData
+	if *configPath != "" {
+		cfgData = mustReadConfig(*configPath)
+		fmt.Fprintf(stderr, "Loaded config from %s\n", *configPath)
 	} else {
-		fmt.Printf("Using inbuild config with %d bytes\n", len(DefaultCfgData))
+		fmt.Fprintf(stderr, "Using inbuild config with %d bytes\n", len(cfgData))
 	}
-	err := LoadRules(DefaultCfgData)
+	err := LoadRules(cfgData)
 	if err != nil {
-		fmt.Printf("Failed to load config: %v", err)
-		os.Exit(1)
+		fmt.Fprintf(stderr, "Failed to load config: %v\n", err)
+		return 1
 	} else {
-		fmt.Printf("Loaded config with %d sites", len(Rules.Sites))
+		fmt.Fprintf(stderr, "Loaded config with %d sites\n", len(Rules.Sites))
+	}
+	if *verbose && (*signFlag || *hashOnly) {
+		fmt.Fprintf(stderr, "Using hash algorithm %s\n", *hashAlgorithm)
 	}
 
-	formattedURL, err := processURL(*urlInput)
-	if err != nil {
-		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
-		os.Exit(1)
+	if *benchmark {
+		if err := runBenchmark(stderr, *urlInput, *iterations); err != nil {
+			fmt.Fprintf(stderr, "Error: %s: %v\n", *urlInput, err)
+			return 1
+		}
+		return 0
+	}
+
+	if *verifyHash != "" {
+		formattedURL, err := processURL(*urlInput)
+		if err != nil {
+			fmt.Fprintf(stderr, "Error: %s: %v\n", *urlInput, err)
+			return 1
+		}
+		signature, err := generateSignatureWith(formattedURL, *hashAlgorithm)
+		if err != nil {
+			fmt.Fprintf(stderr, "Error: %s: %v\n", *urlInput, err)
+			return 1
+		}
+		if signature != *verifyHash {
+			fmt.Fprintf(stderr, "Signature mismatch for %s: expected %s, got %s\n", formattedURL, *verifyHash, signature)
+			return 1
+		}
+		fmt.Fprintln(stdout, "Signature OK:", *verifyHash)
+		return 0
 	}
-	fmt.Println("Formatted URL:", formattedURL)
-	if *signFlag {
-		fmt.Println("Signature:", generateSignature(formattedURL))
+
+	var out io.Writer = stdout
+	var outBuffer *bufio.Writer
+	if *outputFile != "" {
+		mode := os.O_WRONLY | os.O_CREATE | os.O_TRUNC
+		if *appendFlag {
+			mode = os.O_WRONLY | os.O_CREATE | os.O_APPEND
+		}
+		f, err := os.OpenFile(*outputFile, mode, 0o644)
+		if err != nil {
+			fmt.Fprintf(stderr, "Failed to open output file: %v\n", err)
+			return 1
+		}
+		defer f.Close()
+		outBuffer = bufio.NewWriterSize(f, outputBufferSize)
+		defer outBuffer.Flush()
+		out = outBuffer
+	}
+
+	processed, failed := 0, 0
+	// Write result in selected output format, empty values are omitted
+	writeResult := func(rawURL, formattedURL, signature string) {
+		if *tsv {
+			row := []string{tsvEscaper.Replace(rawURL), tsvEscaper.Replace(formattedURL)}
+			if signature != "" {
+				row = append(row, signature)
+			}
+			fmt.Fprintln(out, strings.Join(row, "\t"))
+			return
+		}
+		if formattedURL != "" {
+			fmt.Fprintln(out, "Formatted URL:", formattedURL)
+		}
+		if signature != "" {
+			fmt.Fprintln(out, "Signature:", signature)
+		}
 	}
+
+	// Process single URL, returns false if it failed
+	handleURL := func(rawURL string) bool {
+		processed++
+		if *hashOnly {
+			if !*dryRun {
+				signature, _ := generateSignatureWith(rawURL, *hashAlgorithm)
+				writeResult(rawURL, "", signature)
+			}
+			return true
+		}
+		formattedURL, err := processURL(rawURL)
+		if err != nil {
+			failed++
+			fmt.Fprintf(stderr, "Error: %s: %v\n", rawURL, err)
+			return false
+		}
+		if *dryRun {
+			return true
+		}
+		var signature string
+		if *signFlag {
+			// Algorithm is validated above
+			signature, _ = generateSignatureWith(formattedURL, *hashAlgorithm)
+		}
+		writeResult(rawURL, formattedURL, signature)
+		return true
+	}
+
+	stopped := false
+	// Process URLs from reader until the end, or first failure with -exit-on-error
+	handleLines := func(r io.Reader) error {
+		for line, err := range readURLLines(r) {
+			if err != nil {
+				return err
+			}
+			if !handleURL(line) && *exitOnError {
+				stopped = true
+				break
+			}
+		}
+		return nil
+	}
+
+	// Failure of -url isn't skipped, even with -file or -stdin
+	urlFailed := false
+	if *urlInput != "" {
+		urlFailed = !handleURL(*urlInput)
+		stopped = urlFailed && *exitOnError
+	}
+	if *fileInput != "" && !stopped {
+		f, err := os.Open(*fileInput)
+		if err != nil {
+			fmt.Fprintf(stderr, "Failed to open input file: %v\n", err)
+			return 1
+		}
+		defer f.Close()
+		if err := handleLines(f); err != nil {
+			fmt.Fprintf(stderr, "Failed to read input file: %v\n", err)
+			return 1
+		}
+	}
+	if *stdinInput && !stopped {
+		if err := handleLines(stdin); err != nil {
+			fmt.Fprintf(stderr, "Failed to read stdin: %v\n", err)
+			return 1
+		}
+	}
+
+	if outBuffer != nil {
+		if err := outBuffer.Flush(); err != nil {
+			fmt.Fprintf(stderr, "Failed to write output file: %v\n", err)
+			return 1
+		}
+	}
+
+	if *openFlag && failed == 0 {
+		formattedURL, _ := processURL(*urlInput)
+		if err := browserOpener(formattedURL); err != nil {
+			fmt.Fprintf(stderr, "Failed to open browser: %v\n", err)
+			return 1
+		}
+	}
+
+	if *profile {
+		if err := printProfile(stderr, *urlInput, defaultNormalizationFlags); err != nil {
+			fmt.Fprintf(stderr, "Failed to profile: %v\n", err)
+		}
+	}
+
+	fmt.Fprintf(stderr, "Processed %d URLs, %d errors\n", processed, failed)
+	// Failed URLs in a file or stdin are only skipped, unless told to be strict
+	batch := *fileInput != "" || *stdinInput
+	skipFailures := batch && !*exitOnError && !*dryRun && !*strict
+	if urlFailed || failed > 0 && !skipFailures {
+		return 1
+	}
+	return 0
+}
+
+// Number of times each normalization step is run with -profile
+const profileIterations = 1000
+
+// Run normalization steps of URL repeatedly, and print a table of their
+// timings
+func printProfile(w io.Writer, rawURL string, flags purell.NormalizationFlags) error {
+	type stepTiming struct {
+		name  string
+		calls int
+		total time.Duration
+	}
+	var timings []stepTiming
+	for range profileIterations {
+		steps, err := normalizationSteps(rawURL, flags)
+		if err != nil {
+			return err
+		}
+		if timings == nil {
+			timings = make([]stepTiming, len(steps))
+		}
+		for i, step := range steps {
+			timings[i].name = step.Name
+			timings[i].calls++
+			timings[i].total += step.Elapsed
+		}
+	}
+
+	table := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(table, "Step\tCalls\tTotal\tAverage")
+	for _, timing := range timings {
+		fmt.Fprintf(table, "%s\t%d\t%v\t%v\n", timing.name, timing.calls, timing.total, timing.total/time.Duration(timing.calls))
+	}
+	return table.Flush()
+}
+
+// Keeps benchmarked results alive, so the calls aren't optimized away
+var benchmarkSink string
+
+// Process URL the given number of times, and print throughput and average
+// latency. Time of the loop itself is measured separately and subtracted.
+func runBenchmark(w io.Writer, rawURL string, iterations int) error {
+	// Fail early instead of timing errors
+	if _, err := processURL(rawURL); err != nil {
+		return err
+	}
+
+	start := time.Now()
+	for range iterations {
+		benchmarkSink, _ = processURL(rawURL)
+	}
+	elapsed := time.Since(start)
+
+	start = time.Now()
+	for range iterations {
+		benchmarkSink = rawURL
+	}
+	elapsed -= time.Since(start)
+	elapsed = max(elapsed, time.Nanosecond)
+
+	opsPerSec := float64(iterations) / elapsed.Seconds()
+	fmt.Fprintf(w, "Benchmark: %d iterations in %v\n", iterations, elapsed)
+	fmt.Fprintf(w, "Throughput: %.0f ops/sec\n", opsPerSec)
+	fmt.Fprintf(w, "Average latency: %v\n", elapsed/time.Duration(iterations))
+	return nil
 }
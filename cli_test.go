@@ -0,0 +1,377 @@
+//go:build !js && !wasip1
+// +build !js,!wasip1
+
+package main
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strconv"
+	"strings"
+	"testing"
+)
+
+const (
+	testCLIURL       = "https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"
+	testCLISignature = "7e530349c32069a7dc25485ee2886f8f88e4b8560202fec1cb3200bd8c550b4c"
+)
+
+// Run CLI with arguments, returning exit code, stdout and stderr
+func runCLI(args ...string) (int, string, string) {
+	return runCLIWithStdin("", args...)
+}
+
+// Run CLI with given stdin and arguments
+func runCLIWithStdin(stdin string, args ...string) (int, string, string) {
+	var stdout, stderr bytes.Buffer
+	code := run(args, strings.NewReader(stdin), &stdout, &stderr)
+	return code, stdout.String(), stderr.String()
+}
+
+// Write lines into a file in test temp dir
+func writeTestFile(t *testing.T, name string, lines ...string) string {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), name)
+	if err := os.WriteFile(path, []byte(strings.Join(lines, "\n")+"\n"), 0o644); err != nil {
+		t.Fatalf("❌ Failed to write %s: %v", path, err)
+	}
+	return path
+}
+
+func TestCLIOutputFile(t *testing.T) {
+	input := writeTestFile(t, "urls.txt", testCLIURL, "", "https://unknown.example/")
+	output := filepath.Join(t.TempDir(), "output.txt")
+
+	code, stdout, stderr := runCLI("-file", input, "-output-file", output, "-sign")
+	if code != 0 {
+		t.Errorf("❌ Expected exit code 0 for skipped URL, got %d", code)
+	}
+	if stdout != "" {
+		t.Errorf("❌ Expected no output on stdout, got: %s", stdout)
+	}
+	if !strings.Contains(stderr, "Processed 2 URLs, 1 errors") {
+		t.Errorf("❌ Missing summary line in stderr: %s", stderr)
+	}
+
+	expected := "Formatted URL: " + testCLIURL + "\nSignature: " + testCLISignature + "\n"
+	data, err := os.ReadFile(output)
+	if err != nil {
+		t.Fatalf("❌ Failed to read output file: %v", err)
+	}
+	if string(data) != expected {
+		t.Fatalf("❌ Output file mismatch\nExpected: %q\nGot: %q", expected, string(data))
+	}
+
+	// Truncate by default, append when requested
+	runCLI("-url", testCLIURL, "-output-file", output)
+	runCLI("-url", testCLIURL, "-output-file", output, "-append")
+	data, err = os.ReadFile(output)
+	if err != nil {
+		t.Fatalf("❌ Failed to read output file: %v", err)
+	}
+	expected = strings.Repeat("Formatted URL: "+testCLIURL+"\n", 2)
+	if string(data) != expected {
+		t.Fatalf("❌ Output file mismatch after append\nExpected: %q\nGot: %q", expected, string(data))
+	}
+}
+
+func TestCLIDryRun(t *testing.T) {
+	input := writeTestFile(t, "urls.txt", testCLIURL, "not a url")
+
+	code, stdout, stderr := runCLI("-file", input, "-dry-run", "-sign")
+	if code != 1 {
+		t.Errorf("❌ Expected exit code 1, got %d", code)
+	}
+	if stdout != "" {
+		t.Errorf("❌ Expected no output in dry run, got: %s", stdout)
+	}
+	if !strings.Contains(stderr, "Processed 2 URLs, 1 errors") {
+		t.Errorf("❌ Missing error count in stderr: %s", stderr)
+	}
+
+	input = writeTestFile(t, "valid.txt", testCLIURL)
+	if code, stdout, _ := runCLI("-file", input, "-dry-run"); code != 0 || stdout != "" {
+		t.Errorf("❌ Expected exit code 0 and no output, got %d: %s", code, stdout)
+	}
+}
+
+func TestCLIHashAlgorithm(t *testing.T) {
+	code, stdout, _ := runCLI("-list-algorithms")
+	if code != 0 || stdout != strings.Join(hashAlgorithmNames(), "\n")+"\n" {
+		t.Fatalf("❌ Unexpected algorithm list (exit code %d): %s", code, stdout)
+	}
+
+	for _, algorithm := range hashAlgorithmNames() {
+		t.Run(algorithm, func(t *testing.T) {
+			expected, err := generateSignatureWith(testCLIURL, algorithm)
+			if err != nil {
+				t.Fatalf("❌ Failed to hash with %s: %v", algorithm, err)
+			}
+			code, stdout, stderr := runCLI("-url", testCLIURL, "-hash-algorithm", algorithm)
+			if code != 0 || !strings.Contains(stdout, "Signature: "+expected+"\n") {
+				t.Errorf("❌ Expected signature %s (exit code %d), got: %s", expected, code, stdout)
+			}
+			if strings.Contains(stderr, "Using hash algorithm") {
+				t.Errorf("❌ Algorithm reported in stderr without -verbose: %s", stderr)
+			}
+			if _, _, stderr := runCLI("-url", testCLIURL, "-hash-algorithm", algorithm, "-verbose"); !strings.Contains(stderr, "Using hash algorithm "+algorithm) {
+				t.Errorf("❌ Algorithm not reported in stderr with -verbose: %s", stderr)
+			}
+		})
+	}
+
+	// -sign is a synonym for -hash-algorithm=sha256
+	if _, stdout, _ := runCLI("-url", testCLIURL, "-sign"); !strings.Contains(stdout, "Signature: "+testCLISignature) {
+		t.Errorf("❌ Expected sha256 signature with -sign, got: %s", stdout)
+	}
+	if code, _, _ := runCLI("-url", testCLIURL, "-hash-algorithm", "md4"); code != 1 {
+		t.Errorf("❌ Expected exit code 1 for unknown algorithm, got %d", code)
+	}
+}
+
+func TestCLIModes(t *testing.T) {
+	rawURL := testCLIURL + "?utm_source=test"
+	rawSignature, _ := generateSignatureWith(rawURL, defaultHashAlgorithm)
+
+	tests := []struct {
+		name     string
+		args     []string
+		expected string
+	}{
+		{"normalize-only", []string{"-normalize-only"}, "Formatted URL: " + testCLIURL + "\n"},
+		{"hash-only", []string{"-hash-only"}, "Signature: " + rawSignature + "\n"},
+		{"sign", []string{"-sign"}, "Formatted URL: " + testCLIURL + "\nSignature: " + testCLISignature + "\n"},
+		{"implicit", nil, "Formatted URL: " + testCLIURL + "\n"},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			code, stdout, stderr := runCLI(append([]string{"-url", rawURL}, test.args...)...)
+			if code != 0 || stdout != test.expected {
+				t.Errorf("❌ Expected %q (exit code %d), got: %q", test.expected, code, stdout)
+			}
+			if deprecated := strings.Contains(stderr, "Deprecated:"); deprecated != (test.args == nil) {
+				t.Errorf("❌ Unexpected deprecation notice state in stderr: %s", stderr)
+			}
+		})
+	}
+
+	if code, _, _ := runCLI("-url", rawURL, "-normalize-only", "-sign"); code != 1 {
+		t.Errorf("❌ Expected exit code 1 for conflicting modes, got %d", code)
+	}
+}
+
+func TestCLIHelp(t *testing.T) {
+	for _, arg := range []string{"-h", "-help", "--help"} {
+		code, stdout, _ := runCLI(arg)
+		if code != 0 {
+			t.Errorf("❌ Expected exit code 0 for %s, got %d", arg, code)
+		}
+		for _, expected := range []string{"Usage:", "-url", "-output-file", "Exit codes:", "-3    Output buffer is too small", "-14   SanitizeURL"} {
+			if !strings.Contains(stdout, expected) {
+				t.Errorf("❌ Help for %s is missing %q: %s", arg, expected, stdout)
+			}
+		}
+	}
+
+	code, stdout, stderr := runCLI("-unknown-flag")
+	if code != 1 || stdout != "" || !strings.Contains(stderr, "-unknown-flag") {
+		t.Errorf("❌ Expected parse error on stderr with exit code 1, got %d: %s", code, stderr)
+	}
+}
+
+func TestCLIErrorHandling(t *testing.T) {
+	const invalidURL = "https://unknown.example/"
+	otherURL := testCLIURL + "?utm_source=test"
+	input := writeTestFile(t, "urls.txt", testCLIURL, invalidURL, otherURL)
+
+	tests := []struct {
+		name     string
+		args     []string
+		code     int
+		expected string
+	}{
+		{"default", nil, 0, strings.Repeat("Formatted URL: "+testCLIURL+"\n", 2)},
+		{"continue-on-error", []string{"-continue-on-error"}, 0, strings.Repeat("Formatted URL: "+testCLIURL+"\n", 2)},
+		{"strict", []string{"-continue-on-error", "-strict"}, 1, strings.Repeat("Formatted URL: "+testCLIURL+"\n", 2)},
+		{"exit-on-error", []string{"-exit-on-error"}, 1, "Formatted URL: " + testCLIURL + "\n"},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			code, stdout, stderr := runCLI(append([]string{"-file", input, "-normalize-only"}, test.args...)...)
+			if code != test.code {
+				t.Errorf("❌ Expected exit code %d, got %d", test.code, code)
+			}
+			if stdout != test.expected {
+				t.Errorf("❌ Expected output %q, got %q", test.expected, stdout)
+			}
+			if !strings.Contains(stderr, "Error: "+invalidURL+":") {
+				t.Errorf("❌ Failed URL not reported in stderr: %s", stderr)
+			}
+		})
+	}
+
+	if code, _, _ := runCLI("-file", input, "-exit-on-error", "-continue-on-error"); code != 1 {
+		t.Errorf("❌ Expected exit code 1 for conflicting flags, got %d", code)
+	}
+	// Single URL failure always fails
+	if code, _, _ := runCLI("-url", invalidURL, "-normalize-only"); code != 1 {
+		t.Errorf("❌ Expected exit code 1 for failed URL, got %d", code)
+	}
+	// Also when combined with a file, whose URLs are processed still
+	validInput := writeTestFile(t, "valid.txt", testCLIURL)
+	if code, stdout, _ := runCLI("-url", invalidURL, "-file", validInput, "-normalize-only"); code != 1 || stdout != "Formatted URL: "+testCLIURL+"\n" {
+		t.Errorf("❌ Expected exit code 1 and file output for failed -url, got %d: %q", code, stdout)
+	}
+}
+
+func TestCLITSV(t *testing.T) {
+	rawURL := testCLIURL + "?utm_source=test"
+	input := writeTestFile(t, "urls.txt", rawURL, "https://unknown.example/", testCLIURL)
+
+	code, stdout, _ := runCLI("-file", input, "-tsv", "-sign")
+	if code != 0 {
+		t.Errorf("❌ Expected exit code 0, got %d", code)
+	}
+	expected := rawURL + "\t" + testCLIURL + "\t" + testCLISignature + "\n" +
+		testCLIURL + "\t" + testCLIURL + "\t" + testCLISignature + "\n"
+	if stdout != expected {
+		t.Fatalf("❌ Expected %q, got %q", expected, stdout)
+	}
+
+	// Same as `cut -f2`
+	for _, line := range strings.Split(strings.TrimSuffix(stdout, "\n"), "\n") {
+		if columns := strings.Split(line, "\t"); columns[1] != testCLIURL {
+			t.Errorf("❌ Expected only formatted URLs in second column, got %q", columns[1])
+		}
+	}
+
+	// Without signature, only two columns
+	if _, stdout, _ := runCLI("-url", rawURL, "-tsv", "-normalize-only"); stdout != rawURL+"\t"+testCLIURL+"\n" {
+		t.Errorf("❌ Unexpected two column output: %q", stdout)
+	}
+
+	if got := tsvEscaper.Replace("a\tb\nc"); got != "a%09b%0Ac" {
+		t.Errorf("❌ Expected tabs and newlines to be escaped, got %q", got)
+	}
+}
+
+func TestCLIStdin(t *testing.T) {
+	stdin := "# URLs to check\n" + testCLIURL + "\n\n" + testCLIURL + "?utm_source=test\n"
+	code, stdout, stderr := runCLIWithStdin(stdin, "-stdin", "-sign")
+	if code != 0 {
+		t.Errorf("❌ Expected exit code 0, got %d", code)
+	}
+	expected := strings.Repeat("Formatted URL: "+testCLIURL+"\nSignature: "+testCLISignature+"\n", 2)
+	if stdout != expected {
+		t.Errorf("❌ Expected %q, got %q", expected, stdout)
+	}
+	if !strings.Contains(stderr, "Processed 2 URLs, 0 errors") {
+		t.Errorf("❌ Expected comment and blank lines to be skipped: %s", stderr)
+	}
+
+	// Comments are skipped in files as well
+	input := writeTestFile(t, "urls.txt", "# "+testCLIURL, testCLIURL)
+	if _, _, stderr := runCLI("-file", input); !strings.Contains(stderr, "Processed 1 URLs, 0 errors") {
+		t.Errorf("❌ Expected comment line to be skipped: %s", stderr)
+	}
+}
+
+func TestCLIVerify(t *testing.T) {
+	code, stdout, _ := runCLI("-url", testCLIURL+"?utm_source=test", "-verify", testCLISignature)
+	if code != 0 || stdout != "Signature OK: "+testCLISignature+"\n" {
+		t.Errorf("❌ Expected matching signature (exit code %d), got: %s", code, stdout)
+	}
+
+	otherSignature := strings.Repeat("0", len(testCLISignature))
+	code, stdout, stderr := runCLI("-url", testCLIURL, "-verify", otherSignature)
+	if code != 1 || stdout != "" || !strings.Contains(stderr, "Signature mismatch") {
+		t.Errorf("❌ Expected mismatch (exit code %d): %s", code, stderr)
+	}
+
+	for _, args := range [][]string{
+		{"-url", testCLIURL, "-verify", strings.ToUpper(testCLISignature)},
+		{"-url", testCLIURL, "-verify", testCLISignature[:10]},
+		{"-file", writeTestFile(t, "urls.txt", testCLIURL), "-verify", testCLISignature},
+		{"-url", "https://unknown.example/", "-verify", testCLISignature},
+	} {
+		if code, stdout, _ := runCLI(args...); code != 1 || stdout != "" {
+			t.Errorf("❌ Expected exit code 1 and no output for %v, got %d: %s", args, code, stdout)
+		}
+	}
+
+	// -verify checks against the signature of -hash-algorithm
+	sha512Signature, err := generateSignatureWith(testCLIURL, "sha512")
+	if err != nil {
+		t.Fatalf("❌ Failed to hash with sha512: %v", err)
+	}
+	if code, stdout, _ := runCLI("-url", testCLIURL, "-hash-algorithm", "sha512", "-verify", sha512Signature); code != 0 || stdout != "Signature OK: "+sha512Signature+"\n" {
+		t.Errorf("❌ Expected matching sha512 signature (exit code %d), got: %s", code, stdout)
+	}
+	for _, signature := range []string{testCLISignature, strings.Repeat("0", len(sha512Signature))} {
+		if code, stdout, _ := runCLI("-url", testCLIURL, "-hash-algorithm", "sha512", "-verify", signature); code != 1 || stdout != "" {
+			t.Errorf("❌ Expected sha512 mismatch for %s, got %d: %s", signature, code, stdout)
+		}
+	}
+	for _, signature := range []string{testCLISignature, strings.ToUpper(sha512Signature), "g" + sha512Signature[1:]} {
+		if code, _, stderr := runCLI("-url", testCLIURL, "-hash-algorithm", "sha512", "-verify", signature); code != 1 || !strings.Contains(stderr, "Invalid -verify signature for sha512") {
+			t.Errorf("❌ Expected invalid sha512 signature %s to be rejected, got %d: %s", signature, code, stderr)
+		}
+	}
+}
+
+func TestCLIProfile(t *testing.T) {
+	code, stdout, stderr := runCLI("-url", testCLIURL, "-normalize-only", "-profile")
+	if code != 0 || !strings.Contains(stdout, "Formatted URL:") {
+		t.Errorf("❌ Expected formatted URL (exit code %d), got: %s", code, stdout)
+	}
+	for _, expected := range []string{"Step", "Average", "sort query", "remove dot segments", strconv.Itoa(profileIterations)} {
+		if !strings.Contains(stderr, expected) {
+			t.Errorf("❌ Expected %q in profile output, got: %s", expected, stderr)
+		}
+	}
+	// Only steps of the default normalization flags are profiled
+	if strings.Contains(stderr, "remove fragment") {
+		t.Errorf("❌ Expected no remove fragment step in profile output, got: %s", stderr)
+	}
+
+	if code, _, stderr := runCLI("-file", writeTestFile(t, "urls.txt", testCLIURL), "-profile"); code != 1 || !strings.Contains(stderr, "-profile requires") {
+		t.Errorf("❌ Expected -profile to require -url (exit code %d): %s", code, stderr)
+	}
+}
+
+func TestCLIOpenRequiresBrowserTag(t *testing.T) {
+	if browserOpener != nil {
+		t.Skip("built with the browser tag")
+	}
+	if code, _, stderr := runCLI("-normalize-only", "-open", "-url", testCLIURL); code != 1 || !strings.Contains(stderr, "browser tag") {
+		t.Errorf("❌ Expected -open to require the browser tag (exit code %d): %s", code, stderr)
+	}
+}
+
+func TestCLIBenchmark(t *testing.T) {
+	code, stdout, stderr := runCLI("-benchmark", "-url", testCLIURL, "-iterations", "100")
+	if code != 0 || stdout != "" {
+		t.Errorf("❌ Expected exit code 0 and no output, got %d: %s", code, stdout)
+	}
+	match := regexp.MustCompile(`Throughput: (\d+) ops/sec`).FindStringSubmatch(stderr)
+	if match == nil || match[1] == "0" {
+		t.Errorf("❌ Expected non-zero throughput, got: %s", stderr)
+	}
+	if !strings.Contains(stderr, "Benchmark: 100 iterations") || !strings.Contains(stderr, "Average latency:") {
+		t.Errorf("❌ Expected benchmark summary, got: %s", stderr)
+	}
+
+	for _, args := range [][]string{
+		{"-benchmark", "-file", writeTestFile(t, "urls.txt", testCLIURL)},
+		{"-benchmark", "-url", testCLIURL, "-iterations", "0"},
+		{"-benchmark", "-url", "https://unknown.example/"},
+	} {
+		if code, _, _ := runCLI(args...); code != 1 {
+			t.Errorf("❌ Expected exit code 1 for %v, got %d", args, code)
+		}
+	}
+}
@@ -0,0 +1,35 @@
+package main
+
+import (
+	"sync/atomic"
+)
+
+// Call counters of WASM exports, for hosts measuring usage
+var (
+	normalizeCallCount atomic.Uint64
+	hashCallCount      atomic.Uint64
+)
+
+// Counter incremented by each counted WASM export, by export name
+var exportCallCounters = map[string]*atomic.Uint64{
+	"SanitizeURL":           &normalizeCallCount,
+	"NormalizeURL":          &normalizeCallCount,
+	"NormalizeURLWithFlags": &normalizeCallCount,
+	"GetSignature":          &hashCallCount,
+	"GetSignatureWith":      &hashCallCount,
+	"GetSignatureRaw":       &hashCallCount,
+	"NormalizeAndHashURL":   &hashCallCount,
+	"HashURLHMAC":           &hashCallCount,
+}
+
+// Count a call of the named WASM export. Exports call this first thing, so
+// rejected calls are counted too.
+func countExportCall(export string) {
+	exportCallCounters[export].Add(1)
+}
+
+// Reset call counters to zero
+func resetCallCounters() {
+	normalizeCallCount.Store(0)
+	hashCallCount.Store(0)
+}
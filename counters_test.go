@@ -0,0 +1,123 @@
+package main
+
+import (
+	"go/ast"
+	"go/parser"
+	"go/token"
+	"strconv"
+	"strings"
+	"sync"
+	"testing"
+)
+
+func TestCallCounters(t *testing.T) {
+	resetCallCounters()
+	if normalizeCallCount.Load() != 0 || hashCallCount.Load() != 0 {
+		t.Fatal("❌ Expected counters to be zero after reset")
+	}
+
+	tests := []struct {
+		export    string
+		normalize uint64
+		hash      uint64
+	}{
+		{"SanitizeURL", 1, 0},
+		{"NormalizeURL", 1, 0},
+		{"NormalizeURLWithFlags", 1, 0},
+		{"GetSignature", 0, 1},
+		{"GetSignatureWith", 0, 1},
+		{"GetSignatureRaw", 0, 1},
+		{"NormalizeAndHashURL", 0, 1},
+		{"HashURLHMAC", 0, 1},
+	}
+	if len(tests) != len(exportCallCounters) {
+		t.Errorf("❌ Expected %d counted exports, got %d", len(tests), len(exportCallCounters))
+	}
+	for _, test := range tests {
+		normalize, hash := normalizeCallCount.Load(), hashCallCount.Load()
+		countExportCall(test.export)
+		if delta := normalizeCallCount.Load() - normalize; delta != test.normalize {
+			t.Errorf("❌ Expected %s to add %d normalize calls, got %d", test.export, test.normalize, delta)
+		}
+		if delta := hashCallCount.Load() - hash; delta != test.hash {
+			t.Errorf("❌ Expected %s to add %d hash calls, got %d", test.export, test.hash, delta)
+		}
+	}
+
+	normalize, hash := normalizeCallCount.Load(), hashCallCount.Load()
+	var wg sync.WaitGroup
+	for range 10 {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			countExportCall("NormalizeURL")
+			countExportCall("GetSignature")
+			countExportCall("HashURLHMAC")
+		}()
+	}
+	wg.Wait()
+	if normalizeDelta, hashDelta := normalizeCallCount.Load()-normalize, hashCallCount.Load()-hash; normalizeDelta != 10 || hashDelta != 20 {
+		t.Errorf("❌ Expected deltas 10 and 20, got %d and %d", normalizeDelta, hashDelta)
+	}
+
+	resetCallCounters()
+	if normalizeCallCount.Load() != 0 || hashCallCount.Load() != 0 {
+		t.Error("❌ Expected counters to be zero after reset")
+	}
+}
+
+// wasi.go only builds for wasip1, so check from its source that each export
+// counts its calls under its own name.
+func TestCallCountersWasmExports(t *testing.T) {
+	file, err := parser.ParseFile(token.NewFileSet(), "wasi.go", nil, parser.ParseComments)
+	if err != nil {
+		t.Fatalf("❌ Failed to parse wasi.go: %v", err)
+	}
+
+	counted := map[string]bool{}
+	for _, decl := range file.Decls {
+		fn, ok := decl.(*ast.FuncDecl)
+		if !ok || fn.Doc == nil || !hasWasmExport(fn.Doc) {
+			continue
+		}
+		ast.Inspect(fn.Body, func(n ast.Node) bool {
+			call, ok := n.(*ast.CallExpr)
+			if !ok || len(call.Args) != 1 {
+				return true
+			}
+			if ident, ok := call.Fun.(*ast.Ident); !ok || ident.Name != "countExportCall" {
+				return true
+			}
+			lit, ok := call.Args[0].(*ast.BasicLit)
+			if !ok {
+				t.Errorf("❌ %s counts a non-literal export name", fn.Name.Name)
+				return true
+			}
+			if name, _ := strconv.Unquote(lit.Value); name != fn.Name.Name {
+				t.Errorf("❌ %s counts its calls as %s", fn.Name.Name, name)
+			}
+			counted[fn.Name.Name] = true
+			return true
+		})
+	}
+	for export := range exportCallCounters {
+		if !counted[export] {
+			t.Errorf("❌ Export %s isn't counted in wasi.go", export)
+		}
+	}
+	for export := range counted {
+		if _, ok := exportCallCounters[export]; !ok {
+			t.Errorf("❌ Export %s has no counter", export)
+		}
+	}
+}
+
+// Check if doc comment has a //go:wasmexport directive
+func hasWasmExport(doc *ast.CommentGroup) bool {
+	for _, comment := range doc.List {
+		if strings.HasPrefix(comment.Text, "//go:wasmexport ") {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,67 @@
+package main
+
+import (
+	"encoding/base64"
+	"errors"
+	"net/url"
+	"strings"
+)
+
+// Media type of data URIs without one, as in RFC 2397
+const defaultDataURIMediaType = "text/plain;charset=US-ASCII"
+
+var errNotDataURI = errors.New("not a data URI")
+
+// Check if URL uses the data: scheme, without parsing it
+func urlIsDataURI(rawURL string) bool {
+	return len(rawURL) >= len("data:") && strings.EqualFold(rawURL[:len("data:")], "data:")
+}
+
+// Encode content as a Base64 data URI with the given MIME type, which may
+// have parameters, e.g. "text/html;charset=utf-8".
+//
+//	encodeURLAsDataURI([]byte("Hello"), "text/plain") // "data:text/plain;base64,SGVsbG8="
+func encodeURLAsDataURI(content []byte, mimeType string) string {
+	return "data:" + mimeType + ";base64," + base64.StdEncoding.EncodeToString(content)
+}
+
+// Decode data URI into its MIME type, with parameters, and content. Content
+// is Base64 decoded if the URI has the ";base64" extension, and percent
+// decoded otherwise. URIs without a MIME type get "text/plain;charset=US-ASCII".
+// Other URLs fail with errNotDataURI.
+func decodeDataURI(rawURL string) (string, []byte, error) {
+	if !urlIsDataURI(rawURL) {
+		return "", nil, newURLParseError(rawURL, errNotDataURI)
+	}
+	mediaType, data, ok := strings.Cut(rawURL[len("data:"):], ",")
+	if !ok {
+		return "", nil, newURLParseError(rawURL, errors.New("missing comma before data"))
+	}
+
+	mediaType, isBase64 := strings.CutSuffix(mediaType, ";base64")
+	switch {
+	case mediaType == "":
+		mediaType = defaultDataURIMediaType
+	case strings.HasPrefix(mediaType, ";"):
+		// Parameters without a type, e.g. ";charset=utf-8", apply to text/plain
+		mediaType = "text/plain" + mediaType
+	}
+
+	if isBase64 {
+		// Base64 data may itself be percent-encoded in URLs
+		unescaped, err := url.PathUnescape(data)
+		if err != nil {
+			return "", nil, newURLParseError(rawURL, err)
+		}
+		content, err := base64.StdEncoding.DecodeString(unescaped)
+		if err != nil {
+			return "", nil, newURLParseError(rawURL, err)
+		}
+		return mediaType, content, nil
+	}
+	content, err := url.PathUnescape(data)
+	if err != nil {
+		return "", nil, newURLParseError(rawURL, err)
+	}
+	return mediaType, []byte(content), nil
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"regexp"
+	"testing"
+)
+
+func TestDataURIRoundTrip(t *testing.T) {
+	tests := []struct {
+		content  []byte
+		mimeType string
+		expected string
+	}{
+		{[]byte("Hello"), "text/plain", "data:text/plain;base64,SGVsbG8="},
+		{[]byte("<h1>Päivää</h1>"), "text/html;charset=utf-8", "data:text/html;charset=utf-8;base64,PGgxPlDDpGl2w6TDpDwvaDE+"},
+		{[]byte{0x89, 'P', 'N', 'G', 0x00, 0xff}, "image/png", "data:image/png;base64,iVBORwD/"},
+		{nil, "application/octet-stream", "data:application/octet-stream;base64,"},
+	}
+
+	for _, test := range tests {
+		uri := encodeURLAsDataURI(test.content, test.mimeType)
+		if uri != test.expected {
+			t.Errorf("❌ Expected %q, got %q", test.expected, uri)
+		}
+		mimeType, content, err := decodeDataURI(uri)
+		if err != nil || mimeType != test.mimeType || !bytes.Equal(content, test.content) {
+			t.Errorf("❌ Expected %q and %q from %q, got %q and %q (error: %v)", test.mimeType, test.content, uri, mimeType, content, err)
+		}
+	}
+}
+
+func TestDecodeDataURI(t *testing.T) {
+	tests := []struct {
+		uri      string
+		mimeType string
+		content  string
+	}{
+		{"data:,Hello%2C%20World%21", "text/plain;charset=US-ASCII", "Hello, World!"},
+		{"data:;charset=utf-8,p%C3%A4iv%C3%A4", "text/plain;charset=utf-8", "päivä"},
+		{"data:text/csv;charset=utf-8;header=present,a,b", "text/csv;charset=utf-8;header=present", "a,b"},
+		{"DATA:text/plain;base64,SGVsbG8%3D", "text/plain", "Hello"},
+	}
+
+	for _, test := range tests {
+		mimeType, content, err := decodeDataURI(test.uri)
+		if err != nil || mimeType != test.mimeType || string(content) != test.content {
+			t.Errorf("❌ Expected %q and %q from %q, got %q and %q (error: %v)", test.mimeType, test.content, test.uri, mimeType, content, err)
+		}
+	}
+
+	if _, _, err := decodeDataURI("https://example.com/"); !errors.Is(err, errNotDataURI) {
+		t.Errorf("❌ Expected errNotDataURI, got: %v", err)
+	}
+	for _, input := range []string{"data:text/plain", "data:text/plain;base64,not*base64", "data:,%zz"} {
+		if _, _, err := decodeDataURI(input); err == nil {
+			t.Errorf("❌ Expected error for %q", input)
+		}
+	}
+}
+
+func TestDataURINotNormalized(t *testing.T) {
+	for _, uri := range []string{
+		"data:text/plain;base64,SGVsbG8=",
+		"DATA:Text/HTML,%3ch1%3e?b=2&a=1#x",
+		"data:,a/./b/../c",
+	} {
+		if normalized, err := normalizeURL(uri); err != nil || normalized != uri {
+			t.Errorf("❌ Expected %q unchanged, got %q (error: %v)", uri, normalized, err)
+		}
+		// Helpers modifying the parsed URL keep data URIs unchanged too
+		mask := URLMask{PathPattern: regexp.MustCompile(`a`), PathReplacement: "x", QueryPattern: regexp.MustCompile(`b=2`), QueryReplacement: "b=X"}
+		if masked, err := urlMask(uri, mask); err != nil || masked != uri {
+			t.Errorf("❌ Expected %q unchanged by urlMask, got %q (error: %v)", uri, masked, err)
+		}
+		if canonical, err := urlCanonicalizeForDeduplication(uri); err != nil || canonical != uri {
+			t.Errorf("❌ Expected %q unchanged by urlCanonicalizeForDeduplication, got %q (error: %v)", uri, canonical, err)
+		}
+		var schemeErr *InsecureSchemeError
+		if _, err := urlNormalizeEnsureHTTPS(uri); !errors.As(err, &schemeErr) {
+			t.Errorf("❌ Expected InsecureSchemeError for %q, got: %v", uri, err)
+		}
+	}
+
+	// Sanitizing still rejects data URIs, such as ones carrying scripts
+	script := encodeURLAsDataURI([]byte("alert(1)"), "text/javascript")
+	var dangerErr *DangerousURLError
+	if _, err := sanitizeURL(script); !errors.As(err, &dangerErr) || dangerErr.Kind != DangerDataScheme {
+		t.Errorf("❌ Expected data scheme to be rejected, got: %v", err)
+	}
+}
@@ -0,0 +1,22 @@
+//go:build debug
+// +build debug
+
+package main
+
+import (
+	"fmt"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Panic if URL is not in normalized form. Only available in builds with the
+// debug tag, meant for catching unnormalized URLs in tests and development.
+func assertURLNormalized(rawURL string, flags purell.NormalizationFlags) {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		panic(fmt.Sprintf("URL %q is not normalized: %v", rawURL, err))
+	}
+	if normalized != rawURL {
+		panic(fmt.Sprintf("URL %q is not normalized, expected %q", rawURL, normalized))
+	}
+}
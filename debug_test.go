@@ -0,0 +1,26 @@
+//go:build debug
+// +build debug
+
+package main
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestAssertURLNormalized(t *testing.T) {
+	// Normalized URL doesn't panic
+	assertURLNormalized("https://example.com/a?x=1&y=2", defaultNormalizationFlags)
+
+	for _, input := range []string{"HTTPS://Example.com/a/../b", "://invalid"} {
+		func() {
+			defer func() {
+				r := recover()
+				if msg, ok := r.(string); !ok || !strings.Contains(msg, "is not normalized") {
+					t.Errorf("❌ Expected panic for %q, got: %v", input, r)
+				}
+			}()
+			assertURLNormalized(input, defaultNormalizationFlags)
+		}()
+	}
+}
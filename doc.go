@@ -0,0 +1,22 @@
+// Suola normalizes and hashes URLs, built into a CLI and WebAssembly modules
+// for browser and WASI environments.
+//
+// Everything is in package main, so the Go functions can't be imported, and
+// the public API are the WASM exports of wasi.go and js.go. Within the
+// package, the functions are grouped by file:
+//
+// Normalization, in lib.go and pipeline.go:
+//   - normalizeURL and normalizeURLWithFlags normalize URLs with purell flags
+//   - NormalizationPipeline composes normalization steps, and URLNormalizer
+//     holds flags for reuse
+//   - NormalizedURL holds a URL known to be normalized
+//
+// Hashing, in lib.go and hash.go:
+//   - getSignature and getSignatureRaw hash URLs after applying site rules
+//   - hashAlgorithms lists the supported hash algorithms by name
+//   - hashStableV1 hashes URLs with the normalization of the 1.0 release,
+//     calling purell with frozen flags, so stored hashes never change
+//
+// Other files add helpers for specific URL types and tasks, such as
+// sanitize.go for untrusted input and social.go for social media URLs.
+package main
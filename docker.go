@@ -0,0 +1,85 @@
+package main
+
+import (
+	"errors"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+var errInvalidImageReference = errors.New("invalid container image reference")
+
+// Registry of image references without one, and its aliases
+const defaultDockerRegistry = "docker.io"
+
+var dockerRegistryAliases = []string{"index.docker.io", "registry-1.docker.io", "registry.hub.docker.com"}
+
+// Path components of repository names and tags, as in the Docker
+// distribution reference grammar
+var (
+	dockerRepositoryComponentPattern = regexp.MustCompile(`^[a-z0-9]+(?:(?:[._]|__|-+)[a-z0-9]+)*$`)
+	dockerTagPattern                 = regexp.MustCompile(`^\w[\w.-]{0,127}$`)
+	dockerDigestPattern              = regexp.MustCompile(`^[a-z0-9]+(?:[.+_-][a-z0-9]+)*:[0-9a-fA-F]{32,}$`)
+)
+
+// Normalize container image reference into the full form with registry and
+// tag, so that shorthands of the same image are equal:
+//
+//	urlNormalizeDockerRegistry("ubuntu")                        // "docker.io/library/ubuntu:latest"
+//	urlNormalizeDockerRegistry("docker.io/library/ubuntu:22.04") // "docker.io/library/ubuntu:22.04"
+//	urlNormalizeDockerRegistry("ghcr.io/owner/app")             // "ghcr.io/owner/app:latest"
+//
+// The first path component is a registry if it contains "." or ":", or is
+// "localhost", otherwise the image is on Docker Hub, where official images
+// are under "library/". References pinned by digest get no tag. A
+// "docker://", "https://" or "http://" prefix is removed.
+func urlNormalizeDockerRegistry(ref string) (string, error) {
+	rest := ref
+	for _, prefix := range []string{"https://", "http://", "docker://"} {
+		if s, ok := strings.CutPrefix(rest, prefix); ok {
+			rest = s
+			break
+		}
+	}
+
+	registry := defaultDockerRegistry
+	if first, after, ok := strings.Cut(rest, "/"); ok && (strings.ContainsAny(first, ".:") || first == "localhost") {
+		registry, rest = strings.ToLower(first), after
+		if slices.Contains(dockerRegistryAliases, registry) {
+			registry = defaultDockerRegistry
+		}
+	}
+
+	repository, digest, pinned := strings.Cut(rest, "@")
+	if pinned && !dockerDigestPattern.MatchString(digest) {
+		return "", newURLParseError(ref, errInvalidImageReference)
+	}
+	var tag string
+	if i := strings.LastIndexByte(repository, ':'); i > strings.LastIndexByte(repository, '/') {
+		repository, tag = repository[:i], repository[i+1:]
+		if !dockerTagPattern.MatchString(tag) {
+			return "", newURLParseError(ref, errInvalidImageReference)
+		}
+	} else if !pinned {
+		tag = "latest"
+	}
+
+	components := strings.Split(repository, "/")
+	for _, component := range components {
+		if !dockerRepositoryComponentPattern.MatchString(component) {
+			return "", newURLParseError(ref, errInvalidImageReference)
+		}
+	}
+	if registry == defaultDockerRegistry && len(components) == 1 {
+		repository = "library/" + repository
+	}
+
+	normalized := registry + "/" + repository
+	if tag != "" {
+		normalized += ":" + tag
+	}
+	if pinned {
+		normalized += "@" + digest
+	}
+	return normalized, nil
+}
@@ -0,0 +1,52 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLNormalizeDockerRegistry(t *testing.T) {
+	const expected = "docker.io/library/ubuntu:latest"
+	inputs := []string{
+		"ubuntu",
+		"ubuntu:latest",
+		"library/ubuntu",
+		"docker.io/library/ubuntu",
+		"docker.io/library/ubuntu:latest",
+		"index.docker.io/library/ubuntu",
+		"Docker.IO/ubuntu",
+		"docker://ubuntu",
+		"https://registry-1.docker.io/library/ubuntu:latest",
+	}
+	for _, input := range inputs {
+		if got, err := urlNormalizeDockerRegistry(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	const digest = "sha256:45b23dee08af5e43a7fea6c4cf9c25ccf269ee113168c19722f87876677c5cb2"
+	tests := map[string]string{
+		"ubuntu:22.04":                  "docker.io/library/ubuntu:22.04",
+		"bitnami/redis":                 "docker.io/bitnami/redis:latest",
+		"ghcr.io/owner/app":             "ghcr.io/owner/app:latest",
+		"quay.io/org/sub/image:v1.2.3":  "quay.io/org/sub/image:v1.2.3",
+		"localhost/app":                 "localhost/app:latest",
+		"localhost:5000/app:dev":        "localhost:5000/app:dev",
+		"registry.example.com:5000/a/b": "registry.example.com:5000/a/b:latest",
+		"ubuntu@" + digest:              "docker.io/library/ubuntu@" + digest,
+		"ubuntu:22.04@" + digest:        "docker.io/library/ubuntu:22.04@" + digest,
+		"my_org/my-app__x.y":            "docker.io/my_org/my-app__x.y:latest",
+	}
+	for input, expected := range tests {
+		if got, err := urlNormalizeDockerRegistry(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Repository names must be lowercase
+	for _, input := range []string{"", "Ubuntu", "ubuntu:", "ubuntu@sha256:123", "ghcr.io/", "a//b", "ubuntu:-tag", "-app"} {
+		if got, err := urlNormalizeDockerRegistry(input); !errors.Is(err, errInvalidImageReference) {
+			t.Errorf("❌ Expected errInvalidImageReference for %q, got %q (error: %v)", input, got, err)
+		}
+	}
+}
@@ -0,0 +1,61 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"net"
+	"slices"
+	"strings"
+)
+
+var errIPAddressHost = errors.New("host is an IP address")
+
+// Second level labels under which country code TLDs register domains, as in
+// "bbc.co.uk"
+var countryCodeSecondLevelLabels = []string{"ac", "co", "com", "edu", "gov", "ne", "net", "or", "org"}
+
+// Split lowercase hostname of URL into apex domain and subdomain, which is
+// empty if there is none
+func splitApexDomain(rawURL string) (apex, subdomain string, err error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", "", err
+	}
+	host := strings.TrimSuffix(strings.ToLower(u.Hostname()), ".")
+	if host == "" {
+		return "", "", newURLParseError(rawURL, errMissingOrigin)
+	}
+	if net.ParseIP(host) != nil {
+		return "", "", fmt.Errorf("%s: %w", host, errIPAddressHost)
+	}
+
+	labels := strings.Split(host, ".")
+	n := 2
+	if len(labels) > 2 && len(labels[len(labels)-1]) == 2 && slices.Contains(countryCodeSecondLevelLabels, labels[len(labels)-2]) {
+		n = 3
+	}
+	if len(labels) <= n {
+		return host, "", nil
+	}
+	return strings.Join(labels[len(labels)-n:], "."), strings.Join(labels[:len(labels)-n], "."), nil
+}
+
+// Get apex domain of URL host, the domain without subdomains: "bbc.co.uk"
+// for "https://news.bbc.co.uk/".
+//
+// Keeps the last two labels, or three when the second last is a common
+// second level label of a country code TLD, such as "co.uk". Unlike
+// urlEffectiveTLDPlusOne with the etld build tag, it works without the
+// public suffix list, so "alice.github.io" has the apex "github.io".
+func urlGetApexDomain(rawURL string) (string, error) {
+	apex, _, err := splitApexDomain(rawURL)
+	return apex, err
+}
+
+// Get subdomain of URL host below its apex domain, see urlGetApexDomain:
+// "news" for "https://news.bbc.co.uk/". Returns false if the host is the
+// apex domain.
+func urlGetSubdomain(rawURL string) (string, bool, error) {
+	_, subdomain, err := splitApexDomain(rawURL)
+	return subdomain, subdomain != "", err
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLGetApexDomain(t *testing.T) {
+	tests := []struct {
+		url, apex, subdomain string
+	}{
+		{"https://news.bbc.co.uk/", "bbc.co.uk", "news"},
+		{"https://bbc.co.uk/", "bbc.co.uk", ""},
+		{"https://WWW.Example.COM./path", "example.com", "www"},
+		{"https://a.b.example.com:8443/", "example.com", "a.b"},
+		{"https://www.iltalehti.fi/kotimaa", "iltalehti.fi", "www"},
+		{"https://shop.example.com.au/", "example.com.au", "shop"},
+		{"https://www.example.ne.jp/", "example.ne.jp", "www"},
+		{"https://alice.github.io/", "github.io", "alice"},
+		// Common second level labels are only special under country codes
+		{"https://www.co.com/", "co.com", "www"},
+		{"http://localhost:8080/", "localhost", ""},
+	}
+
+	for _, test := range tests {
+		if got, err := urlGetApexDomain(test.url); err != nil || got != test.apex {
+			t.Errorf("❌ urlGetApexDomain(%q): expected %q, got %q (error: %v)", test.url, test.apex, got, err)
+		}
+		if got, ok, err := urlGetSubdomain(test.url); err != nil || got != test.subdomain || ok != (test.subdomain != "") {
+			t.Errorf("❌ urlGetSubdomain(%q): expected %q, got %q, %v (error: %v)", test.url, test.subdomain, got, ok, err)
+		}
+	}
+
+	for _, rawURL := range []string{"http://127.0.0.1/", "http://[::1]:8080/"} {
+		if _, err := urlGetApexDomain(rawURL); !errors.Is(err, errIPAddressHost) {
+			t.Errorf("❌ urlGetApexDomain(%q): expected errIPAddressHost, got: %v", rawURL, err)
+		}
+	}
+	if _, err := urlGetApexDomain("/relative"); !errors.Is(err, errMissingOrigin) {
+		t.Errorf("❌ Expected errMissingOrigin, got: %v", err)
+	}
+	if _, _, err := urlGetSubdomain("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,212 @@
+package main
+
+import (
+	"errors"
+	"net/url"
+	"strings"
+	"unicode/utf8"
+)
+
+// Percent-encode a single path segment, including any "/" in it.
+//
+//	urlEncodePathSegment("a/b c") // "a%2Fb%20c"
+func urlEncodePathSegment(segment string) string {
+	return url.PathEscape(segment)
+}
+
+// Decode a percent-encoded path segment. Unlike query values, "+" is kept
+// as-is.
+//
+//	urlDecodePathSegment("a%2Fb%20c") // "a/b c", nil
+func urlDecodePathSegment(encoded string) (string, error) {
+	return url.PathUnescape(encoded)
+}
+
+// Encode a query parameter value, spaces are encoded as "+".
+//
+//	urlEncodeQueryValue("a&b=c d") // "a%26b%3Dc+d"
+func urlEncodeQueryValue(value string) string {
+	return url.QueryEscape(value)
+}
+
+// Decode a query parameter value, "+" is decoded as space.
+//
+//	urlDecodeQueryValue("a%26b%3Dc+d") // "a&b=c d", nil
+func urlDecodeQueryValue(encoded string) (string, error) {
+	return url.QueryUnescape(encoded)
+}
+
+// Check if c is an unreserved character of RFC 3986 section 2.3
+func isUnreserved(c byte) bool {
+	return 'A' <= c && c <= 'Z' || 'a' <= c && c <= 'z' || '0' <= c && c <= '9' ||
+		c == '-' || c == '.' || c == '_' || c == '~'
+}
+
+// Hex digits of percent-encoded escapes
+const upperhex = "0123456789ABCDEF"
+
+// Percent-encode all but the unreserved characters of RFC 3986. This is
+// stricter than JavaScript's encodeURIComponent, which keeps "!", "'", "(",
+// ")" and "*" unescaped: they are reserved sub-delimiters in RFC 3986, so
+// they're encoded here, as in the fixedEncodeURIComponent example on MDN.
+//
+//	encodeURLComponent("шеллы?") // "%D1%88%D0%B5%D0%BB%D0%BB%D1%8B%3F"
+func encodeURLComponent(s string) string {
+	var b strings.Builder
+	b.Grow(len(s))
+	for i := 0; i < len(s); i++ {
+		if c := s[i]; isUnreserved(c) {
+			b.WriteByte(c)
+		} else {
+			b.Write([]byte{'%', upperhex[c>>4], upperhex[c&15]})
+		}
+	}
+	return b.String()
+}
+
+// Get value of a hex digit
+func unhex(c byte) (byte, bool) {
+	switch {
+	case '0' <= c && c <= '9':
+		return c - '0', true
+	case 'a' <= c && c <= 'f':
+		return c - 'a' + 10, true
+	case 'A' <= c && c <= 'F':
+		return c - 'A' + 10, true
+	}
+	return 0, false
+}
+
+// Re-encode percent-encoded string: escapes are uppercased, escapes of
+// unreserved characters are decoded, and characters not allowed unescaped are
+// encoded. Escapes of reserved characters are kept, so the meaning of the
+// string doesn't change. Fails with url.EscapeError on malformed escapes.
+func reencodeComponent(s string, allowed func(c byte) bool) (string, error) {
+	var b strings.Builder
+	b.Grow(len(s))
+	for i := 0; i < len(s); i++ {
+		c := s[i]
+		switch {
+		case c == '%':
+			if i+2 >= len(s) {
+				return "", url.EscapeError(s[i:])
+			}
+			hi, ok1 := unhex(s[i+1])
+			lo, ok2 := unhex(s[i+2])
+			if !ok1 || !ok2 {
+				return "", url.EscapeError(s[i : i+3])
+			}
+			if decoded := hi<<4 | lo; isUnreserved(decoded) {
+				b.WriteByte(decoded)
+			} else {
+				b.Write([]byte{'%', upperhex[decoded>>4], upperhex[decoded&15]})
+			}
+			i += 2
+		case allowed(c):
+			b.WriteByte(c)
+		default:
+			b.Write([]byte{'%', upperhex[c>>4], upperhex[c&15]})
+		}
+	}
+	return b.String(), nil
+}
+
+// Check if character may appear unescaped in a query or fragment, as pchar,
+// "/" or "?" of RFC 3986
+func isQueryChar(c byte) bool {
+	return isPathChar(c) || c == '?'
+}
+
+// Normalize encoding of URL by re-encoding path, query and fragment from
+// scratch with reencodeComponent, using the character set of each component.
+// Userinfo is re-encoded by url.URL, and the host is kept as-is.
+//
+// The purell steps FlagUppercaseEscapes, FlagDecodeUnnecessaryEscapes and
+// FlagEncodeNecessaryEscapes fix encoding issues of the path one by one. This
+// is slower, but gives the same encoding to all components regardless of how
+// the input was encoded, and keeps escaped reserved characters, such as
+// "%2F" in a path segment, escaped.
+//
+//	urlNormalizeConsistentEncoding("https://example.com/%7euser/a b?q=%e2%82%ac#%7Etop")
+//	// "https://example.com/~user/a%20b?q=%E2%82%AC#~top"
+func urlNormalizeConsistentEncoding(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+
+	if u.Opaque != "" {
+		if u.Opaque, err = reencodeComponent(u.Opaque, isPathChar); err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+	}
+	if u.RawPath, err = reencodeComponent(u.EscapedPath(), isPathChar); err != nil {
+		return "", newURLParseError(rawURL, err)
+	}
+	// Unescaping can't fail, as the path was just re-encoded
+	u.Path, _ = url.PathUnescape(u.RawPath)
+	if u.RawQuery, err = reencodeComponent(u.RawQuery, isQueryChar); err != nil {
+		return "", newURLParseError(rawURL, err)
+	}
+	if u.Fragment != "" {
+		if u.RawFragment, err = reencodeComponent(u.EscapedFragment(), isQueryChar); err != nil {
+			return "", newURLParseError(rawURL, err)
+		}
+		u.Fragment, _ = url.PathUnescape(u.RawFragment)
+	}
+	return u.String(), nil
+}
+
+var errInvalidComponentUTF8 = errors.New("decoded URL component is not valid UTF-8")
+
+// Decode all percent-encoded sequences, like JavaScript's
+// decodeURIComponent. Fails with url.EscapeError on malformed sequences, and
+// errInvalidComponentUTF8 if the result is not valid UTF-8.
+//
+//	decodeURLComponent("%D1%88%D0%B5%D0%BB%D0%BB%D1%8B%3F") // "шеллы?", nil
+func decodeURLComponent(s string) (string, error) {
+	if !strings.Contains(s, "%") {
+		return s, nil
+	}
+	decoded := make([]byte, 0, len(s))
+	for i := 0; i < len(s); i++ {
+		if s[i] != '%' {
+			decoded = append(decoded, s[i])
+			continue
+		}
+		if i+2 >= len(s) {
+			return "", url.EscapeError(s[i:])
+		}
+		hi, ok1 := unhex(s[i+1])
+		lo, ok2 := unhex(s[i+2])
+		if !ok1 || !ok2 {
+			return "", url.EscapeError(s[i : i+3])
+		}
+		decoded = append(decoded, hi<<4|lo)
+		i += 2
+	}
+	if !utf8.Valid(decoded) {
+		return "", errInvalidComponentUTF8
+	}
+	return string(decoded), nil
+}
+
+// Replace raw spaces with "%20" everywhere in URL, without parsing it.
+func urlPercentEncodeSpaces(rawURL string) string {
+	return strings.ReplaceAll(rawURL, " ", "%20")
+}
+
+// Replace "+" with "%20" in the query of URL, for queries encoded as
+// application/x-www-form-urlencoded by HTML forms. Path and fragment are left
+// as-is, as "+" is a literal plus there.
+func urlReplacePlusWithSpace(rawURL string) string {
+	end := len(rawURL)
+	if i := strings.IndexByte(rawURL, '#'); i >= 0 {
+		end = i
+	}
+	start := strings.IndexByte(rawURL[:end], '?')
+	if start < 0 {
+		return rawURL
+	}
+	return rawURL[:start] + strings.ReplaceAll(rawURL[start:end], "+", "%20") + rawURL[end:]
+}
@@ -0,0 +1,150 @@
+//go:build etld
+// +build etld
+
+package main
+
+import (
+	"fmt"
+	"net"
+	"net/url"
+	"strings"
+
+	"golang.org/x/net/publicsuffix"
+)
+
+func init() {
+	icannPublicSuffix = func(host string) string {
+		if suffix, icann := publicsuffix.PublicSuffix(host); icann {
+			return suffix
+		}
+		return ""
+	}
+}
+
+// Get lowercase hostname of URL, which must be a domain name
+func urlDomainName(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+	if host == "" {
+		return "", newURLParseError(rawURL, errMissingOrigin)
+	}
+	if net.ParseIP(host) != nil {
+		return "", fmt.Errorf("%s: %w", host, errIPAddressHost)
+	}
+	return strings.TrimSuffix(host, "."), nil
+}
+
+// Get effective top-level domain plus one label of the URL host, e.g.
+// "example.co.uk" for "https://www.news.example.co.uk/".
+//
+// Uses the public suffix list bundled with golang.org/x/net, including its
+// private domains, so "alice.github.io" and "bob.github.io" are distinct.
+func urlEffectiveTLDPlusOne(rawURL string) (string, error) {
+	host, err := urlDomainName(rawURL)
+	if err != nil {
+		return "", err
+	}
+	return publicsuffix.EffectiveTLDPlusOne(host)
+}
+
+// Get longest of private TLDs the host is in, lowercase and without dots
+// around it
+func matchingPrivateTLD(host string, privateTLDs []string) (string, bool) {
+	var longest string
+	for _, tld := range privateTLDs {
+		tld = strings.Trim(strings.ToLower(tld), ".")
+		if tld != "" && len(tld) > len(longest) && (host == tld || strings.HasSuffix(host, "."+tld)) {
+			longest = tld
+		}
+	}
+	return longest, longest != ""
+}
+
+// Get eTLD+1 of the URL host like urlEffectiveTLDPlusOne, treating private
+// TLDs of an intranet, such as "corp.internal", as public suffixes:
+//
+//	urlEffectiveTLDPlusOneWithTLDs("https://wiki.team.corp.internal/", "corp.internal") // "team.corp.internal"
+//
+// TLDs missing from the public suffix list count as single label suffixes
+// anyway, so only private TLDs with several labels, or longer than the
+// listed suffix of the host, change the result.
+func urlEffectiveTLDPlusOneWithTLDs(rawURL string, extraTLDs ...string) (string, error) {
+	host, err := urlDomainName(rawURL)
+	if err != nil {
+		return "", err
+	}
+	tld, ok := matchingPrivateTLD(host, extraTLDs)
+	if suffix, _ := publicsuffix.PublicSuffix(host); !ok || len(tld) <= len(suffix) {
+		return publicsuffix.EffectiveTLDPlusOne(host)
+	}
+	if host == tld {
+		return "", fmt.Errorf("cannot derive eTLD+1 for private TLD %q", host)
+	}
+	rest := strings.TrimSuffix(host, "."+tld)
+	return rest[strings.LastIndexByte(rest, '.')+1:] + "." + tld, nil
+}
+
+// Check if URL host is in one of the private TLDs, see
+// urlEffectiveTLDPlusOneWithTLDs
+func urlIsPrivateDomain(rawURL string, privateTLDs ...string) (bool, error) {
+	host, err := urlDomainName(rawURL)
+	if err != nil {
+		return false, err
+	}
+	_, ok := matchingPrivateTLD(host, privateTLDs)
+	return ok, nil
+}
+
+// Get public suffix of the URL host, e.g. "co.uk" for "https://www.example.co.uk/"
+func urlPublicSuffix(rawURL string) (string, error) {
+	host, err := urlDomainName(rawURL)
+	if err != nil {
+		return "", err
+	}
+	suffix, _ := publicsuffix.PublicSuffix(host)
+	return suffix, nil
+}
+
+// Check if URL is on the same site as base URL: same scheme and eTLD+1, so
+// "https://blog.example.com/" is internal to "https://www.example.com/".
+// Relative URLs are resolved against base first.
+//
+// Hosts without eTLD+1, such as IP addresses and "localhost", must match
+// exactly.
+func urlIsInternal(rawURL, baseURL string) (bool, error) {
+	if !urlIsAbsolute(baseURL) {
+		return false, newURLParseError(baseURL, errMissingOrigin)
+	}
+	base, err := parseURL(baseURL)
+	if err != nil {
+		return false, err
+	}
+	if base.Hostname() == "" {
+		return false, newURLParseError(baseURL, errMissingOrigin)
+	}
+	ref, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	u := base.ResolveReference(ref)
+	if u.Scheme != base.Scheme {
+		return false, nil
+	}
+	return urlSite(u) == urlSite(base), nil
+}
+
+// Get eTLD+1 of URL host, or the host itself if it has none
+func urlSite(u *url.URL) string {
+	host := strings.TrimSuffix(strings.ToLower(u.Hostname()), ".")
+	if net.ParseIP(host) != nil {
+		return host
+	}
+	site, err := publicsuffix.EffectiveTLDPlusOne(host)
+	if err != nil {
+		return host
+	}
+	return site
+}
@@ -0,0 +1,159 @@
+//go:build etld
+// +build etld
+
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLEffectiveTLDPlusOne(t *testing.T) {
+	tests := []struct {
+		url, etldPlusOne, suffix string
+	}{
+		{"https://www.news.example.co.uk/", "example.co.uk", "co.uk"},
+		{"https://WWW.Example.COM/path", "example.com", "com"},
+		{"https://example.com./", "example.com", "com"},
+		{"https://alice.github.io/", "alice.github.io", "github.io"},
+		{"https://blog.bob.github.io/", "bob.github.io", "github.io"},
+		{"https://www.iltalehti.fi:443/kotimaa", "iltalehti.fi", "fi"},
+	}
+
+	for _, test := range tests {
+		if got, err := urlEffectiveTLDPlusOne(test.url); err != nil || got != test.etldPlusOne {
+			t.Errorf("❌ urlEffectiveTLDPlusOne(%q): expected %q, got %q (error: %v)", test.url, test.etldPlusOne, got, err)
+		}
+		if got, err := urlPublicSuffix(test.url); err != nil || got != test.suffix {
+			t.Errorf("❌ urlPublicSuffix(%q): expected %q, got %q (error: %v)", test.url, test.suffix, got, err)
+		}
+	}
+
+	// Single label hosts and public suffixes themselves have no eTLD+1
+	for _, rawURL := range []string{"http://localhost/", "https://co.uk/", "https://github.io/", "/relative"} {
+		if got, err := urlEffectiveTLDPlusOne(rawURL); err == nil {
+			t.Errorf("❌ urlEffectiveTLDPlusOne(%q): expected error, got %q", rawURL, got)
+		}
+	}
+
+	for _, rawURL := range []string{"http://127.0.0.1/", "http://[::1]:8080/"} {
+		if _, err := urlEffectiveTLDPlusOne(rawURL); !errors.Is(err, errIPAddressHost) {
+			t.Errorf("❌ urlEffectiveTLDPlusOne(%q): expected errIPAddressHost, got: %v", rawURL, err)
+		}
+		if _, err := urlPublicSuffix(rawURL); !errors.Is(err, errIPAddressHost) {
+			t.Errorf("❌ urlPublicSuffix(%q): expected errIPAddressHost, got: %v", rawURL, err)
+		}
+	}
+}
+
+func TestURLIsInternal(t *testing.T) {
+	const base = "https://www.example.com/news/"
+	tests := []struct {
+		url      string
+		internal bool
+	}{
+		{"https://www.example.com/about", true},
+		{"https://blog.example.com/", true},
+		{"https://EXAMPLE.COM./", true},
+		{"/about", true},
+		{"../contact?x=1", true},
+		{"#top", true},
+		{"http://www.example.com/", false},
+		{"https://example.org/", false},
+		{"https://alice.github.io/", false},
+		// Injection attempts with the origin outside of the host
+		{"https://attacker.com/evil?redirect=https://example.com", false},
+		{"https://attacker.com/https://www.example.com/", false},
+		{"https://attacker.com/#https://www.example.com", false},
+		{"https://www.example.com@attacker.com/", false},
+		{"https://www.example.com.attacker.com/", false},
+		{"https://example.com-attacker.com/", false},
+		{"//attacker.com/?www.example.com", false},
+		{"javascript:location='https://www.example.com'", false},
+	}
+
+	for _, test := range tests {
+		if internal, err := urlIsInternal(test.url, base); err != nil || internal != test.internal {
+			t.Errorf("❌ urlIsInternal(%q): expected %v, got %v (error: %v)", test.url, test.internal, internal, err)
+		}
+	}
+
+	// Hosts without eTLD+1 must match exactly
+	if internal, _ := urlIsInternal("http://127.0.0.1:8080/a", "http://127.0.0.1/"); !internal {
+		t.Error("❌ Expected same IP address to be internal")
+	}
+	if internal, _ := urlIsInternal("http://127.0.0.2/", "http://127.0.0.1/"); internal {
+		t.Error("❌ Expected different IP address not to be internal")
+	}
+	if internal, _ := urlIsInternal("http://localhost/a", "http://localhost/"); !internal {
+		t.Error("❌ Expected same localhost to be internal")
+	}
+
+	for _, baseURL := range []string{"/relative", "", "file:///etc/hosts"} {
+		if _, err := urlIsInternal("https://www.example.com/", baseURL); !errors.Is(err, errMissingOrigin) {
+			t.Errorf("❌ Expected errMissingOrigin for base %q, got: %v", baseURL, err)
+		}
+	}
+}
+
+func TestURLEffectiveTLDPlusOneWithTLDs(t *testing.T) {
+	privateTLDs := []string{"internal", ".Corp.Internal.", "local"}
+	tests := []struct {
+		url, etldPlusOne string
+		private          bool
+	}{
+		{"https://wiki.intranet.internal/", "intranet.internal", true},
+		{"https://wiki.team.corp.internal/", "team.corp.internal", true},
+		{"https://Printer.Office.local./", "office.local", true},
+		{"https://www.news.example.co.uk/", "example.co.uk", false},
+		{"https://blog.bob.github.io/", "bob.github.io", false},
+		{"https://internal.example.com/", "example.com", false},
+	}
+
+	for _, test := range tests {
+		if got, err := urlEffectiveTLDPlusOneWithTLDs(test.url, privateTLDs...); err != nil || got != test.etldPlusOne {
+			t.Errorf("❌ urlEffectiveTLDPlusOneWithTLDs(%q): expected %q, got %q (error: %v)", test.url, test.etldPlusOne, got, err)
+		}
+		if private, err := urlIsPrivateDomain(test.url, privateTLDs...); err != nil || private != test.private {
+			t.Errorf("❌ urlIsPrivateDomain(%q): expected %v, got %v (error: %v)", test.url, test.private, private, err)
+		}
+
+		// Without private TLDs the result is the same as urlEffectiveTLDPlusOne
+		expected, expectedErr := urlEffectiveTLDPlusOne(test.url)
+		if got, err := urlEffectiveTLDPlusOneWithTLDs(test.url); got != expected || (err == nil) != (expectedErr == nil) {
+			t.Errorf("❌ urlEffectiveTLDPlusOneWithTLDs(%q) without TLDs: expected %q, got %q (error: %v)", test.url, expected, got, err)
+		}
+		if private, _ := urlIsPrivateDomain(test.url); private {
+			t.Errorf("❌ urlIsPrivateDomain(%q) without TLDs: expected false", test.url)
+		}
+	}
+
+	// Private TLDs themselves have no eTLD+1
+	for _, rawURL := range []string{"https://corp.internal/", "https://internal/", "/relative"} {
+		if got, err := urlEffectiveTLDPlusOneWithTLDs(rawURL, privateTLDs...); err == nil {
+			t.Errorf("❌ urlEffectiveTLDPlusOneWithTLDs(%q): expected error, got %q", rawURL, got)
+		}
+	}
+
+	if _, err := urlIsPrivateDomain("http://10.0.0.1/", privateTLDs...); !errors.Is(err, errIPAddressHost) {
+		t.Errorf("❌ Expected errIPAddressHost, got: %v", err)
+	}
+}
+
+func TestIsGoogleHostPublicSuffix(t *testing.T) {
+	tests := map[string]bool{
+		"www.google.com":    true,
+		"google.com.au":     true,
+		"www.google.co.uk":  true,
+		"google.com.sg":     true,
+		"google.fi.":        true,
+		"google.github.io":  false, // Private suffix
+		"google.example.fi": false,
+		"google.invalid":    false,
+	}
+	for host, expected := range tests {
+		if got := isGoogleHost(host); got != expected {
+			t.Errorf("❌ Expected %v for %q, got %v", expected, host, got)
+		}
+	}
+}
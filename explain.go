@@ -0,0 +1,63 @@
+package main
+
+import (
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Single normalization step applied by normalizationSteps
+type normalizationStepResult struct {
+	Index   int // 1-based index among the steps in flags
+	Name    string
+	Before  string
+	After   string
+	Elapsed time.Duration
+}
+
+// Normalize URL one step at a time, in the order of FlagBit* constants,
+// returning the URL before and after each step in flags, and how long the
+// step took.
+func normalizationSteps(rawURL string, flags purell.NormalizationFlags) ([]normalizationStepResult, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+
+	var steps []normalizationStepResult
+	for _, mapping := range flagBitNormalizationFlags {
+		if flags&mapping.flags == 0 {
+			continue
+		}
+		before := u.String()
+		start := time.Now()
+		after := purell.NormalizeURL(u, mapping.flags)
+		steps = append(steps, normalizationStepResult{len(steps) + 1, mapping.name, before, after, time.Since(start)})
+	}
+	return steps, nil
+}
+
+// Normalize URL one step at a time, and explain what each step did.
+//
+// Returns lines like "Step 1 (sort query): 'https://example.com/?b=2&a=1' →
+// 'https://example.com/?a=1&b=2'". Steps are the ones in flags, in the order
+// of FlagBit* constants. Steps that didn't change the URL are only listed
+// when showUnchanged is set. Helps to see why different looking URLs end up
+// with the same signature.
+func normalizeURLExplain(rawURL string, flags purell.NormalizationFlags, showUnchanged bool) (string, error) {
+	steps, err := normalizationSteps(rawURL, flags)
+	if err != nil {
+		return "", err
+	}
+
+	var explanation strings.Builder
+	for _, step := range steps {
+		if step.Before == step.After && !showUnchanged {
+			continue
+		}
+		fmt.Fprintf(&explanation, "Step %d (%s): '%s' → '%s'\n", step.Index, step.Name, step.Before, step.After)
+	}
+	return explanation.String(), nil
+}
@@ -0,0 +1,40 @@
+package main
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestNormalizeURLExplain(t *testing.T) {
+	explanation, err := normalizeURLExplain("https://Example.com:443/a/../b?b=2&a=1", defaultNormalizationFlags, false)
+	if err != nil {
+		t.Fatalf("❌ Failed to explain: %v", err)
+	}
+	expected := "Step 1 (sort query): 'https://Example.com:443/a/../b?b=2&a=1' → 'https://Example.com:443/a/../b?a=1&b=2'\n" +
+		"Step 2 (remove dot segments): 'https://Example.com:443/a/../b?a=1&b=2' → 'https://Example.com:443/b?a=1&b=2'\n" +
+		"Step 4 (lowercase host): 'https://Example.com:443/b?a=1&b=2' → 'https://example.com:443/b?a=1&b=2'\n" +
+		"Step 5 (remove default port): 'https://example.com:443/b?a=1&b=2' → 'https://example.com/b?a=1&b=2'\n"
+	if explanation != expected {
+		t.Errorf("❌ Unexpected explanation\nExpected: %s\nGot: %s", expected, explanation)
+	}
+
+	// Last step ends with the fully normalized URL
+	lines := strings.Split(strings.TrimSuffix(explanation, "\n"), "\n")
+	normalized, _ := normalizeURL("https://Example.com:443/a/../b?b=2&a=1")
+	if !strings.HasSuffix(lines[len(lines)-1], "→ '"+normalized+"'") {
+		t.Errorf("❌ Expected last step to end with %s, got: %s", normalized, lines[len(lines)-1])
+	}
+
+	// Unchanged steps are listed on request
+	all, _ := normalizeURLExplain("https://example.com/", defaultNormalizationFlags, true)
+	if count := strings.Count(all, "\n"); count != 9 {
+		t.Errorf("❌ Expected all 9 default steps to be listed, got %d:\n%s", count, all)
+	}
+	if none, _ := normalizeURLExplain("https://example.com/", defaultNormalizationFlags, false); none != "" {
+		t.Errorf("❌ Expected no steps for normalized URL, got: %s", none)
+	}
+
+	if _, err := normalizeURLExplain("://invalid", defaultNormalizationFlags, false); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,98 @@
+package main
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/PuerkitoBio/purell"
+	"gopkg.in/yaml.v2"
+)
+
+// Normalization test case read from testdata/fixtures/*.yaml
+type normalizationFixture struct {
+	Description        string          `yaml:"description"`
+	Input              string          `yaml:"input"`
+	ExpectedNormalized string          `yaml:"expected_normalized,omitempty"`
+	ExpectedHash       string          `yaml:"expected_hash,omitempty"`  // Signature of the normalized URL
+	ExpectedError      string          `yaml:"expected_error,omitempty"` // Reason of URLParseError, instead of a normalized URL
+	Options            map[string]bool `yaml:"options,omitempty"`        // Steps to enable or disable, on top of the defaults
+}
+
+// Read normalization fixtures from YAML file
+func loadNormalizationFixtures(t *testing.T, path string) []normalizationFixture {
+	t.Helper()
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatalf("❌ Failed to read fixtures: %v", err)
+	}
+	var fixtures []normalizationFixture
+	if err := yaml.UnmarshalStrict(data, &fixtures); err != nil {
+		t.Fatalf("❌ Failed to parse fixtures %s: %v", path, err)
+	}
+	return fixtures
+}
+
+// Get normalization flags of fixture options. Options are named after the
+// steps of flagBitNormalizationFlags, with underscores instead of spaces.
+func fixtureFlags(t *testing.T, options map[string]bool) purell.NormalizationFlags {
+	t.Helper()
+	flags := defaultNormalizationFlags
+	for name, enabled := range options {
+		found := false
+		for _, mapping := range flagBitNormalizationFlags {
+			if strings.ReplaceAll(mapping.name, " ", "_") != name {
+				continue
+			}
+			found = true
+			if enabled {
+				flags |= mapping.flags
+			} else {
+				flags &^= mapping.flags
+			}
+		}
+		if !found {
+			t.Fatalf("❌ Unknown fixture option: %s", name)
+		}
+	}
+	return flags
+}
+
+func TestNormalizationFixtures(t *testing.T) {
+	files, err := filepath.Glob(filepath.Join("testdata", "fixtures", "*.yaml"))
+	if err != nil || len(files) == 0 {
+		t.Fatalf("❌ No fixture files found (error: %v)", err)
+	}
+
+	for _, file := range files {
+		for _, fixture := range loadNormalizationFixtures(t, file) {
+			t.Run(filepath.Base(file)+"/"+fixture.Description, func(t *testing.T) {
+				normalized, err := normalizeURLWithFlags(fixture.Input, fixtureFlags(t, fixture.Options))
+				if fixture.ExpectedError != "" {
+					var parseErr *URLParseError
+					if !errors.As(err, &parseErr) || !strings.Contains(parseErr.Err.Error(), fixture.ExpectedError) {
+						t.Fatalf("❌ Expected URLParseError with reason %q for: %s\nGot: %q (error: %v)", fixture.ExpectedError, fixture.Input, normalized, err)
+					}
+					return
+				}
+				if err != nil || normalized != fixture.ExpectedNormalized {
+					t.Fatalf("❌ Test failed for: %s\nExpected: %s\nGot: %s\nError: %v", fixture.Input, fixture.ExpectedNormalized, normalized, err)
+				}
+				if fixture.ExpectedHash != "" {
+					if signature := generateSignature(normalized); signature != fixture.ExpectedHash {
+						t.Errorf("❌ Signature mismatch for: %s\nExpected: %s\nGot: %s", fixture.Input, fixture.ExpectedHash, signature)
+					}
+				}
+				// Toggling a step changes the signature of NormalizeAndHashURL too
+				if len(fixture.Options) > 0 {
+					normalizedDefault, err := normalizeURLWithFlags(fixture.Input, defaultNormalizationFlags)
+					if err != nil || generateSignature(normalizedDefault) == generateSignature(normalized) {
+						t.Errorf("❌ Expected options to change the signature of: %s\nDefault: %s\nError: %v", fixture.Input, normalizedDefault, err)
+					}
+				}
+			})
+		}
+	}
+}
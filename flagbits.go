@@ -0,0 +1,65 @@
+package main
+
+import (
+	"github.com/PuerkitoBio/purell"
+)
+
+// Normalization flag bits for hosts that can't use purell flags directly,
+// such as WASM hosts. The bit layout is stable, new steps are only added to
+// the end.
+const (
+	// Sort query parameters by key
+	FlagBitSortQuery uint32 = 1 << iota
+	// Remove "." and ".." path segments
+	FlagBitRemoveDotSegments
+	// Lowercase the scheme
+	FlagBitLowercaseScheme
+	// Lowercase the host
+	FlagBitLowercaseHost
+	// Remove default port of the scheme, e.g. ":443" with https
+	FlagBitRemoveDefaultPort
+	// Remove the fragment
+	FlagBitRemoveFragment
+	// Remove "?" when the query is empty
+	FlagBitRemoveEmptyQuerySeparator
+	// Uppercase percent-encoded escapes, e.g. "%3a" to "%3A"
+	FlagBitUppercaseEscapes
+	// Decode escapes of unreserved characters, e.g. "%7E" to "~"
+	FlagBitDecodeUnnecessaryEscapes
+	// Encode characters that must be escaped
+	FlagBitEncodeNecessaryEscapes
+)
+
+// Purell flags and step name corresponding to each flag bit
+var flagBitNormalizationFlags = []struct {
+	bit   uint32
+	flags purell.NormalizationFlags
+	name  string
+}{
+	{FlagBitSortQuery, purell.FlagSortQuery, "sort query"},
+	{FlagBitRemoveDotSegments, purell.FlagRemoveDotSegments, "remove dot segments"},
+	{FlagBitLowercaseScheme, purell.FlagLowercaseScheme, "lowercase scheme"},
+	{FlagBitLowercaseHost, purell.FlagLowercaseHost, "lowercase host"},
+	{FlagBitRemoveDefaultPort, purell.FlagRemoveDefaultPort, "remove default port"},
+	{FlagBitRemoveFragment, purell.FlagRemoveFragment, "remove fragment"},
+	{FlagBitRemoveEmptyQuerySeparator, purell.FlagRemoveEmptyQuerySeparator, "remove empty query separator"},
+	{FlagBitUppercaseEscapes, purell.FlagUppercaseEscapes, "uppercase escapes"},
+	{FlagBitDecodeUnnecessaryEscapes, purell.FlagDecodeUnnecessaryEscapes, "decode unnecessary escapes"},
+	{FlagBitEncodeNecessaryEscapes, purell.FlagEncodeNecessaryEscapes, "encode necessary escapes"},
+}
+
+// Flag bits matching defaultNormalizationFlags
+const defaultFlagBits = FlagBitSortQuery | FlagBitRemoveDotSegments | FlagBitLowercaseScheme |
+	FlagBitLowercaseHost | FlagBitRemoveDefaultPort | FlagBitRemoveEmptyQuerySeparator |
+	FlagBitUppercaseEscapes | FlagBitDecodeUnnecessaryEscapes | FlagBitEncodeNecessaryEscapes
+
+// Convert flag bits into purell normalization flags, unknown bits are ignored
+func normalizationFlagsFromBits(bits uint32) purell.NormalizationFlags {
+	var flags purell.NormalizationFlags
+	for _, mapping := range flagBitNormalizationFlags {
+		if bits&mapping.bit != 0 {
+			flags |= mapping.flags
+		}
+	}
+	return flags
+}
@@ -0,0 +1,32 @@
+package main
+
+import (
+	"testing"
+)
+
+func TestNormalizationFlagsFromBits(t *testing.T) {
+	if got := normalizationFlagsFromBits(defaultFlagBits); got != defaultNormalizationFlags {
+		t.Errorf("❌ Default flag bits don't match default flags: %b != %b", got, defaultNormalizationFlags)
+	}
+	if got := normalizationFlagsFromBits(0); got != 0 {
+		t.Errorf("❌ Expected no flags, got %b", got)
+	}
+	if got := normalizationFlagsFromBits(1 << 31); got != 0 {
+		t.Errorf("❌ Expected unknown bits to be ignored, got %b", got)
+	}
+}
+
+// Effect of each step on URLs is tested by toggling it in
+// testdata/fixtures/normalize.yaml, see TestNormalizationFixtures.
+func TestNormalizationFlagBitsMapping(t *testing.T) {
+	var all uint32
+	for _, mapping := range flagBitNormalizationFlags {
+		if got := normalizationFlagsFromBits(mapping.bit); got != mapping.flags {
+			t.Errorf("❌ Expected bit %#x to map to %s flags %b, got %b", mapping.bit, mapping.name, mapping.flags, got)
+		}
+		if all&mapping.bit != 0 {
+			t.Errorf("❌ Bit %#x of %s is used twice", mapping.bit, mapping.name)
+		}
+		all |= mapping.bit
+	}
+}
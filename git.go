@@ -0,0 +1,51 @@
+package main
+
+import (
+	"errors"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+var errMissingGitRepository = errors.New("missing repository path in Git remote")
+
+// Hosting platforms whose Git remotes urlNormalizeGitRemote canonicalizes
+var gitHostingHosts = []string{"github.com", "gitlab.com", "bitbucket.org"}
+
+// SCP-like syntax of SSH remotes, "[user@]host:path"
+var scpLikeGitRemotePattern = regexp.MustCompile(`^(?:[\w.-]+@)?([\w.-]+):(.*)$`)
+
+// Normalize Git remote of a repository on GitHub, GitLab or Bitbucket into
+// the HTTPS URL of the repository, without "www." and ".git" suffix:
+//
+//	urlNormalizeGitRemote("git@github.com:user/repo.git") // "https://github.com/user/repo"
+//
+// Accepts SCP-like SSH remotes, and ssh, git, http and https URLs. Remotes
+// on other hosts are returned as-is, as their HTTPS URL can't be known.
+func urlNormalizeGitRemote(remote string) (string, error) {
+	var host, path string
+	if match := scpLikeGitRemotePattern.FindStringSubmatch(remote); match != nil && !strings.HasPrefix(match[2], "//") {
+		host, path = match[1], match[2]
+	} else {
+		u, err := parseURL(remote)
+		if err != nil {
+			return "", err
+		}
+		switch strings.ToLower(u.Scheme) {
+		case "https", "http", "git", "ssh", "git+ssh":
+		default:
+			return remote, nil
+		}
+		host, path = u.Hostname(), u.EscapedPath()
+	}
+
+	host = strings.TrimPrefix(strings.ToLower(host), "www.")
+	if !slices.Contains(gitHostingHosts, host) {
+		return remote, nil
+	}
+	path = strings.TrimSuffix(strings.Trim(path, "/"), ".git")
+	if path == "" {
+		return "", newURLParseError(remote, errMissingGitRepository)
+	}
+	return "https://" + host + "/" + path, nil
+}
@@ -0,0 +1,57 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLNormalizeGitRemote(t *testing.T) {
+	tests := map[string][]string{
+		"https://github.com/user/repo": {
+			"git@github.com:user/repo.git",
+			"git@github.com:user/repo",
+			"https://github.com/user/repo",
+			"https://github.com/user/repo.git",
+			"https://www.github.com/user/repo/",
+			"http://GitHub.com/user/repo.git",
+			"git://github.com/user/repo",
+			"ssh://git@github.com/user/repo.git",
+			"ssh://git@github.com:22/user/repo.git",
+			"git+ssh://git@github.com/user/repo.git",
+		},
+		"https://gitlab.com/group/subgroup/repo": {
+			"git@gitlab.com:group/subgroup/repo.git",
+			"https://gitlab.com/group/subgroup/repo.git",
+			"ssh://git@gitlab.com/group/subgroup/repo",
+		},
+		"https://bitbucket.org/team/repo": {
+			"git@bitbucket.org:team/repo.git",
+			"https://user@bitbucket.org/team/repo.git",
+			"https://bitbucket.org/team/repo",
+		},
+	}
+
+	for expected, inputs := range tests {
+		for _, input := range inputs {
+			if got, err := urlNormalizeGitRemote(input); err != nil || got != expected {
+				t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+			}
+		}
+	}
+
+	// Remotes on other hosts are returned as-is
+	for _, input := range []string{"git@example.com:user/repo.git", "https://git.example.com/user/repo.git", "file:///srv/git/repo.git", "/srv/git/repo.git"} {
+		if got, err := urlNormalizeGitRemote(input); err != nil || got != input {
+			t.Errorf("❌ Expected %q as-is, got %q (error: %v)", input, got, err)
+		}
+	}
+
+	for _, input := range []string{"git@github.com:", "https://github.com/", "https://github.com/.git"} {
+		if _, err := urlNormalizeGitRemote(input); !errors.Is(err, errMissingGitRepository) {
+			t.Errorf("❌ Expected errMissingGitRepository for %q, got: %v", input, err)
+		}
+	}
+	if _, err := urlNormalizeGitRemote("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
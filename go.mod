@@ -4,10 +4,8 @@ go 1.24.2
 
 require (
 	github.com/PuerkitoBio/purell v1.2.1
+	golang.org/x/net v0.40.0
 	gopkg.in/yaml.v2 v2.4.0
 )
 
-require (
-	golang.org/x/net v0.40.0 // indirect
-	golang.org/x/text v0.25.0 // indirect
-)
+require golang.org/x/text v0.25.0 // indirect
@@ -0,0 +1,374 @@
+package main
+
+import (
+	"bytes"
+	"crypto/hmac"
+	"crypto/sha256"
+	"crypto/sha512"
+	"encoding/binary"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"hash"
+	"io"
+	"maps"
+	"math"
+	"math/big"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Hash algorithm used for signatures unless told otherwise
+const defaultHashAlgorithm = "sha256"
+
+// Supported hash algorithms by name
+var hashAlgorithms = map[string]func() hash.Hash{
+	"sha256": sha256.New,
+	"sha512": sha512.New,
+}
+
+// Get names of supported hash algorithms, sorted
+func hashAlgorithmNames() []string {
+	return slices.Sorted(maps.Keys(hashAlgorithms))
+}
+
+// Generate hex encoded hash of the given string using named algorithm
+func generateSignatureWith(input string, algorithm string) (string, error) {
+	newHash, ok := hashAlgorithms[algorithm]
+	if !ok {
+		return "", fmt.Errorf("unknown hash algorithm: %s", algorithm)
+	}
+	if len(signatureHooks) == 0 {
+		h := newHash()
+		h.Write([]byte(input))
+		return hex.EncodeToString(h.Sum(nil)), nil
+	}
+	start := time.Now()
+	h := newHash()
+	h.Write([]byte(input))
+	signature := hex.EncodeToString(h.Sum(nil))
+	callSignatureHooks(input, algorithm, signature, start)
+	return signature, nil
+}
+
+// Normalize URL and write hex encoded SHA-256 hash of it into w, without
+// allocating the intermediate hex string. Nothing is written if
+// normalization fails.
+func hashURLToWriter(w io.Writer, rawURL string, flags purell.NormalizationFlags) error {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return err
+	}
+	start := time.Now()
+	digest := sha256.Sum256([]byte(normalized))
+	var encoded [sha256.Size * 2]byte
+	hex.Encode(encoded[:], digest[:])
+	if len(signatureHooks) > 0 {
+		callSignatureHooks(normalized, defaultHashAlgorithm, string(encoded[:]), start)
+	}
+	_, err = w.Write(encoded[:])
+	return err
+}
+
+// Normalization flags of stable hash version 1, the default flags of the 1.0
+// release spelled out. Never change these, add a new version instead.
+const stableV1NormalizationFlags = purell.FlagLowercaseScheme | purell.FlagLowercaseHost |
+	purell.FlagUppercaseEscapes | purell.FlagDecodeUnnecessaryEscapes | purell.FlagEncodeNecessaryEscapes |
+	purell.FlagRemoveDefaultPort | purell.FlagRemoveEmptyQuerySeparator |
+	purell.FlagRemoveDotSegments | purell.FlagSortQuery
+
+// Hash URL with SHA-256 after normalizing it with the pinned flags of
+// version 1. Unlike signatures with defaultNormalizationFlags, the hash of a
+// URL never changes when normalization steps are added, so it is safe to
+// store.
+//
+// Normalization calls purell directly, as in the 1.0 release, bypassing
+// normalizeURLWithFlags with its hooks and special cases, such as keeping
+// data URIs as-is, which would change the hash of some URLs.
+func hashStableV1(rawURL string) ([sha256.Size]byte, error) {
+	normalized, err := purell.NormalizeURLString(rawURL, stableV1NormalizationFlags)
+	if err != nil {
+		return [sha256.Size]byte{}, newURLParseError(rawURL, err)
+	}
+	return sha256.Sum256([]byte(normalized)), nil
+}
+
+// Get hex encoded hash of URL from the latest stable hash version, see
+// hashStableV1.
+func urlHashStableAcrossVersions(rawURL string) (string, error) {
+	digest, err := hashStableV1(rawURL)
+	if err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(digest[:]), nil
+}
+
+// Range of hash prefix lengths in bytes of short IDs
+const (
+	minShortIDBytes = 4
+	maxShortIDBytes = sha256.Size
+)
+
+var (
+	errShortIDLength  = fmt.Errorf("short ID length must be between %d and %d bytes", minShortIDBytes, maxShortIDBytes)
+	errInvalidShortID = errors.New("invalid short ID")
+)
+
+// Number of Base62 digits encoding n bytes
+func shortIDEncodedLen(n int) int {
+	return int(math.Ceil(float64(n) * 8 / math.Log2(62)))
+}
+
+// Get a compact identifier of URL, such as for short links: the first length
+// bytes of its stable hash, see hashStableV1, encoded with Base62 digits
+// 0-9, a-z and A-Z. Length must be between 4 and 32, and 8 bytes give 11
+// digits.
+//
+// The ID is padded with zeros to the same number of digits for the same
+// length, so shortIDToHashPrefix can decode it. Short prefixes collide
+// sooner, and 4 bytes are expected to collide within about 77,000 URLs.
+func urlHashToShortID(rawURL string, length int) (string, error) {
+	if length < minShortIDBytes || length > maxShortIDBytes {
+		return "", errShortIDLength
+	}
+	digest, err := hashStableV1(rawURL)
+	if err != nil {
+		return "", err
+	}
+	id := new(big.Int).SetBytes(digest[:length]).Text(62)
+	return strings.Repeat("0", shortIDEncodedLen(length)-len(id)) + id, nil
+}
+
+// Decode short ID from urlHashToShortID back into the hash prefix it was
+// encoded from.
+func shortIDToHashPrefix(id string) ([]byte, error) {
+	length := 0
+	for n := minShortIDBytes; n <= maxShortIDBytes; n++ {
+		if shortIDEncodedLen(n) == len(id) {
+			length = n
+			break
+		}
+	}
+	if length == 0 {
+		return nil, errInvalidShortID
+	}
+	// Checked, as SetString would also accept a sign
+	for _, c := range id {
+		if !('0' <= c && c <= '9' || 'a' <= c && c <= 'z' || 'A' <= c && c <= 'Z') {
+			return nil, errInvalidShortID
+		}
+	}
+	n, ok := new(big.Int).SetString(id, 62)
+	if !ok || n.BitLen() > 8*length {
+		return nil, errInvalidShortID
+	}
+	return n.FillBytes(make([]byte, length)), nil
+}
+
+// Hash URL together with a timestamp, for signatures valid in a time window.
+//
+// The 8-byte big-endian timestamp is appended to the URL before hashing with
+// SHA-256. Callers choose the window by how they compute the timestamp, e.g.
+// Unix time divided by 3600 for hourly windows. URL is hashed as-is, without
+// normalization.
+func hashURLWithTimestamp(rawURL string, timestampSecs uint64) [sha256.Size]byte {
+	h := sha256.New()
+	h.Write([]byte(rawURL))
+	h.Write(binary.BigEndian.AppendUint64(nil, timestampSecs))
+	var digest [sha256.Size]byte
+	h.Sum(digest[:0])
+	return digest
+}
+
+var errEmptyHMACKey = errors.New("HMAC key must not be empty")
+
+// Compute HMAC of data with key, using named hash algorithm
+func hmacWith(data, key []byte, algorithm string) ([]byte, error) {
+	newHash, ok := hashAlgorithms[algorithm]
+	if !ok {
+		return nil, fmt.Errorf("unknown hash algorithm: %s", algorithm)
+	}
+	mac := hmac.New(newHash, key)
+	mac.Write(data)
+	return mac.Sum(nil), nil
+}
+
+// Normalize URL and compute HMAC of it with key, using named hash algorithm.
+//
+// Unlike plain signatures, the MAC can't be computed for a guessed URL
+// without the key, so it is safe to expose, e.g. in signed links. Key must not
+// be empty. Returns the raw MAC bytes.
+func hashURLSaltedHMAC(rawURL string, key []byte, algorithm string) ([]byte, error) {
+	if len(key) == 0 {
+		return nil, errEmptyHMACKey
+	}
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	return hmacWith([]byte(normalized), key, algorithm)
+}
+
+// Compute a fingerprint of a set of URLs, for checking cheaply whether two
+// sets are identical.
+//
+// Each URL is normalized and hashed with SHA-256, and the fingerprint is the
+// SHA-256 of the sorted hashes, so it doesn't depend on the order of URLs.
+// URLs normalizing to the same form are counted once.
+func computeURLChecksum(urls []string, flags purell.NormalizationFlags) ([sha256.Size]byte, error) {
+	hashes := make([][sha256.Size]byte, 0, len(urls))
+	for _, rawURL := range urls {
+		normalized, err := normalizeURLWithFlags(rawURL, flags)
+		if err != nil {
+			return [sha256.Size]byte{}, err
+		}
+		hashes = append(hashes, sha256.Sum256([]byte(normalized)))
+	}
+	slices.SortFunc(hashes, func(a, b [sha256.Size]byte) int {
+		return bytes.Compare(a[:], b[:])
+	})
+	hashes = slices.Compact(hashes)
+
+	h := sha256.New()
+	for _, digest := range hashes {
+		h.Write(digest[:])
+	}
+	var checksum [sha256.Size]byte
+	h.Sum(checksum[:0])
+	return checksum, nil
+}
+
+var errNoBuckets = errors.New("number of buckets must be positive")
+
+// Assign URL to one of n buckets, for sharding URL processing. The bucket is
+// the first 4 bytes of the SHA-256 hash of the normalized URL as big-endian
+// integer, modulo n, so URLs with the same canonical form always get the
+// same bucket.
+//
+// Changing n moves most URLs to other buckets; consistent hashing is needed
+// for resharding with minimal moves.
+func urlBucket(rawURL string, n uint32, flags purell.NormalizationFlags) (uint32, error) {
+	if n == 0 {
+		return 0, errNoBuckets
+	}
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return 0, err
+	}
+	digest := sha256.Sum256([]byte(normalized))
+	return binary.BigEndian.Uint32(digest[:4]) % n, nil
+}
+
+// SHA-256 truncated to 64 bits, for use where a hash.Hash64 is expected
+type sha256Hash64 struct {
+	hash.Hash
+}
+
+// Create a hash.Hash64 computing SHA-256, with Sum64 returning the first 8
+// bytes of the digest as big-endian integer.
+func newSha256Hash64() hash.Hash64 {
+	return sha256Hash64{sha256.New()}
+}
+
+func (h sha256Hash64) Sum64() uint64 {
+	return binary.BigEndian.Uint64(h.Sum(nil))
+}
+
+// HashLengthError is returned when a hex encoded hash has wrong length in bytes.
+type HashLengthError int
+
+func (e HashLengthError) Error() string {
+	return fmt.Sprintf("hash must be %d hex characters, got %d bytes", hex.EncodedLen(sha256.Size), int(e))
+}
+
+// HashCharError is returned when a hex encoded hash contains a character that
+// isn't lowercase hexadecimal.
+type HashCharError rune
+
+func (e HashCharError) Error() string {
+	return fmt.Sprintf("invalid hex character %q in hash", rune(e))
+}
+
+// Types of malformed hashes detected by validateHash
+type HashValidationKind int
+
+const (
+	HashWrongLength HashValidationKind = iota
+	HashInvalidCharacter
+	HashUppercaseHex
+)
+
+// HashValidationError is returned by validateHash for malformed hashes.
+type HashValidationError struct {
+	Kind HashValidationKind
+	Want int  // Expected length in hex characters, with HashWrongLength
+	Got  int  // Length in bytes, with HashWrongLength
+	Pos  int  // Byte offset of the character, with HashInvalidCharacter and HashUppercaseHex
+	Char rune // Offending character, with HashInvalidCharacter and HashUppercaseHex
+}
+
+func (e *HashValidationError) Error() string {
+	switch e.Kind {
+	case HashWrongLength:
+		return fmt.Sprintf("hash must be %d hex characters, got %d bytes", e.Want, e.Got)
+	case HashUppercaseHex:
+		return fmt.Sprintf("hash must be lowercase hex, got %q at %d", e.Char, e.Pos)
+	}
+	return fmt.Sprintf("invalid character %q at %d in hash", e.Char, e.Pos)
+}
+
+// Check that a hex encoded hash of a digest of size bytes is well-formed:
+// exactly 2*size lowercase hexadecimal characters, without whitespace, e.g.
+// 64 for SHA-256 with sha256.Size.
+func validateHash(s string, size int) error {
+	if want := hex.EncodedLen(size); len(s) != want {
+		return &HashValidationError{Kind: HashWrongLength, Want: want, Got: len(s)}
+	}
+	for i, c := range s {
+		switch {
+		case '0' <= c && c <= '9' || 'a' <= c && c <= 'f':
+		case 'A' <= c && c <= 'F':
+			return &HashValidationError{Kind: HashUppercaseHex, Pos: i, Char: c}
+		default:
+			return &HashValidationError{Kind: HashInvalidCharacter, Pos: i, Char: c}
+		}
+	}
+	return nil
+}
+
+// Decode a lowercase hex encoded SHA-256 hash into raw bytes
+func hexToHashBytes(s string) ([sha256.Size]byte, error) {
+	var digest [sha256.Size]byte
+	if len(s) != hex.EncodedLen(sha256.Size) {
+		return digest, HashLengthError(len(s))
+	}
+	for _, c := range s {
+		if !('0' <= c && c <= '9' || 'a' <= c && c <= 'f') {
+			return digest, HashCharError(c)
+		}
+	}
+
+	fromHex := func(c byte) byte {
+		if c <= '9' {
+			return c - '0'
+		}
+		return c - 'a' + 10
+	}
+	for i := range digest {
+		digest[i] = fromHex(s[2*i])<<4 | fromHex(s[2*i+1])
+	}
+	return digest, nil
+}
+
+// Get signature of URL as raw SHA-256 bytes instead of hex, for hosts storing
+// hashes as binary data. Same as decoding the result of getSignature.
+func getSignatureRaw(inputURL string) ([sha256.Size]byte, error) {
+	signature, err := getSignature(inputURL)
+	if err != nil {
+		return [sha256.Size]byte{}, err
+	}
+	return hexToHashBytes(signature)
+}
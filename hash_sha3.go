@@ -0,0 +1,16 @@
+//go:build sha3
+// +build sha3
+
+package main
+
+import (
+	"crypto/sha3"
+	"hash"
+)
+
+// SHA-3 is opt-in with the sha3 build tag, to keep wasm builds that only
+// need SHA-256 small.
+func init() {
+	hashAlgorithms["sha3-256"] = func() hash.Hash { return sha3.New256() }
+	hashAlgorithms["sha3-512"] = func() hash.Hash { return sha3.New512() }
+}
@@ -0,0 +1,32 @@
+//go:build sha3
+// +build sha3
+
+package main
+
+import (
+	"testing"
+)
+
+func TestSha3Signature(t *testing.T) {
+	// Sample messages from NIST SHA-3 examples
+	tests := []struct {
+		algorithm string
+		input     string
+		expected  string
+	}{
+		{"sha3-256", "", "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"},
+		{"sha3-256", "abc", "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"},
+		{"sha3-512", "", "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"},
+		{"sha3-512", "abc", "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"},
+	}
+
+	for _, test := range tests {
+		got, err := generateSignatureWith(test.input, test.algorithm)
+		if err != nil {
+			t.Fatalf("❌ Failed to hash with %s: %v", test.algorithm, err)
+		}
+		if got != test.expected {
+			t.Errorf("❌ %s(%q): expected %s, got %s", test.algorithm, test.input, test.expected, got)
+		}
+	}
+}
@@ -0,0 +1,436 @@
+package main
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"crypto/sha512"
+	"encoding/binary"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+	"testing"
+)
+
+func TestSha256Hash64(t *testing.T) {
+	const input = "https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"
+	digest := sha256.Sum256([]byte(input))
+
+	h := newSha256Hash64()
+	h.Write([]byte(input[:10]))
+	h.Write([]byte(input[10:]))
+	if got, expected := h.Sum64(), binary.BigEndian.Uint64(digest[:8]); got != expected {
+		t.Errorf("❌ Expected %x, got %x", expected, got)
+	}
+	if h.Size() != sha256.Size {
+		t.Errorf("❌ Expected size %d, got %d", sha256.Size, h.Size())
+	}
+
+	// Same input hashes the same after reset, and differs from other input
+	first := h.Sum64()
+	h.Reset()
+	h.Write([]byte(input))
+	if h.Sum64() != first {
+		t.Error("❌ Hash changed after reset")
+	}
+	h.Reset()
+	h.Write([]byte("https://example.com/"))
+	if h.Sum64() == first {
+		t.Error("❌ Different inputs produced the same hash")
+	}
+}
+
+func TestHexToHashBytes(t *testing.T) {
+	const signature = "7e530349c32069a7dc25485ee2886f8f88e4b8560202fec1cb3200bd8c550b4c"
+	expected := sha256.Sum256([]byte("https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"))
+
+	got, err := hexToHashBytes(signature)
+	if err != nil || got != expected {
+		t.Fatalf("❌ Expected %x, got %x (error: %v)", expected, got, err)
+	}
+	if hex.EncodeToString(got[:]) != signature {
+		t.Errorf("❌ Round trip mismatch: %x", got)
+	}
+
+	lengthTests := map[string]int{
+		"":              0,
+		signature[:63]:  63,
+		signature + "0": 65,
+	}
+	for input, length := range lengthTests {
+		var lengthErr HashLengthError
+		if _, err := hexToHashBytes(input); !errors.As(err, &lengthErr) || int(lengthErr) != length {
+			t.Errorf("❌ Expected HashLengthError(%d) for %q, got: %v", length, input, err)
+		}
+	}
+
+	charTests := map[string]rune{
+		strings.ToUpper(signature): 'E',
+		"g" + signature[1:]:        'g',
+		signature[:62] + "é":       'é',
+		signature[:63] + " ":       ' ',
+	}
+	for input, char := range charTests {
+		var charErr HashCharError
+		if _, err := hexToHashBytes(input); !errors.As(err, &charErr) || rune(charErr) != char {
+			t.Errorf("❌ Expected HashCharError(%q) for %q, got: %v", char, input, err)
+		}
+	}
+}
+
+func TestValidateHash(t *testing.T) {
+	const signature = "7e530349c32069a7dc25485ee2886f8f88e4b8560202fec1cb3200bd8c550b4c"
+	if err := validateHash(signature, sha256.Size); err != nil {
+		t.Errorf("❌ Expected valid hash, got: %v", err)
+	}
+
+	tests := []struct {
+		name     string
+		input    string
+		expected HashValidationError
+	}{
+		{"empty", "", HashValidationError{Kind: HashWrongLength, Want: 64, Got: 0}},
+		{"too short", signature[:63], HashValidationError{Kind: HashWrongLength, Want: 64, Got: 63}},
+		{"too long", signature + "0", HashValidationError{Kind: HashWrongLength, Want: 64, Got: 65}},
+		{"uppercase", strings.ToUpper(signature), HashValidationError{Kind: HashUppercaseHex, Pos: 1, Char: 'E'}},
+		{"invalid character", "g" + signature[1:], HashValidationError{Kind: HashInvalidCharacter, Pos: 0, Char: 'g'}},
+		{"whitespace", signature[:63] + " ", HashValidationError{Kind: HashInvalidCharacter, Pos: 63, Char: ' '}},
+		{"leading whitespace", "\t" + signature[1:], HashValidationError{Kind: HashInvalidCharacter, Pos: 0, Char: '\t'}},
+		{"multibyte", signature[:62] + "é", HashValidationError{Kind: HashInvalidCharacter, Pos: 62, Char: 'é'}},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			var validationErr *HashValidationError
+			if err := validateHash(test.input, sha256.Size); !errors.As(err, &validationErr) || *validationErr != test.expected {
+				t.Errorf("❌ Expected %+v, got: %v", test.expected, err)
+			}
+		})
+	}
+
+	// Other digest sizes are validated the same way
+	sha512Signature := strings.Repeat(signature, 2)
+	if err := validateHash(sha512Signature, sha512.Size); err != nil {
+		t.Errorf("❌ Expected valid SHA-512 hash, got: %v", err)
+	}
+	for input, expected := range map[string]HashValidationError{
+		signature:                        {Kind: HashWrongLength, Want: 128, Got: 64},
+		strings.ToUpper(sha512Signature): {Kind: HashUppercaseHex, Pos: 1, Char: 'E'},
+		sha512Signature[:127] + "x":      {Kind: HashInvalidCharacter, Pos: 127, Char: 'x'},
+	} {
+		var validationErr *HashValidationError
+		if err := validateHash(input, sha512.Size); !errors.As(err, &validationErr) || *validationErr != expected {
+			t.Errorf("❌ Expected %+v for %q, got: %v", expected, input, err)
+		}
+	}
+}
+
+func TestHashURLToWriter(t *testing.T) {
+	const rawURL = "HTTPS://Example.com:443/a/../b?z=1&y=2"
+	normalized, _ := normalizeURL(rawURL)
+	digest := sha256.Sum256([]byte(normalized))
+
+	var buf bytes.Buffer
+	for range 2 {
+		if err := hashURLToWriter(&buf, rawURL, defaultNormalizationFlags); err != nil {
+			t.Fatalf("❌ Failed to hash: %v", err)
+		}
+		buf.WriteByte('\n')
+	}
+	expected := strings.Repeat(hex.EncodeToString(digest[:])+"\n", 2)
+	if buf.String() != expected {
+		t.Errorf("❌ Expected %q, got %q", expected, buf.String())
+	}
+	if buf.String()[:64] != generateSignature(normalized) {
+		t.Errorf("❌ Written hash doesn't match generateSignature")
+	}
+
+	buf.Reset()
+	if err := hashURLToWriter(&buf, "://invalid", defaultNormalizationFlags); err == nil || buf.Len() != 0 {
+		t.Errorf("❌ Expected error and no output, got %q (error: %v)", buf.String(), err)
+	}
+}
+
+func TestComputeURLChecksum(t *testing.T) {
+	urls := []string{
+		"https://example.com/a",
+		"https://example.com/b?y=2&x=1",
+		"https://example.org/",
+	}
+	checksum, err := computeURLChecksum(urls, defaultNormalizationFlags)
+	if err != nil {
+		t.Fatalf("❌ Failed to compute checksum: %v", err)
+	}
+
+	reordered := []string{urls[2], urls[0], urls[1]}
+	if other, err := computeURLChecksum(reordered, defaultNormalizationFlags); err != nil || other != checksum {
+		t.Errorf("❌ Expected same checksum in different order, got %x (error: %v)", other, err)
+	}
+
+	// Same set in different forms, with a duplicate
+	equivalent := []string{"HTTPS://Example.com:443/a", "https://example.com/b?x=1&y=2", "https://example.org/", "https://example.com/a"}
+	if other, err := computeURLChecksum(equivalent, defaultNormalizationFlags); err != nil || other != checksum {
+		t.Errorf("❌ Expected same checksum for equivalent URLs, got %x (error: %v)", other, err)
+	}
+
+	for _, changed := range [][]string{
+		append(slices.Clone(urls), "https://example.com/c"),
+		urls[:2],
+		nil,
+	} {
+		if other, err := computeURLChecksum(changed, defaultNormalizationFlags); err != nil || other == checksum {
+			t.Errorf("❌ Expected different checksum for %v, got %x (error: %v)", changed, other, err)
+		}
+	}
+
+	if _, err := computeURLChecksum([]string{urls[0], "://invalid"}, defaultNormalizationFlags); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestHashURLWithTimestamp(t *testing.T) {
+	const rawURL = "https://example.com/a"
+	const window = 3600
+	hourly := func(unix uint64) [sha256.Size]byte {
+		return hashURLWithTimestamp(rawURL, unix/window)
+	}
+
+	if hashURLWithTimestamp(rawURL, 42) != hashURLWithTimestamp(rawURL, 42) {
+		t.Error("❌ Same URL and timestamp produced different hashes")
+	}
+	if hashURLWithTimestamp(rawURL, 42) == hashURLWithTimestamp(rawURL, 43) {
+		t.Error("❌ Different timestamps produced the same hash")
+	}
+	if hashURLWithTimestamp(rawURL, 42) == hashURLWithTimestamp("https://example.com/b", 42) {
+		t.Error("❌ Different URLs produced the same hash")
+	}
+
+	// Hash stays the same within a window, and changes at its boundary
+	if hourly(7200) != hourly(10799) {
+		t.Error("❌ Hash changed within a window")
+	}
+	if hourly(7199) == hourly(7200) {
+		t.Error("❌ Hash didn't change at window boundary")
+	}
+
+	var timestamp [8]byte
+	binary.BigEndian.PutUint64(timestamp[:], 42)
+	if expected := sha256.Sum256(append([]byte(rawURL), timestamp[:]...)); hashURLWithTimestamp(rawURL, 42) != expected {
+		t.Errorf("❌ Expected SHA-256 of URL and big-endian timestamp %x", expected)
+	}
+}
+
+func TestGetSignatureRaw(t *testing.T) {
+	if err := LoadRules(DefaultCfgData); err != nil {
+		t.Fatalf("❌ Failed to load rules: %v", err)
+	}
+
+	const rawURL = "https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"
+	signature, err := getSignature(rawURL)
+	if err != nil {
+		t.Fatalf("❌ Failed to get signature: %v", err)
+	}
+	expected, _ := hex.DecodeString(signature)
+
+	digest, err := getSignatureRaw(rawURL)
+	if err != nil || !bytes.Equal(digest[:], expected) {
+		t.Errorf("❌ Expected %x, got %x (error: %v)", expected, digest, err)
+	}
+
+	if _, err := getSignatureRaw("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestHashStableV1(t *testing.T) {
+	// Golden hashes of version 1, never change these
+	tests := map[string]string{
+		"https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80": "7e530349c32069a7dc25485ee2886f8f88e4b8560202fec1cb3200bd8c550b4c",
+		"HTTPS://Example.com:443/a/../b?z=1&y=2":                                  "c4baeb3d3da63e5822846615da260fd1780912ff9434191bdeaf2e4429cff478",
+		"https://example.com/%7euser":                                             "ab9c6a86a4b68c3b76dfbab50c3e670793d220c2edf5cd9abe5189cffb4d5ae5",
+		// Data URIs are normalized like other URLs, lowercasing the scheme
+		"data:text/plain,hello":           "e5dd724e20159bcb2c3dcf70eca829e7b39decb6c72beff5aa37ae885df61f51",
+		"DATA:text/plain,hello":           "e5dd724e20159bcb2c3dcf70eca829e7b39decb6c72beff5aa37ae885df61f51",
+		"data:text/plain;base64,aGVsbG8=": "134c40e7dfa053dccdea35bf3322344afcaf65acda86cccce5c14290ef993107",
+	}
+
+	for input, expected := range tests {
+		digest, err := hashStableV1(input)
+		if err != nil || hex.EncodeToString(digest[:]) != expected {
+			t.Errorf("❌ Expected %s for %q, got %x (error: %v)", expected, input, digest, err)
+		}
+		if signature, err := urlHashStableAcrossVersions(input); err != nil || signature != expected {
+			t.Errorf("❌ Expected latest stable hash %s for %q, got %s (error: %v)", expected, input, signature, err)
+		}
+	}
+
+	if _, err := hashStableV1("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestHMACWith(t *testing.T) {
+	// Test cases 1 and 2 of RFC 4231
+	tests := []struct {
+		key       []byte
+		data      string
+		algorithm string
+		expected  string
+	}{
+		{bytes.Repeat([]byte{0x0b}, 20), "Hi There", "sha256", "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"},
+		{bytes.Repeat([]byte{0x0b}, 20), "Hi There", "sha512", "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"},
+		{[]byte("Jefe"), "what do ya want for nothing?", "sha256", "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"},
+		{[]byte("Jefe"), "what do ya want for nothing?", "sha512", "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"},
+	}
+
+	for _, test := range tests {
+		mac, err := hmacWith([]byte(test.data), test.key, test.algorithm)
+		if err != nil || hex.EncodeToString(mac) != test.expected {
+			t.Errorf("❌ Expected %s HMAC %s for %q, got %x (error: %v)", test.algorithm, test.expected, test.data, mac, err)
+		}
+	}
+
+	if _, err := hmacWith([]byte("Hi There"), []byte("Jefe"), "md5"); err == nil {
+		t.Error("❌ Expected error for unknown algorithm")
+	}
+}
+
+func TestHashURLSaltedHMAC(t *testing.T) {
+	// Keys of RFC 4231 test cases 1 and 2, with a URL as data. Equivalent URLs
+	// must get the MAC of the normalized URL "https://example.com/a/b?a=2&z=1",
+	// computed independently with Python's hmac module.
+	rawURL := "HTTPS://Example.com:443/a/./b?z=1&a=2"
+	tests := []struct {
+		key       []byte
+		algorithm string
+		expected  string
+	}{
+		{bytes.Repeat([]byte{0x0b}, 20), "sha256", "d059a23b5e78a95a2b58679b40987a5bf103146bc8b5b044d9f5c6984161bd24"},
+		{bytes.Repeat([]byte{0x0b}, 20), "sha512", "2e691c81ebe98a4fb86aa9a2793831a429780e6cc5101c4aaa1dbd94626fdebd212fdd02adbd2b4f6269b40899e8835d46bb66af362373336ecc3983b1b7e264"},
+		{[]byte("Jefe"), "sha256", "4afc50b557fd0f1d7675ba24e076dbc84ee7919336a7e50ce9d15b16a5be62a0"},
+		{[]byte("Jefe"), "sha512", "9edb36df92fbaaa4c51e4eec6a202580741eca0aae7672b8db659e632f0cae5f7f4d6c218add76efa9ff2a9c0f0a2ba753644f188fa49ebc5f7658739cc7479a"},
+	}
+	for _, test := range tests {
+		mac, err := hashURLSaltedHMAC(rawURL, test.key, test.algorithm)
+		if err != nil || hex.EncodeToString(mac) != test.expected {
+			t.Errorf("❌ Expected %s HMAC %s with key %q, got %x (error: %v)", test.algorithm, test.expected, test.key, mac, err)
+		}
+	}
+
+	// Different keys give different MACs
+	mac1, _ := hashURLSaltedHMAC(rawURL, []byte("key1"), defaultHashAlgorithm)
+	mac2, _ := hashURLSaltedHMAC(rawURL, []byte("key2"), defaultHashAlgorithm)
+	if bytes.Equal(mac1, mac2) {
+		t.Error("❌ Expected different MACs for different keys")
+	}
+
+	if _, err := hashURLSaltedHMAC(rawURL, nil, defaultHashAlgorithm); !errors.Is(err, errEmptyHMACKey) {
+		t.Errorf("❌ Expected empty key error, got: %v", err)
+	}
+	if _, err := hashURLSaltedHMAC(rawURL, []byte("Jefe"), "md5"); err == nil {
+		t.Error("❌ Expected error for unknown algorithm")
+	}
+	if _, err := hashURLSaltedHMAC("://invalid", []byte("Jefe"), defaultHashAlgorithm); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLBucket(t *testing.T) {
+	const rawURL = "https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"
+	// First 4 bytes of the known signature are 0x7e530349
+	if bucket, err := urlBucket(rawURL, 1000, defaultNormalizationFlags); err != nil || bucket != 0x7e530349%1000 {
+		t.Errorf("❌ Expected bucket %d, got %d (error: %v)", 0x7e530349%1000, bucket, err)
+	}
+
+	// Equivalent URLs get the same bucket
+	a, _ := urlBucket("HTTPS://Example.com:443/a/./b?z=1&a=2", 16, defaultNormalizationFlags)
+	b, _ := urlBucket("https://example.com/a/b?a=2&z=1", 16, defaultNormalizationFlags)
+	if a != b {
+		t.Errorf("❌ Expected same bucket for equivalent URLs, got %d and %d", a, b)
+	}
+
+	// Roughly uniform over many URLs
+	const n, urls = 8, 8000
+	counts := make([]int, n)
+	for i := range urls {
+		bucket, err := urlBucket(fmt.Sprintf("https://example.com/item/%d", i), n, defaultNormalizationFlags)
+		if err != nil || bucket >= n {
+			t.Fatalf("❌ Expected bucket below %d, got %d (error: %v)", n, bucket, err)
+		}
+		counts[bucket]++
+	}
+	for bucket, count := range counts {
+		if count < urls/n*8/10 || count > urls/n*12/10 {
+			t.Errorf("❌ Expected about %d URLs in bucket %d, got %d", urls/n, bucket, count)
+		}
+	}
+
+	for i := range 10 {
+		if bucket, err := urlBucket(fmt.Sprintf("https://example.com/%d", i), 1, defaultNormalizationFlags); err != nil || bucket != 0 {
+			t.Errorf("❌ Expected bucket 0 with one bucket, got %d (error: %v)", bucket, err)
+		}
+	}
+	if _, err := urlBucket(rawURL, 0, defaultNormalizationFlags); !errors.Is(err, errNoBuckets) {
+		t.Errorf("❌ Expected errNoBuckets, got: %v", err)
+	}
+	if _, err := urlBucket("://invalid", 8, defaultNormalizationFlags); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLHashToShortID(t *testing.T) {
+	const rawURL = "https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"
+	digest, err := hashStableV1(rawURL)
+	if err != nil {
+		t.Fatalf("❌ Failed to hash URL: %v", err)
+	}
+
+	for length, expectedLen := range map[int]int{4: 6, 8: 11, 16: 22, 32: 43} {
+		id, err := urlHashToShortID(rawURL, length)
+		if err != nil || len(id) != expectedLen {
+			t.Errorf("❌ Expected %d characters for length %d, got %q (error: %v)", expectedLen, length, id, err)
+		}
+		if strings.Trim(id, "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ") != "" {
+			t.Errorf("❌ Expected only Base62 characters, got %q", id)
+		}
+		if prefix, err := shortIDToHashPrefix(id); err != nil || !bytes.Equal(prefix, digest[:length]) {
+			t.Errorf("❌ Expected hash prefix %x for %q, got %x (error: %v)", digest[:length], id, prefix, err)
+		}
+	}
+
+	// Golden IDs of stable hash version 1, never change these
+	for input, expected := range map[string]string{
+		rawURL:                  "aQq6MvVM3uD",
+		"DATA:text/plain,hello": "jJz6eaDn9DZ",
+	} {
+		if id, err := urlHashToShortID(input, 8); err != nil || id != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, id, err)
+		}
+	}
+
+	// Same URL in another form gets the same ID
+	a, _ := urlHashToShortID(rawURL, 8)
+	if b, err := urlHashToShortID("HTTPS://WWW.Iltalehti.fi:443/kotimaa/a/./7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80", 8); err != nil || a != b {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", a, b, err)
+	}
+
+	// Leading zero bytes are kept
+	if prefix, err := shortIDToHashPrefix("00000000001"); err != nil || !bytes.Equal(prefix, []byte{0, 0, 0, 0, 0, 0, 0, 1}) {
+		t.Errorf("❌ Expected 7 zero bytes and 1, got %x (error: %v)", prefix, err)
+	}
+
+	for _, length := range []int{0, 3, 33} {
+		if _, err := urlHashToShortID(rawURL, length); !errors.Is(err, errShortIDLength) {
+			t.Errorf("❌ Expected errShortIDLength for length %d, got: %v", length, err)
+		}
+	}
+	for _, id := range []string{"", "abc", "-0000000001", "0000000000_", "ZZZZZZZZZZZ"} {
+		if _, err := shortIDToHashPrefix(id); !errors.Is(err, errInvalidShortID) {
+			t.Errorf("❌ Expected errInvalidShortID for %q, got: %v", id, err)
+		}
+	}
+	if _, err := urlHashToShortID("://invalid", 8); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
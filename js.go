@@ -5,6 +5,7 @@ package main // Build when target is wasm
 
 import (
 	"fmt"
+	"strings"
 	"syscall/js"
 )
 
@@ -17,8 +18,76 @@ func hashUrl(this js.Value, args []js.Value) any {
 	return hash
 }
 
+// Sanitize URL, returns null if the URL is dangerous or invalid
+func sanitizeUrl(this js.Value, args []js.Value) any {
+	url := args[0].String()
+	sanitized, err := sanitizeURL(url)
+	if err != nil {
+		return nil
+	}
+	return sanitized
+}
+
+// Register UrlNormalizerConfig class, holding normalization steps used by
+// normalizeUrlWithConfig as flag bits. Each step has a setter named after it,
+// such as setSortQuery(bool) and setLowercaseScheme(bool), returning the
+// config for chaining:
+//
+//	const config = new UrlNormalizerConfig().setSortQuery(false);
+//	normalizeUrlWithConfig(url, config);
+func registerUrlNormalizerConfig() {
+	constructor := js.FuncOf(func(this js.Value, args []js.Value) any {
+		this.Set("flags", defaultFlagBits)
+		return this
+	})
+	prototype := constructor.Get("prototype")
+	for _, mapping := range flagBitNormalizationFlags {
+		bit := mapping.bit
+		prototype.Set(configSetterName(mapping.name), js.FuncOf(func(this js.Value, args []js.Value) any {
+			flags := uint32(this.Get("flags").Int())
+			if len(args) > 0 && args[0].Truthy() {
+				flags |= bit
+			} else {
+				flags &^= bit
+			}
+			this.Set("flags", flags)
+			return this
+		}))
+	}
+	js.Global().Set("UrlNormalizerConfig", constructor)
+}
+
+// Get name of the setter of a normalization step, e.g. "setSortQuery" for
+// "sort query"
+func configSetterName(step string) string {
+	var b strings.Builder
+	b.WriteString("set")
+	for _, word := range strings.Fields(step) {
+		b.WriteString(strings.ToUpper(word[:1]) + word[1:])
+	}
+	return b.String()
+}
+
+// Normalize URL without site rules, using steps of a UrlNormalizerConfig.
+// Returns null if the URL can't be normalized.
+func normalizeUrlWithConfig(this js.Value, args []js.Value) any {
+	if len(args) < 2 || args[1].Type() != js.TypeObject || args[1].Get("flags").Type() != js.TypeNumber {
+		return nil
+	}
+	url := args[0].String()
+	flags := normalizationFlagsFromBits(uint32(args[1].Get("flags").Int()))
+	normalized, err := normalizeURLWithFlags(url, flags)
+	if err != nil {
+		return nil
+	}
+	return normalized
+}
+
 func RegisterCallbacks() {
 	js.Global().Set("hashUrl", js.FuncOf(hashUrl))
+	js.Global().Set("sanitizeUrl", js.FuncOf(sanitizeUrl))
+	registerUrlNormalizerConfig()
+	js.Global().Set("normalizeUrlWithConfig", js.FuncOf(normalizeUrlWithConfig))
 }
 
 func main() {
@@ -0,0 +1,42 @@
+//go:build js && wasm
+
+package main
+
+import (
+	"syscall/js"
+	"testing"
+)
+
+func TestUrlNormalizerConfig(t *testing.T) {
+	RegisterCallbacks()
+	normalize := func(url string, config js.Value) js.Value {
+		return js.Global().Call("normalizeUrlWithConfig", url, config)
+	}
+	const input = "HTTPS://Example.com/a/./b?z=1&a=2#top"
+
+	config := js.Global().Get("UrlNormalizerConfig").New()
+	if got := normalize(input, config); got.String() != "https://example.com/a/b?a=2&z=1#top" {
+		t.Errorf("❌ Expected default normalization, got %v", got)
+	}
+
+	// Setters return the config for chaining
+	chained := config.Call("setSortQuery", false).Call("setRemoveFragment", true)
+	if !chained.Equal(config) {
+		t.Error("❌ Expected setters to return the config")
+	}
+	if got := normalize(input, config); got.String() != "https://example.com/a/b?z=1&a=2" {
+		t.Errorf("❌ Expected unsorted query without fragment, got %v", got)
+	}
+
+	// Configs are independent
+	if got := normalize(input, js.Global().Get("UrlNormalizerConfig").New().Call("setLowercaseHost", false)); got.String() != "https://Example.com/a/b?a=2&z=1#top" {
+		t.Errorf("❌ Expected host case preserved, got %v", got)
+	}
+
+	if got := normalize("://invalid", config); !got.IsNull() {
+		t.Errorf("❌ Expected null for invalid URL, got %v", got)
+	}
+	if got := js.Global().Call("normalizeUrlWithConfig", input, js.Null()); !got.IsNull() {
+		t.Errorf("❌ Expected null without config, got %v", got)
+	}
+}
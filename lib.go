@@ -4,6 +4,7 @@ import (
 	"crypto/sha256"
 	_ "embed"
 	"encoding/hex"
+	"errors"
 	"fmt"
 	"io"
 	"net/url"
@@ -11,6 +12,7 @@ import (
 	"regexp"
 	"strings"
 	"text/template"
+	"time"
 
 	"github.com/PuerkitoBio/purell"
 	"gopkg.in/yaml.v2"
@@ -96,9 +98,173 @@ func LoadRules(data []byte) error {
 	return nil
 }
 
+// URLParseError is returned when an input URL cannot be parsed.
+type URLParseError struct {
+	Input string // The offending input
+	Err   error  // Underlying reason, e.g. url.EscapeError or url.InvalidHostError
+}
+
+func (e *URLParseError) Error() string {
+	return fmt.Sprintf("failed to parse URL '%s': %v", e.Input, e.Err)
+}
+
+func (e *URLParseError) Unwrap() error {
+	return e.Err
+}
+
+// Wrap error with the offending input, unwrapping url.Error to keep the reason
+func newURLParseError(input string, err error) error {
+	var urlErr *url.Error
+	if errors.As(err, &urlErr) {
+		err = urlErr.Err
+	}
+	return &URLParseError{Input: input, Err: err}
+}
+
+// Parse URL, returning URLParseError on failure
+func parseURL(rawURL string) (*url.URL, error) {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return nil, newURLParseError(rawURL, err)
+	}
+	return u, nil
+}
+
+// Normalization flags used for signature generation
+const defaultNormalizationFlags = purell.FlagsSafe | purell.FlagRemoveDotSegments | purell.FlagSortQuery
+
 // Normalize URL using purell
 func normalizeURL(rawURL string) (string, error) {
-	return purell.NormalizeURLString(rawURL, purell.FlagsSafe|purell.FlagRemoveDotSegments|purell.FlagSortQuery)
+	return normalizeURLWithFlags(rawURL, defaultNormalizationFlags)
+}
+
+// Remove steps lowercasing scheme and host from normalization flags
+func preserveCaseFlags(flags purell.NormalizationFlags) purell.NormalizationFlags {
+	return flags &^ (purell.FlagLowercaseScheme | purell.FlagLowercaseHost)
+}
+
+// Normalize URL with default flags, but keeping the case of scheme and host,
+// for systems with case-sensitive URLs. Query sorting, dot segment removal
+// and the other steps are applied as usual.
+func normalizeURLPreserveCase(rawURL string) (string, error) {
+	normalized, err := normalizeURLWithFlags(rawURL, preserveCaseFlags(defaultNormalizationFlags))
+	if err != nil {
+		return "", err
+	}
+	// URL parser lowercases the scheme regardless of flags, restore it
+	if i := strings.IndexByte(rawURL, ':'); i > 0 && len(normalized) > i && strings.EqualFold(rawURL[:i], normalized[:i]) {
+		normalized = rawURL[:i] + normalized[i:]
+	}
+	return normalized, nil
+}
+
+// NormalizeHook is called after each normalization with its input, flags,
+// result and duration. Hooks are registered in init functions of optional
+// instrumentation, such as the metrics and tracing build tags.
+type NormalizeHook func(rawURL string, flags purell.NormalizationFlags, normalized string, elapsed time.Duration, err error)
+
+// SignatureHook is called after each signature generation with the name of
+// the hash algorithm, like NormalizeHook.
+type SignatureHook func(input, algorithm, signature string, elapsed time.Duration)
+
+var (
+	normalizeHooks []NormalizeHook
+	signatureHooks []SignatureHook
+)
+
+// Normalize URL using purell with custom normalization flags
+func normalizeURLWithFlags(rawURL string, flags purell.NormalizationFlags) (string, error) {
+	if len(normalizeHooks) == 0 {
+		return normalizeURLString(rawURL, flags)
+	}
+	start := time.Now()
+	normalized, err := normalizeURLString(rawURL, flags)
+	elapsed := time.Since(start)
+	for _, hook := range normalizeHooks {
+		hook(rawURL, flags, normalized, elapsed, err)
+	}
+	return normalized, err
+}
+
+func normalizeURLString(rawURL string, flags purell.NormalizationFlags) (string, error) {
+	// Data URIs carry content rather than point to it, so they are kept as-is
+	if urlIsDataURI(rawURL) {
+		return rawURL, nil
+	}
+	normalized, err := purell.NormalizeURLString(rawURL, flags)
+	if err != nil {
+		return "", newURLParseError(rawURL, err)
+	}
+	return normalized, nil
+}
+
+// Normalize URL with default flags, or return def as-is if it fails.
+//
+// Convenient in data pipelines where a known-good fallback is acceptable,
+// but errors are silently dropped. Use normalizeURL to handle them.
+func normalizeURLOrDefault(rawURL, def string) string {
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return def
+	}
+	return normalized
+}
+
+// Get SHA-256 signature of URL normalized with default flags, or return def
+// as-is if normalization fails. Errors are silently dropped, as with
+// normalizeURLOrDefault.
+func hashURLOrDefault(rawURL, def string) string {
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return def
+	}
+	return generateSignature(normalized)
+}
+
+// Normalize URL and write it into w, for streaming output into files or
+// network buffers. Nothing is written if normalization fails.
+func normalizeURLToWriter(w io.Writer, rawURL string, flags purell.NormalizationFlags) error {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return err
+	}
+	_, err = io.WriteString(w, normalized)
+	return err
+}
+
+// NormalizedURL is a URL in normalized form.
+//
+// It is a plain string, so it converts to string and compares with string
+// literals directly. It can be used as a map key as-is: URLs with the same
+// canonical form are equal keys, no matter the input they were created from.
+type NormalizedURL string
+
+// Normalize URL with default normalization flags
+func NewNormalizedURL(rawURL string) (NormalizedURL, error) {
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	return NormalizedURL(normalized), nil
+}
+
+func (n NormalizedURL) String() string {
+	return string(n)
+}
+
+// Normalize URL, taking at least minDuration regardless of the input.
+//
+// Padding hides the normalization time from observers that could infer URL
+// structure from response latency (e.g. number of query parameters). This is a
+// defense-in-depth measure only, and does not provide cryptographic constant
+// time guarantees: the sleep granularity and the result still leak timing.
+func normalizeURLPadded(rawURL string, flags purell.NormalizationFlags, minDuration time.Duration) (string, error) {
+	start := time.Now()
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if remaining := minDuration - time.Since(start); remaining > 0 {
+		time.Sleep(remaining)
+	}
+	return normalized, err
 }
 
 // Extract fields using regex and query parameters
@@ -160,7 +326,7 @@ func processURL(inputURL string) (string, error) {
 		return "", err
 	}
 
-	parsed, err := url.Parse(normalizedURL)
+	parsed, err := parseURL(normalizedURL)
 
 	if err != nil {
 		return "", err
@@ -189,8 +355,23 @@ func processURL(inputURL string) (string, error) {
 
 // Generate SHA-256 hash of the given string
 func generateSignature(input string) string {
+	if len(signatureHooks) == 0 {
+		hash := sha256.Sum256([]byte(input))
+		return hex.EncodeToString(hash[:])
+	}
+	start := time.Now()
 	hash := sha256.Sum256([]byte(input))
-	return hex.EncodeToString(hash[:])
+	signature := hex.EncodeToString(hash[:])
+	callSignatureHooks(input, defaultHashAlgorithm, signature, start)
+	return signature
+}
+
+// Call signature hooks with signature of input generated since start
+func callSignatureHooks(input, algorithm, signature string, start time.Time) {
+	elapsed := time.Since(start)
+	for _, hook := range signatureHooks {
+		hook(input, algorithm, signature, elapsed)
+	}
 }
 
 // Get signature for a given URL.
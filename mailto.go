@@ -0,0 +1,112 @@
+package main
+
+import (
+	"errors"
+	"net/url"
+	"slices"
+	"strings"
+)
+
+var errNotMailto = errors.New("not a mailto URL")
+
+// MailtoComponents are the recipients and headers of a mailto: URL.
+type MailtoComponents struct {
+	To      []string
+	Cc      []string
+	Bcc     []string
+	Subject string // Empty if not given
+	Body    string // Empty if not given
+}
+
+// Check if URL uses mailto: scheme, without parsing it
+func urlIsMailto(rawURL string) bool {
+	return len(rawURL) >= len("mailto:") && strings.EqualFold(rawURL[:len("mailto:")], "mailto:")
+}
+
+// Parse recipients and headers of mailto: URL, as in RFC 6068:
+//
+//	parseMailtoURL("mailto:user@example.com?subject=Hello&cc=other@example.com")
+//
+// To holds the address list and addresses of "to" headers, and Cc and Bcc the
+// addresses of "cc" and "bcc" headers, each split on commas. Subject and Body
+// come from the last "subject" and "body" headers. Header names are matched
+// case-insensitively, "+" is kept as a literal plus, unlike in form encoded
+// queries, and other headers are ignored.
+func parseMailtoURL(rawURL string) (MailtoComponents, error) {
+	var mailto MailtoComponents
+	if !urlIsMailto(rawURL) {
+		return mailto, newURLParseError(rawURL, errNotMailto)
+	}
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return mailto, err
+	}
+
+	addresses := func(list string) ([]string, error) {
+		decoded, err := url.PathUnescape(list)
+		if err != nil {
+			return nil, newURLParseError(rawURL, err)
+		}
+		var result []string
+		for _, address := range strings.Split(decoded, ",") {
+			if address = strings.TrimSpace(address); address != "" {
+				result = append(result, address)
+			}
+		}
+		return result, nil
+	}
+
+	if mailto.To, err = addresses(u.Opaque); err != nil {
+		return mailto, err
+	}
+	for _, header := range strings.Split(u.RawQuery, "&") {
+		name, value, _ := strings.Cut(header, "=")
+		switch strings.ToLower(name) {
+		case "to", "cc", "bcc":
+			list, err := addresses(value)
+			if err != nil {
+				return mailto, err
+			}
+			switch strings.ToLower(name) {
+			case "to":
+				mailto.To = append(mailto.To, list...)
+			case "cc":
+				mailto.Cc = append(mailto.Cc, list...)
+			default:
+				mailto.Bcc = append(mailto.Bcc, list...)
+			}
+		case "subject", "body":
+			decoded, err := url.PathUnescape(value)
+			if err != nil {
+				return mailto, newURLParseError(rawURL, err)
+			}
+			if strings.EqualFold(name, "subject") {
+				mailto.Subject = decoded
+			} else {
+				mailto.Body = decoded
+			}
+		}
+	}
+	return mailto, nil
+}
+
+// Get all recipient addresses of mailto: URL, from the address list and the
+// "to", "cc" and "bcc" headers, lowercased and without duplicates:
+//
+//	urlExtractEmails("mailto:to@example.com,CC@example.com?subject=Hi&bcc=other@example.com")
+//	// ["to@example.com", "cc@example.com", "other@example.com"]
+//
+// Returns nil for other URLs, and mailto: URLs that can't be parsed.
+func urlExtractEmails(rawURL string) []string {
+	mailto, err := parseMailtoURL(rawURL)
+	if err != nil {
+		return nil
+	}
+	var emails []string
+	for _, address := range slices.Concat(mailto.To, mailto.Cc, mailto.Bcc) {
+		if address = strings.ToLower(address); !slices.Contains(emails, address) {
+			emails = append(emails, address)
+		}
+	}
+	return emails
+}
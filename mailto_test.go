@@ -0,0 +1,82 @@
+package main
+
+import (
+	"errors"
+	"reflect"
+	"slices"
+	"testing"
+)
+
+func TestParseMailtoURL(t *testing.T) {
+	tests := []struct {
+		name     string
+		url      string
+		expected MailtoComponents
+	}{
+		{"single recipient", "mailto:user@example.com",
+			MailtoComponents{To: []string{"user@example.com"}}},
+		{"multiple recipients", "mailto:a@example.com,b@example.com?to=c@example.com",
+			MailtoComponents{To: []string{"a@example.com", "b@example.com", "c@example.com"}}},
+		{"headers", "mailto:user@example.com?subject=Hello&cc=other@example.com",
+			MailtoComponents{To: []string{"user@example.com"}, Cc: []string{"other@example.com"}, Subject: "Hello"}},
+		{"encoded subject and body", "MAILTO:user@example.com?Subject=Hyv%C3%A4%C3%A4%20p%C3%A4iv%C3%A4%C3%A4&body=1+1%3D2%0Abye",
+			MailtoComponents{To: []string{"user@example.com"}, Subject: "Hyvää päivää", Body: "1+1=2\nbye"}},
+		{"encoded recipients", "mailto:first%20last%40example.com,%20b@example.com?cc=c@example.com%2Cd@example.com",
+			MailtoComponents{To: []string{"first last@example.com", "b@example.com"}, Cc: []string{"c@example.com", "d@example.com"}}},
+		{"headers only", "mailto:?to=user@example.com&in-reply-to=%3Cid@example.com%3E",
+			MailtoComponents{To: []string{"user@example.com"}}},
+		{"blind copies", "mailto:user@example.com?BCC=a@example.com,b@example.com&cc=c@example.com",
+			MailtoComponents{To: []string{"user@example.com"}, Cc: []string{"c@example.com"}, Bcc: []string{"a@example.com", "b@example.com"}}},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			if got, err := parseMailtoURL(test.url); err != nil || !reflect.DeepEqual(got, test.expected) {
+				t.Errorf("❌ Expected %+v, got %+v (error: %v)", test.expected, got, err)
+			}
+		})
+	}
+
+	for _, input := range []string{"https://example.com/", "mail:user@example.com", ""} {
+		if _, err := parseMailtoURL(input); !errors.Is(err, errNotMailto) {
+			t.Errorf("❌ Expected errNotMailto for %q, got: %v", input, err)
+		}
+	}
+	if _, err := parseMailtoURL("mailto:user@example.com?subject=%zz"); err == nil {
+		t.Error("❌ Expected error for invalid escape")
+	}
+}
+
+func TestURLExtractEmails(t *testing.T) {
+	tests := map[string][]string{
+		"mailto:to@example.com,CC@Example.com?subject=Hi":                        {"to@example.com", "cc@example.com"},
+		"mailto:user@example.com?cc=copy@example.com&bcc=blind@example.com":      {"user@example.com", "copy@example.com", "blind@example.com"},
+		"mailto:first.last%40example.com?cc=a@example.com%2C%20USER@example.com": {"first.last@example.com", "a@example.com", "user@example.com"},
+		"mailto:user@example.com?to=User@Example.com&cc=user@example.com":        {"user@example.com"},
+		"mailto:user@example.com?subject=%zz":                                    nil,
+		"mailto:?subject=Hi":                                                     nil,
+		"https://example.com/?to=user@example.com":                               nil,
+	}
+
+	for input, expected := range tests {
+		if got := urlExtractEmails(input); !slices.Equal(got, expected) {
+			t.Errorf("❌ urlExtractEmails(%q): expected %q, got %q", input, expected, got)
+		}
+	}
+}
+
+func TestURLIsMailto(t *testing.T) {
+	tests := map[string]bool{
+		"mailto:user@example.com": true,
+		"MailTo:user@example.com": true,
+		"mailto:":                 true,
+		"https://example.com/":    false,
+		"mailto":                  false,
+		"user@example.com":        false,
+	}
+	for input, expected := range tests {
+		if got := urlIsMailto(input); got != expected {
+			t.Errorf("❌ Expected %v for %q, got %v", expected, input, got)
+		}
+	}
+}
@@ -1,8 +1,13 @@
 package main
 
 import (
+	"bytes"
+	"errors"
 	"fmt"
+	"net/url"
+	"strings"
 	"testing"
+	"time"
 )
 
 func TestExtractionRules(t *testing.T) {
@@ -45,3 +50,172 @@ func TestExtractionRules(t *testing.T) {
 		}
 	}
 }
+
+// Reasons of parse errors are tested in testdata/fixtures/normalize.yaml, see
+// TestNormalizationFixtures.
+func TestURLParseError(t *testing.T) {
+	const input = "http://example.com:port/"
+	_, err := normalizeURL(input)
+
+	var parseErr *URLParseError
+	if !errors.As(err, &parseErr) {
+		t.Fatalf("❌ Expected URLParseError, got: %v", err)
+	}
+	if parseErr.Input != input {
+		t.Errorf("❌ Expected input %q, got %q", input, parseErr.Input)
+	}
+	if !strings.Contains(err.Error(), fmt.Sprintf("failed to parse URL '%s'", input)) {
+		t.Errorf("❌ Error message does not contain input: %v", err)
+	}
+	var urlErr *url.Error
+	if errors.As(err, &urlErr) {
+		t.Errorf("❌ Underlying url.Error should be unwrapped: %v", urlErr)
+	}
+
+	var escapeErr url.EscapeError
+	if _, err := normalizeURL("http://example.com/%zz"); !errors.As(err, &escapeErr) {
+		t.Errorf("❌ Expected url.EscapeError, got: %v", err)
+	}
+	var hostErr url.InvalidHostError
+	if _, err := normalizeURL("http://exa mple.com/"); !errors.As(err, &hostErr) {
+		t.Errorf("❌ Expected url.InvalidHostError, got: %v", err)
+	}
+}
+
+func TestNormalizeURLPadded(t *testing.T) {
+	const minDuration = 20 * time.Millisecond
+
+	for _, rawURL := range []string{"https://example.com/?b=2&a=1", "://invalid"} {
+		start := time.Now()
+		_, err := normalizeURLPadded(rawURL, defaultNormalizationFlags, minDuration)
+		if elapsed := time.Since(start); elapsed < minDuration {
+			t.Errorf("❌ Normalizing %s took %v, expected at least %v (error: %v)", rawURL, elapsed, minDuration, err)
+		}
+	}
+
+	normalized, err := normalizeURLPadded("https://example.com/?b=2&a=1", defaultNormalizationFlags, minDuration)
+	if err != nil || normalized != "https://example.com/?a=1&b=2" {
+		t.Errorf("❌ Unexpected result: %s (error: %v)", normalized, err)
+	}
+}
+
+func TestNormalizedURL(t *testing.T) {
+	n, err := NewNormalizedURL("HTTPS://Example.com:443/a/../b?z=1&y=2")
+	if err != nil {
+		t.Fatalf("❌ Failed to normalize: %v", err)
+	}
+	if n != "https://example.com/b?y=2&z=1" {
+		t.Errorf("❌ Unexpected normalized URL: %s", n)
+	}
+	s := n.String()
+	if s != string(n) || fmt.Sprint(n) != s {
+		t.Errorf("❌ String conversion mismatch: %s", s)
+	}
+
+	if _, err := NewNormalizedURL("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestNormalizedURLMapKey(t *testing.T) {
+	visits := make(map[NormalizedURL]int)
+	for _, rawURL := range []string{
+		"https://example.com/a?x=1&y=2",
+		"HTTPS://EXAMPLE.com:443/a?y=2&x=1",
+		"https://example.com/b/../a?x=1&y=2",
+		"https://example.com/other",
+	} {
+		n, err := NewNormalizedURL(rawURL)
+		if err != nil {
+			t.Fatalf("❌ Failed to normalize %s: %v", rawURL, err)
+		}
+		visits[n]++
+	}
+
+	if len(visits) != 2 || visits["https://example.com/a?x=1&y=2"] != 3 {
+		t.Errorf("❌ Unexpected map contents: %v", visits)
+	}
+}
+
+func TestNormalizeURLToWriter(t *testing.T) {
+	const rawURL = "HTTPS://Example.com:443/a/../b?z=1&y=2"
+	expected, _ := normalizeURL(rawURL)
+
+	var buf bytes.Buffer
+	buf.WriteString("url: ")
+	if err := normalizeURLToWriter(&buf, rawURL, defaultNormalizationFlags); err != nil {
+		t.Fatalf("❌ Failed to normalize: %v", err)
+	}
+	if buf.String() != "url: "+expected {
+		t.Errorf("❌ Expected %q, got %q", "url: "+expected, buf.String())
+	}
+
+	buf.Reset()
+	var parseErr *URLParseError
+	if err := normalizeURLToWriter(&buf, "://invalid", defaultNormalizationFlags); !errors.As(err, &parseErr) {
+		t.Errorf("❌ Expected URLParseError, got: %v", err)
+	}
+	if buf.Len() != 0 {
+		t.Errorf("❌ Expected nothing written on error, got %q", buf.String())
+	}
+}
+
+const benchmarkURL = "HTTPS://www.Example.com:443/kotimaa/a/../b/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80?utm_source=x&b=2&a=1"
+
+func BenchmarkNormalizeURLString(b *testing.B) {
+	var buf bytes.Buffer
+	for b.Loop() {
+		buf.Reset()
+		normalized, _ := normalizeURLWithFlags(benchmarkURL, defaultNormalizationFlags)
+		fmt.Fprint(&buf, normalized)
+	}
+}
+
+func BenchmarkNormalizeURLToWriter(b *testing.B) {
+	var buf bytes.Buffer
+	for b.Loop() {
+		buf.Reset()
+		normalizeURLToWriter(&buf, benchmarkURL, defaultNormalizationFlags)
+	}
+}
+
+func TestNormalizeURLOrDefault(t *testing.T) {
+	const rawURL = "HTTPS://Example.com:443/a/../b?z=1&y=2"
+	normalized, _ := normalizeURL(rawURL)
+	if got := normalizeURLOrDefault(rawURL, "fallback"); got != normalized {
+		t.Errorf("❌ Expected %q, got %q", normalized, got)
+	}
+	if got := hashURLOrDefault(rawURL, "fallback"); got != generateSignature(normalized) {
+		t.Errorf("❌ Expected signature of %q, got %q", normalized, got)
+	}
+
+	// Default is returned unchanged, even if it isn't normalized or valid
+	for _, def := range []string{"HTTPS://Example.com:443/", "://also invalid", ""} {
+		if got := normalizeURLOrDefault("://invalid", def); got != def {
+			t.Errorf("❌ normalizeURLOrDefault: expected default %q, got %q", def, got)
+		}
+		if got := hashURLOrDefault("://invalid", def); got != def {
+			t.Errorf("❌ hashURLOrDefault: expected default %q, got %q", def, got)
+		}
+	}
+}
+
+func TestNormalizeURLPreserveCase(t *testing.T) {
+	tests := map[string]string{
+		"HTTPS://EXAMPLE.COM/FOO":              "HTTPS://EXAMPLE.COM/FOO",
+		"HTTPS://EXAMPLE.COM/A/../FOO?Z=1&Y=2": "HTTPS://EXAMPLE.COM/FOO?Y=2&Z=1",
+		"https://GitHub.com/Org/Repo/./Blob":   "https://GitHub.com/Org/Repo/Blob",
+		"Https://Example.com:443/Path":         "Https://Example.com/Path",
+		"https://example.com/%7eUser":          "https://example.com/~User",
+	}
+
+	for input, expected := range tests {
+		if normalized, err := normalizeURLPreserveCase(input); err != nil || normalized != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, normalized, err)
+		}
+	}
+
+	if _, err := normalizeURLPreserveCase("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
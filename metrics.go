@@ -0,0 +1,46 @@
+//go:build metrics
+// +build metrics
+
+package main
+
+import (
+	"errors"
+	"expvar"
+	"net/url"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Normalization metrics published with expvar, served from /debug/vars by
+// services importing net/http with the default mux.
+var (
+	normalizeCalls      = expvar.NewInt("suola.normalize.calls")
+	normalizeErrors     = expvar.NewMap("suola.normalize.errors")
+	normalizeDurationNs = expvar.NewInt("suola.normalize.duration_ns")
+)
+
+func init() {
+	normalizeHooks = append(normalizeHooks, recordNormalizeMetrics)
+}
+
+// Get error type label for metrics
+func normalizeErrorType(err error) string {
+	var hostErr url.InvalidHostError
+	var escapeErr url.EscapeError
+	switch {
+	case errors.As(err, &hostErr):
+		return "invalid_host"
+	case errors.As(err, &escapeErr):
+		return "invalid_escape"
+	}
+	return "parse"
+}
+
+func recordNormalizeMetrics(_ string, _ purell.NormalizationFlags, _ string, elapsed time.Duration, err error) {
+	normalizeCalls.Add(1)
+	normalizeDurationNs.Add(elapsed.Nanoseconds())
+	if err != nil {
+		normalizeErrors.Add(normalizeErrorType(err), 1)
+	}
+}
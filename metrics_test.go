@@ -0,0 +1,42 @@
+//go:build metrics
+// +build metrics
+
+package main
+
+import (
+	"expvar"
+	"testing"
+)
+
+// Get value of error counter, 0 if not set yet
+func normalizeErrorCount(errorType string) int64 {
+	if v, ok := normalizeErrors.Get(errorType).(*expvar.Int); ok {
+		return v.Value()
+	}
+	return 0
+}
+
+func TestNormalizeMetrics(t *testing.T) {
+	calls, duration := normalizeCalls.Value(), normalizeDurationNs.Value()
+	hostErrors, escapeErrors := normalizeErrorCount("invalid_host"), normalizeErrorCount("invalid_escape")
+
+	normalizeURL("https://example.com/?b=2&a=1")
+	normalizeURL("http://exa mple.com/")
+	normalizeURL("https://example.com/%zz")
+
+	if got := normalizeCalls.Value() - calls; got != 3 {
+		t.Errorf("❌ Expected 3 calls, got %d", got)
+	}
+	if normalizeDurationNs.Value() <= duration {
+		t.Error("❌ Expected duration to increase")
+	}
+	if got := normalizeErrorCount("invalid_host") - hostErrors; got != 1 {
+		t.Errorf("❌ Expected 1 invalid host error, got %d", got)
+	}
+	if got := normalizeErrorCount("invalid_escape") - escapeErrors; got != 1 {
+		t.Errorf("❌ Expected 1 invalid escape error, got %d", got)
+	}
+	if published := expvar.Get("suola.normalize.calls"); published == nil || published.String() != normalizeCalls.String() {
+		t.Errorf("❌ Calls counter isn't published: %v", published)
+	}
+}
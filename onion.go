@@ -0,0 +1,65 @@
+package main
+
+import (
+	"encoding/base32"
+	"errors"
+	"strings"
+)
+
+var errInvalidOnionAddress = errors.New("invalid .onion address")
+
+// Lengths of Tor hidden service addresses in base32 characters
+const (
+	onionV2AddressLength = 16 // 80-bit hash of the service key, deprecated
+	onionV3AddressLength = 56 // Public key, checksum and version byte
+)
+
+// Check if URL points to a Tor hidden service, i.e. its host is under the
+// .onion special-use domain. Works without network access, as the host is
+// not resolved.
+func urlIsTorHiddenService(rawURL string) (bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	return isOnionHost(u.Hostname()), nil
+}
+
+// Check if host is under the .onion domain
+func isOnionHost(host string) bool {
+	host = strings.ToLower(strings.TrimSuffix(host, "."))
+	return strings.HasSuffix(host, ".onion")
+}
+
+// Check syntax of a lowercase .onion hostname: the label before ".onion" must
+// be a v2 or v3 address in base32, and v3 addresses must have version 3.
+// Subdomains are allowed. The checksum of v3 addresses isn't verified, as it
+// requires SHA-3.
+func isValidOnionHost(host string) bool {
+	name := strings.TrimSuffix(strings.TrimSuffix(host, "."), ".onion")
+	address := name[strings.LastIndexByte(name, '.')+1:]
+	if len(address) != onionV2AddressLength && len(address) != onionV3AddressLength {
+		return false
+	}
+	decoded, err := base32.StdEncoding.DecodeString(strings.ToUpper(address))
+	if err != nil {
+		return false
+	}
+	return len(address) == onionV2AddressLength || decoded[len(decoded)-1] == 3
+}
+
+// Normalize URL with default flags, checking .onion hosts of Tor hidden
+// services. Base32 is case-insensitive, so the host is lowercased as usual,
+// and malformed .onion hostnames fail with errInvalidOnionAddress. Other URLs
+// are normalized as-is.
+func normalizeOnionURL(rawURL string) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+	if isOnionHost(host) && !isValidOnionHost(host) {
+		return "", newURLParseError(rawURL, errInvalidOnionAddress)
+	}
+	return u.String(), nil
+}
@@ -0,0 +1,60 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLIsTorHiddenService(t *testing.T) {
+	tests := map[string]bool{
+		"http://duckduckgogg42xjoc72x3sjasowoarfbgcmvfimaftt6twagswzczad.onion/": true,
+		"http://expyuzz4wqqyqhjn.onion/":                                         true,
+		"http://www.expyuzz4wqqyqhjn.ONION./about":                               true,
+		"http://invalid.onion/":                                                  true,
+		"https://example.com/":                                                   false,
+		"https://onion.example/":                                                 false,
+		"https://example.com/page.onion":                                         false,
+	}
+
+	for input, expected := range tests {
+		if got, err := urlIsTorHiddenService(input); err != nil || got != expected {
+			t.Errorf("❌ urlIsTorHiddenService(%q): expected %v, got %v (error: %v)", input, expected, got, err)
+		}
+	}
+
+	if _, err := urlIsTorHiddenService("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestNormalizeOnionURL(t *testing.T) {
+	tests := map[string]string{
+		"http://DuckDuckGoGG42XJOC72X3SJASOWOARFBGCMVFIMAFTT6TWAGSWZCZAD.onion/": "http://duckduckgogg42xjoc72x3sjasowoarfbgcmvfimaftt6twagswzczad.onion/",
+		"http://aaaqeayeaudaocajbifqydiob4ibceqtcqkrmfyydenbwha5dyp3kead.onion/": "http://aaaqeayeaudaocajbifqydiob4ibceqtcqkrmfyydenbwha5dyp3kead.onion/",
+		"http://EXPYUZZ4WQQYQHJN.onion:80/a/../b?y=2&x=1":                        "http://expyuzz4wqqyqhjn.onion/b?x=1&y=2",
+		"http://www.expyuzz4wqqyqhjn.onion/":                                     "http://www.expyuzz4wqqyqhjn.onion/",
+		"HTTPS://Example.com/":                                                   "https://example.com/",
+	}
+
+	for input, expected := range tests {
+		if got, err := normalizeOnionURL(input); err != nil || got != expected {
+			t.Errorf("❌ normalizeOnionURL(%q): expected %q, got %q (error: %v)", input, expected, got, err)
+		}
+	}
+
+	for _, input := range []string{
+		"http://expyuzz4wqqyqhj.onion/",
+		"http://duckduckgogg42xjoc72x3sjasowoarfbgcmvfimaftt6twagswzczadx.onion/",
+		"http://aaaqeayeaudaocajbifqydiob4ibceqtcqkrmfyydenbwha5dyp3keae.onion/",
+		"http://expyuzz4wqqyqhj1.onion/",
+		"http://.onion/",
+	} {
+		if _, err := normalizeOnionURL(input); !errors.Is(err, errInvalidOnionAddress) {
+			t.Errorf("❌ Expected errInvalidOnionAddress for %q, got: %v", input, err)
+		}
+	}
+
+	if _, err := normalizeOnionURL("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
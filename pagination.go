@@ -0,0 +1,47 @@
+package main
+
+import (
+	"net/url"
+	"slices"
+	"strings"
+)
+
+// PaginationOptions selects query parameters urlNormalizePagination removes.
+type PaginationOptions struct {
+	PageParamNames  []string // Keys of pagination parameters, e.g. "page"
+	FirstPageValues []string // Values pointing to the first page, e.g. "1"
+}
+
+// Pagination parameters of common sites, both one and zero based
+var defaultPaginationOptions = PaginationOptions{
+	PageParamNames:  []string{"page", "p", "offset"},
+	FirstPageValues: []string{"1", "0"},
+}
+
+// Remove pagination parameters pointing to the first page from URL, as a page
+// without them shows the same content:
+//
+//	urlNormalizePagination("https://example.com/?page=1&q=foo", defaultPaginationOptions) // "https://example.com/?q=foo"
+//	urlNormalizePagination("https://example.com/?page=2&q=foo", defaultPaginationOptions) // unchanged
+//
+// Other parameters keep their order and encoding.
+func urlNormalizePagination(rawURL string, opts PaginationOptions) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	if u.RawQuery == "" {
+		return u.String(), nil
+	}
+
+	params := strings.Split(u.RawQuery, "&")
+	params = slices.DeleteFunc(params, func(param string) bool {
+		key, value, _ := strings.Cut(param, "=")
+		key, keyErr := url.QueryUnescape(key)
+		value, valueErr := url.QueryUnescape(value)
+		return keyErr == nil && valueErr == nil &&
+			slices.Contains(opts.PageParamNames, key) && slices.Contains(opts.FirstPageValues, value)
+	})
+	u.RawQuery = strings.Join(params, "&")
+	return u.String(), nil
+}
@@ -0,0 +1,34 @@
+package main
+
+import "testing"
+
+func TestURLNormalizePagination(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/?page=1&q=foo":        "https://example.com/?q=foo",
+		"https://example.com/?page=2&q=foo":        "https://example.com/?page=2&q=foo",
+		"https://example.com/?q=foo&p=1&offset=20": "https://example.com/?q=foo&offset=20",
+		"https://example.com/?offset=0&page=3":     "https://example.com/?page=3",
+		"https://example.com/list?page=1":          "https://example.com/list",
+		"https://example.com/?q=a%20b&page=1#t":    "https://example.com/?q=a%20b#t",
+		"https://example.com/?pages=1&q=foo":       "https://example.com/?pages=1&q=foo",
+		"https://example.com/?page=01":             "https://example.com/?page=01",
+		"https://example.com/":                     "https://example.com/",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlNormalizePagination(input, defaultPaginationOptions); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Custom parameter names and first page
+	opts := PaginationOptions{PageParamNames: []string{"sivu"}, FirstPageValues: []string{"1"}}
+	got, err := urlNormalizePagination("https://example.com/?sivu=1&page=1", opts)
+	if expected := "https://example.com/?page=1"; err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	if _, err := urlNormalizePagination("://invalid", defaultPaginationOptions); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
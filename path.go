@@ -0,0 +1,513 @@
+package main
+
+import (
+	"errors"
+	"net/url"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+// Remove "." and ".." segments from path, as in RFC 3986 section 5.2.4
+func removeDotSegments(path string) string {
+	var output []string
+	input := path
+	for input != "" {
+		switch {
+		case strings.HasPrefix(input, "../"):
+			input = input[3:]
+		case strings.HasPrefix(input, "./"):
+			input = input[2:]
+		case strings.HasPrefix(input, "/./"):
+			input = input[2:]
+		case input == "/.":
+			input = "/"
+		case strings.HasPrefix(input, "/../"):
+			input = input[3:]
+			if len(output) > 0 {
+				output = output[:len(output)-1]
+			}
+		case input == "/..":
+			input = "/"
+			if len(output) > 0 {
+				output = output[:len(output)-1]
+			}
+		case input == "." || input == "..":
+			input = ""
+		default:
+			// Move the first segment, with its leading slash if any, to output
+			end := len(input)
+			if i := strings.IndexByte(input[1:], '/'); i >= 0 {
+				end = i + 1
+			}
+			output = append(output, input[:end])
+			input = input[end:]
+		}
+	}
+	return strings.Join(output, "")
+}
+
+// Join relative path against base path, resolving dot segments.
+//
+// This is the path-only analog of resolving a relative URL reference, and
+// follows the merge and dot segment removal of RFC 3986 section 5.2.
+func urlPathJoin(basePath, relativePath string) string {
+	var merged string
+	switch {
+	case relativePath == "":
+		return basePath
+	case strings.HasPrefix(relativePath, "/"):
+		merged = relativePath
+	default:
+		merged = basePath[:strings.LastIndex(basePath, "/")+1] + relativePath
+	}
+	return removeDotSegments(merged)
+}
+
+var errInvalidPathSegment = errors.New("path segment must not be empty, \".\" or \"..\"")
+
+// Append path segments to the path of base URL, with exactly one "/" between
+// them, for building sub-URLs of APIs:
+//
+//	urlJoinPath("https://api.example.com/v1", "users", "42", "orders")
+//	// "https://api.example.com/v1/users/42/orders"
+//
+// Each segment is percent-encoded on its own, so "/" in a segment is encoded
+// too. Empty and dot segments fail with errInvalidPathSegment, as they would
+// change the meaning of the path. Query and fragment of base are kept.
+func urlJoinPath(base string, segments ...string) (string, error) {
+	u, err := parseURL(base)
+	if err != nil {
+		return "", err
+	}
+	escaped := strings.TrimSuffix(u.EscapedPath(), "/")
+	for _, segment := range segments {
+		if segment == "" || segment == "." || segment == ".." {
+			return "", newURLParseError(base, errInvalidPathSegment)
+		}
+		escaped += "/" + urlEncodePathSegment(segment)
+	}
+	// Unescaping can't fail, as the segments were just escaped
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+	return u.String(), nil
+}
+
+// Check if path of URL matches a glob pattern, where "*" matches one path
+// segment and "**" zero or more segments. Other segments must match exactly.
+//
+//	urlPathMatch("https://example.com/articles/foo", "/articles/*")      // true
+//	urlPathMatch("https://example.com/articles/foo/bar", "/articles/*")  // false
+//	urlPathMatch("https://example.com/articles/foo/bar", "/articles/**") // true
+func urlPathMatch(rawURL, pattern string) (bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	return matchPathSegments(splitPathSegments(pattern), splitPathSegments(u.Path)), nil
+}
+
+// Check if path of URL matches any of the glob patterns, see urlPathMatch
+func urlPatternMatchesAny(rawURL string, patterns []string) (bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	segments := splitPathSegments(u.Path)
+	return slices.ContainsFunc(patterns, func(pattern string) bool {
+		return matchPathSegments(splitPathSegments(pattern), segments)
+	}), nil
+}
+
+// Split path into segments without the leading slash, root path has none
+func splitPathSegments(path string) []string {
+	path = strings.TrimPrefix(path, "/")
+	if path == "" {
+		return nil
+	}
+	return strings.Split(path, "/")
+}
+
+func matchPathSegments(pattern, segments []string) bool {
+	if len(pattern) == 0 {
+		return len(segments) == 0
+	}
+	if pattern[0] == "**" {
+		// Match zero segments, or consume one and try again
+		return matchPathSegments(pattern[1:], segments) ||
+			len(segments) > 0 && matchPathSegments(pattern, segments[1:])
+	}
+	if len(segments) == 0 {
+		return false
+	}
+	if pattern[0] == "*" && segments[0] != "" || pattern[0] == segments[0] {
+		return matchPathSegments(pattern[1:], segments[1:])
+	}
+	return false
+}
+
+// Remove matrix parameters, e.g. ";jsessionid=1", from each path segment,
+// leaving the query as-is:
+//
+//	urlStripMatrixParameters("https://example.com/a;x=1/b;y=2?q=1") // "https://example.com/a/b?q=1"
+func urlStripMatrixParameters(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	removeMatrixParams(u)
+	return u.String(), nil
+}
+
+// Remove matrix parameters from path segments of URL in place. Escaped
+// semicolons, "%3B", are kept.
+func removeMatrixParams(u *url.URL) {
+	escaped := u.EscapedPath()
+	if !strings.Contains(escaped, ";") {
+		return
+	}
+	segments := strings.Split(escaped, "/")
+	for i, segment := range segments {
+		segments[i], _, _ = strings.Cut(segment, ";")
+	}
+	escaped = strings.Join(segments, "/")
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+}
+
+// Path segments of ASP.NET cookieless sessions, such as "/(S(abc123))", with
+// forms authentication tickets "F(...)" and anonymous IDs "A(...)"
+var defaultPathSessionPatterns = []*regexp.Regexp{
+	regexp.MustCompile(`/\((?:[ASF]\([^()/]*\))+\)`),
+}
+
+// Normalize URL and remove session IDs embedded in its path, which are
+// matches of patterns in the escaped path:
+//
+//	urlNormalizeStripSessionFromPath("https://example.com/shop/(S(abc123))/products") // "https://example.com/shop/products"
+//
+// Without patterns, defaultPathSessionPatterns is used. Patterns should
+// include the leading "/" of the segments they remove, e.g.
+// `/sessionid/[^/]+` for "/index.php/sessionid/abc123/page".
+func urlNormalizeStripSessionFromPath(rawURL string, patterns ...*regexp.Regexp) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	if len(patterns) == 0 {
+		patterns = defaultPathSessionPatterns
+	}
+
+	escaped := u.EscapedPath()
+	for _, pattern := range patterns {
+		escaped = pattern.ReplaceAllString(escaped, "")
+	}
+	if escaped == u.EscapedPath() {
+		return u.String(), nil
+	}
+	if escaped == "" {
+		escaped = "/"
+	}
+	if u.Path, err = url.PathUnescape(escaped); err != nil {
+		return "", newURLParseError(rawURL, err)
+	}
+	u.RawPath = escaped
+	return u.String(), nil
+}
+
+// Get index of the extension dot in path segment, or -1 if it has none.
+// Leading dot of hidden files, e.g. ".gitignore", doesn't start an extension.
+func extensionIndex(segment string) int {
+	i := strings.LastIndexByte(segment, '.')
+	if i <= 0 || i == len(segment)-1 {
+		return -1
+	}
+	return i
+}
+
+// Get extension of the last path segment of URL, without the dot, e.g. "gz"
+// for "https://example.com/file.tar.gz?v=2". Returns false if there is no
+// extension, or the URL can't be parsed.
+func urlExtractPathExtension(rawURL string) (string, bool) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", false
+	}
+	segment := u.Path[strings.LastIndexByte(u.Path, '/')+1:]
+	if i := extensionIndex(segment); i >= 0 {
+		return segment[i+1:], true
+	}
+	return "", false
+}
+
+// Remove extension of the last path segment of URL, e.g.
+// "https://example.com/file.tar" for "https://example.com/file.tar.gz?v=2".
+// Query and fragment are kept, and URLs without an extension are returned
+// as-is.
+func urlStripPathExtension(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	escaped := u.EscapedPath()
+	start := strings.LastIndexByte(escaped, '/') + 1
+	i := extensionIndex(escaped[start:])
+	if i < 0 {
+		return rawURL, nil
+	}
+	escaped = escaped[:start+i]
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+	return u.String(), nil
+}
+
+// Sort path segments of URL alphabetically, except for the first one, which
+// is usually a namespace:
+//
+//	urlSortPathSegments("https://example.com/api/users/groups/admins") // "https://example.com/api/admins/groups/users"
+//
+// Only for APIs where the order of segments doesn't matter, as it usually
+// does. A trailing slash is kept.
+func urlSortPathSegments(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	sortPathSegments(u)
+	return u.String(), nil
+}
+
+// Sort path segments of URL in place, see urlSortPathSegments
+func sortPathSegments(u *url.URL) {
+	escaped := u.EscapedPath()
+	trailingSlash := strings.HasSuffix(escaped, "/")
+	segments := splitPathSegments(strings.TrimSuffix(escaped, "/"))
+	if len(segments) < 3 {
+		return
+	}
+	slices.Sort(segments[1:])
+	escaped = "/" + strings.Join(segments, "/")
+	if trailingSlash {
+		escaped += "/"
+	}
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+}
+
+// Handling of the trailing slash of paths by urlNormalizeTrailingSlashPolicy
+type TrailingSlashPolicy int
+
+const (
+	TrailingSlashPreserve     TrailingSlashPolicy = iota // Keep path as-is
+	TrailingSlashAlwaysAdd                               // Add slash to all paths
+	TrailingSlashAlwaysRemove                            // Remove slash from all paths but the root
+	TrailingSlashSmart                                   // Remove slash from file-like paths, add to others
+)
+
+// Normalize URL and add or remove the trailing slash of its path by policy.
+//
+// With TrailingSlashSmart, paths are file-like if their last segment has a
+// dot, so "/dir/file.html" has no slash and "/api/users" gets one. Root path
+// always keeps its slash.
+func urlNormalizeTrailingSlashPolicy(rawURL string, policy TrailingSlashPolicy) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	applyTrailingSlashPolicy(u, policy)
+	return u.String(), nil
+}
+
+// Add or remove trailing slash of URL path in place, see
+// urlNormalizeTrailingSlashPolicy
+func applyTrailingSlashPolicy(u *url.URL, policy TrailingSlashPolicy) {
+	escaped := u.EscapedPath()
+	trimmed := strings.TrimSuffix(escaped, "/")
+	if policy == TrailingSlashSmart {
+		policy = TrailingSlashAlwaysAdd
+		if strings.Contains(trimmed[strings.LastIndexByte(trimmed, '/')+1:], ".") {
+			policy = TrailingSlashAlwaysRemove
+		}
+	}
+
+	switch policy {
+	case TrailingSlashAlwaysAdd:
+		escaped = trimmed + "/"
+	case TrailingSlashAlwaysRemove:
+		if trimmed != "" {
+			escaped = trimmed
+		}
+	}
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+}
+
+// Check if character may appear unescaped in a path, as pchar or "/" of
+// RFC 3986
+func isPathChar(c byte) bool {
+	return isUnreserved(c) || strings.IndexByte("!$&'()*+,;=:@/", c) >= 0
+}
+
+// Check if standalone path, without the rest of a URL, is valid: it starts
+// with "/", has only path characters and well-formed escapes, no empty
+// segments, as in "//", and no ".." segments above the root. Escaped dots,
+// e.g. "%2E%2E", count as dot segments too.
+func urlPathIsValid(path string) bool {
+	if !strings.HasPrefix(path, "/") || strings.Contains(path, "//") {
+		return false
+	}
+	for i := 0; i < len(path); i++ {
+		switch c := path[i]; {
+		case c == '%':
+			if i+2 >= len(path) {
+				return false
+			}
+			if _, ok := unhex(path[i+1]); !ok {
+				return false
+			}
+			if _, ok := unhex(path[i+2]); !ok {
+				return false
+			}
+			i += 2
+		case !isPathChar(c):
+			return false
+		}
+	}
+
+	depth := 0
+	for _, segment := range splitPathSegments(path) {
+		switch strings.ToUpper(segment) {
+		case ".", "%2E":
+		case "..", ".%2E", "%2E.", "%2E%2E":
+			if depth--; depth < 0 {
+				return false
+			}
+		default:
+			depth++
+		}
+	}
+	return true
+}
+
+// Normalize standalone path: escapes are uppercased, escaped unreserved
+// characters are decoded, characters that must be escaped are encoded, and
+// dot segments are removed. Fails with url.EscapeError on malformed escapes.
+//
+//	urlPathNormalize("/a/%7euser/./b/../c d") // "/a/~user/c%20d"
+func urlPathNormalize(path string) (string, error) {
+	encoded, err := reencodeComponent(path, isPathChar)
+	if err != nil {
+		return "", newURLParseError(path, err)
+	}
+	return removeDotSegments(encoded), nil
+}
+
+// Path segments considered variables by urlPathVariables
+var (
+	numericSegmentPattern = regexp.MustCompile(`^[0-9]+$`)
+	uuidSegmentPattern    = regexp.MustCompile(`^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$`)
+)
+
+// Get placeholder for path segment looking like an ID, "{id}" for numbers and
+// "{uuid}" for UUIDs, or an empty string for other segments
+func pathVariablePlaceholder(segment string) string {
+	switch {
+	case numericSegmentPattern.MatchString(segment):
+		return "{id}"
+	case uuidSegmentPattern.MatchString(segment):
+		return "{uuid}"
+	}
+	return ""
+}
+
+// Get path segments of URL looking like IDs, numbers or UUIDs, in order:
+//
+//	urlPathVariables("https://example.com/users/42/orders/7") // ["42", "7"]
+//
+// Returns nil if there are none, or the URL can't be parsed.
+func urlPathVariables(rawURL string) []string {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return nil
+	}
+	var variables []string
+	for _, segment := range splitPathSegments(u.Path) {
+		if pathVariablePlaceholder(segment) != "" {
+			variables = append(variables, segment)
+		}
+	}
+	return variables
+}
+
+// Get escaped path of URL with segments found by urlPathVariables replaced
+// with "{id}" or "{uuid}", for counting distinct endpoints:
+//
+//	urlPathWithoutVariables("https://example.com/users/42/orders/7?x=1") // "/users/{id}/orders/{id}"
+func urlPathWithoutVariables(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	segments := strings.Split(u.EscapedPath(), "/")
+	for i, segment := range segments {
+		if placeholder := pathVariablePlaceholder(segment); placeholder != "" {
+			segments[i] = placeholder
+		}
+	}
+	return strings.Join(segments, "/"), nil
+}
+
+// Truncate path of URL to at most maxDepth segments, for limiting crawl
+// depth. Query and fragment are kept, and maxDepth below 1 keeps only the
+// root:
+//
+//	urlPathDepthLimit("https://example.com/a/b/c/d?q=1", 2) // "https://example.com/a/b?q=1"
+//
+// Paths no deeper than maxDepth are returned as-is, with a trailing slash
+// not counting as a segment.
+func urlPathDepthLimit(rawURL string, maxDepth int) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	segments := splitPathSegments(strings.TrimSuffix(u.EscapedPath(), "/"))
+	if len(segments) <= maxDepth {
+		return rawURL, nil
+	}
+	escaped := "/" + strings.Join(segments[:max(maxDepth, 0)], "/")
+	// Unescaping can't fail, as the path was escaped by url.URL
+	u.Path, _ = url.PathUnescape(escaped)
+	u.RawPath = escaped
+	return u.String(), nil
+}
+
+// Path prefix of well-known URIs, RFC 8615
+const wellKnownPathPrefix = "/.well-known/"
+
+// Get suffix of a well-known URI after "/.well-known/", e.g.
+// "openid-configuration" for
+// "https://example.com/.well-known/openid-configuration". Returns false for
+// other URLs, and "/.well-known/" itself, as the suffix must not be empty.
+func urlWellKnownSuffix(rawURL string) (string, bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", false, err
+	}
+	suffix, ok := strings.CutPrefix(u.Path, wellKnownPathPrefix)
+	if !ok || suffix == "" {
+		return "", false, nil
+	}
+	return suffix, true, nil
+}
+
+// Check if URL is a well-known URI of RFC 8615, such as
+// "https://example.com/.well-known/security.txt". Crawlers may want to skip
+// or specially handle these.
+func urlIsWellKnown(rawURL string) (bool, error) {
+	_, ok, err := urlWellKnownSuffix(rawURL)
+	return ok, err
+}
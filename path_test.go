@@ -0,0 +1,449 @@
+package main
+
+import (
+	"errors"
+	"regexp"
+	"slices"
+	"testing"
+)
+
+func TestURLPathJoin(t *testing.T) {
+	// RFC 3986 section 5.4 reference resolution examples, restricted to paths.
+	// Base URI "http://a/b/c/d;p?q" has the path "/b/c/d;p".
+	const base = "/b/c/d;p"
+	tests := map[string]string{
+		// Normal examples
+		"g":       "/b/c/g",
+		"./g":     "/b/c/g",
+		"g/":      "/b/c/g/",
+		"/g":      "/g",
+		";x":      "/b/c/;x",
+		"g;x":     "/b/c/g;x",
+		"":        "/b/c/d;p",
+		".":       "/b/c/",
+		"./":      "/b/c/",
+		"..":      "/b/",
+		"../":     "/b/",
+		"../g":    "/b/g",
+		"../..":   "/",
+		"../../":  "/",
+		"../../g": "/g",
+		// Abnormal examples
+		"../../../g":    "/g",
+		"../../../../g": "/g",
+		"/./g":          "/g",
+		"/../g":         "/g",
+		"g.":            "/b/c/g.",
+		".g":            "/b/c/.g",
+		"g..":           "/b/c/g..",
+		"..g":           "/b/c/..g",
+		"./../g":        "/b/g",
+		"./g/.":         "/b/c/g/",
+		"g/./h":         "/b/c/g/h",
+		"g/../h":        "/b/c/h",
+		"g;x=1/./y":     "/b/c/g;x=1/y",
+		"g;x=1/../y":    "/b/c/y",
+	}
+
+	for relative, expected := range tests {
+		if got := urlPathJoin(base, relative); got != expected {
+			t.Errorf("❌ urlPathJoin(%q, %q): expected %q, got %q", base, relative, expected, got)
+		}
+	}
+}
+
+func TestURLPathMatch(t *testing.T) {
+	tests := []struct {
+		url      string
+		pattern  string
+		expected bool
+	}{
+		{"https://example.com/articles/foo", "/articles/*", true},
+		{"https://example.com/articles/bar?x=1", "/articles/*", true},
+		{"https://example.com/articles/foo/bar", "/articles/*", false},
+		{"https://example.com/articles/", "/articles/*", false},
+		{"https://example.com/articles", "/articles/*", false},
+		{"https://example.com/articles", "/articles", true},
+		{"https://example.com/news", "/articles", false},
+		{"https://example.com/", "/", true},
+		{"https://example.com", "/", true},
+		// "**" at end
+		{"https://example.com/articles", "/articles/**", true},
+		{"https://example.com/articles/foo/bar", "/articles/**", true},
+		{"https://example.com/news/foo", "/articles/**", false},
+		// "**/" prefix
+		{"https://example.com/feed.xml", "/**/feed.xml", true},
+		{"https://example.com/a/b/feed.xml", "/**/feed.xml", true},
+		{"https://example.com/a/b/feed.json", "/**/feed.xml", false},
+		// Multiple wildcards
+		{"https://example.com/user/42/post/7", "/user/*/post/*", true},
+		{"https://example.com/user/42/comment/7", "/user/*/post/*", false},
+		{"https://example.com/a/x/y/b/z", "/a/**/b/*", true},
+		{"https://example.com/a/b/z", "/a/**/b/*", true},
+		{"https://example.com/a/x/b/z/c", "/a/**/b/*", false},
+		{"https://example.com/a/b/c/d", "/**/*/d", true},
+		// Wildcards are whole segments only
+		{"https://example.com/articles/foo", "/articles/f*", false},
+	}
+
+	for _, test := range tests {
+		if got, err := urlPathMatch(test.url, test.pattern); err != nil || got != test.expected {
+			t.Errorf("❌ urlPathMatch(%q, %q): expected %v, got %v (error: %v)", test.url, test.pattern, test.expected, got, err)
+		}
+	}
+
+	if _, err := urlPathMatch("://invalid", "/**"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLPatternMatchesAny(t *testing.T) {
+	patterns := []string{"/articles/*", "/**/feed.xml"}
+	tests := map[string]bool{
+		"https://example.com/articles/foo": true,
+		"https://example.com/a/b/feed.xml": true,
+		"https://example.com/articles/a/b": false,
+		"https://example.com/":             false,
+	}
+
+	for input, expected := range tests {
+		if got, err := urlPatternMatchesAny(input, patterns); err != nil || got != expected {
+			t.Errorf("❌ Expected %v for %q, got %v (error: %v)", expected, input, got, err)
+		}
+	}
+	if got, _ := urlPatternMatchesAny("https://example.com/", nil); got {
+		t.Error("❌ Expected no match without patterns")
+	}
+}
+
+func TestURLStripMatrixParameters(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/path;param=value/":              "https://example.com/path/",
+		"https://example.com/a;x=1/b;y=2;z=3/c":              "https://example.com/a/b/c",
+		"https://example.com/a;x=1/b?q=1;2&r=2#frag;x":       "https://example.com/a/b?q=1;2&r=2#frag;x",
+		"https://example.com/app/login.jsf;jsessionid=ABC?x": "https://example.com/app/login.jsf?x",
+		"https://example.com/a%3Bb/c":                        "https://example.com/a%3Bb/c",
+		"https://example.com/a%20b;x=1":                      "https://example.com/a%20b",
+		"https://example.com/plain?x=1":                      "https://example.com/plain?x=1",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlStripMatrixParameters(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	if _, err := urlStripMatrixParameters("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLNormalizeStripSessionFromPath(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/shop/(S(abc123))/products":            "https://example.com/shop/products",
+		"https://example.com/(F(xyz-1_2))/login.aspx?b=2&a=1":      "https://example.com/login.aspx?a=1&b=2",
+		"https://example.com/(A(anon)S(sess)F(auth))/default.aspx": "https://example.com/default.aspx",
+		"https://example.com/(S(abc123))":                          "https://example.com/",
+		"https://example.com/shop/products":                        "https://example.com/shop/products",
+		"https://example.com/wiki/Python_(programming_language)":   "https://example.com/wiki/Python_(programming_language)",
+		"https://example.com/shop/(S(abc123))/a%20b?q=1#top":       "https://example.com/shop/a%20b?q=1#top",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlNormalizeStripSessionFromPath(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	// Custom patterns replace the default ones
+	custom := regexp.MustCompile(`/sessionid/[^/]+`)
+	got, err := urlNormalizeStripSessionFromPath("https://example.com/index.php/sessionid/abc123/page", custom)
+	if expected := "https://example.com/index.php/page"; err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+	got, err = urlNormalizeStripSessionFromPath("https://example.com/(S(abc123))/sessionid/1", custom)
+	if expected := "https://example.com/(S(abc123))"; err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	if _, err := urlNormalizeStripSessionFromPath("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLPathExtension(t *testing.T) {
+	tests := []struct {
+		url       string
+		extension string // Empty for none
+		stripped  string
+	}{
+		{"https://example.com/file.tar.gz?v=2", "gz", "https://example.com/file.tar?v=2"},
+		{"https://example.com/a/photo.JPG#top", "JPG", "https://example.com/a/photo#top"},
+		{"https://example.com/v1.2/page", "", "https://example.com/v1.2/page"},
+		{"https://example.com/no-extension", "", "https://example.com/no-extension"},
+		{"https://example.com/repo/.gitignore", "", "https://example.com/repo/.gitignore"},
+		{"https://example.com/docs.old/", "", "https://example.com/docs.old/"},
+		{"https://example.com/trailing.", "", "https://example.com/trailing."},
+		{"https://example.com/?file=a.txt", "", "https://example.com/?file=a.txt"},
+		{"https://example.com/a%20b.html?q=c.d", "html", "https://example.com/a%20b?q=c.d"},
+		{"https://example.com", "", "https://example.com"},
+	}
+
+	for _, test := range tests {
+		extension, ok := urlExtractPathExtension(test.url)
+		if extension != test.extension || ok != (test.extension != "") {
+			t.Errorf("❌ urlExtractPathExtension(%q): expected %q, got %q (%v)", test.url, test.extension, extension, ok)
+		}
+		if stripped, err := urlStripPathExtension(test.url); err != nil || stripped != test.stripped {
+			t.Errorf("❌ urlStripPathExtension(%q): expected %q, got %q (error: %v)", test.url, test.stripped, stripped, err)
+		}
+	}
+
+	if _, ok := urlExtractPathExtension("://invalid"); ok {
+		t.Error("❌ Expected no extension for invalid URL")
+	}
+	if _, err := urlStripPathExtension("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLSortPathSegments(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/api/users/groups/admins":  "https://example.com/api/admins/groups/users",
+		"https://example.com/api/users/groups/admins/": "https://example.com/api/admins/groups/users/",
+		"https://example.com/api/b/a?z=1&y=2#f":        "https://example.com/api/a/b?z=1&y=2#f",
+		"https://example.com/api/a%20b/a":              "https://example.com/api/a/a%20b",
+		"https://example.com/zeta/alpha":               "https://example.com/zeta/alpha",
+		"https://example.com/users":                    "https://example.com/users",
+		"https://example.com/":                         "https://example.com/",
+	}
+
+	for input, expected := range tests {
+		if got, err := urlSortPathSegments(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	if _, err := urlSortPathSegments("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLNormalizeTrailingSlashPolicy(t *testing.T) {
+	paths := []string{"/", "/dir/", "/dir/file.html", "/api/users", "/a%20b"}
+	tests := map[TrailingSlashPolicy][]string{
+		TrailingSlashPreserve:     {"/", "/dir/", "/dir/file.html", "/api/users", "/a%20b"},
+		TrailingSlashAlwaysAdd:    {"/", "/dir/", "/dir/file.html/", "/api/users/", "/a%20b/"},
+		TrailingSlashAlwaysRemove: {"/", "/dir", "/dir/file.html", "/api/users", "/a%20b"},
+		TrailingSlashSmart:        {"/", "/dir/", "/dir/file.html", "/api/users/", "/a%20b/"},
+	}
+
+	for policy, expected := range tests {
+		for i, path := range paths {
+			input := "https://Example.com" + path + "?b=2&a=1"
+			want := "https://example.com" + expected[i] + "?a=1&b=2"
+			if got, err := urlNormalizeTrailingSlashPolicy(input, policy); err != nil || got != want {
+				t.Errorf("❌ Expected %q for %q with policy %d, got %q (error: %v)", want, input, policy, got, err)
+			}
+		}
+	}
+
+	// File-like paths with a trailing slash lose it with the smart policy
+	if got, _ := urlNormalizeTrailingSlashPolicy("https://example.com/file.html/", TrailingSlashSmart); got != "https://example.com/file.html" {
+		t.Errorf("❌ Expected slash removed from file-like path, got %q", got)
+	}
+	if got, _ := urlNormalizeTrailingSlashPolicy("https://example.com", TrailingSlashAlwaysAdd); got != "https://example.com/" {
+		t.Errorf("❌ Expected slash added to empty path, got %q", got)
+	}
+
+	if _, err := urlNormalizeTrailingSlashPolicy("://invalid", TrailingSlashSmart); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLPathIsValid(t *testing.T) {
+	tests := map[string]bool{
+		"/":                      true,
+		"/a/b/":                  true,
+		"/a;x=1/b:c@d/~e!$&'()*": true,
+		"/p%C3%A4iv%C3%A4":       true,
+		"/a/./b/../c":            true,
+		"/a/..":                  true,
+		// Not starting with "/"
+		"":    false,
+		"a/b": false,
+		// Invalid characters or escapes
+		"/a b":   false,
+		"/päivä": false,
+		"/a?b":   false,
+		"/a#b":   false,
+		"/%zz":   false,
+		"/a%4":   false,
+		// Empty segments
+		"//a":   false,
+		"/a//b": false,
+		// Above the root
+		"/..":          false,
+		"/a/../..":     false,
+		"/%2e%2E/etc":  false,
+		"/a/.%2E/.%2e": false,
+	}
+
+	for input, expected := range tests {
+		if valid := urlPathIsValid(input); valid != expected {
+			t.Errorf("❌ Expected %v for %q, got %v", expected, input, valid)
+		}
+	}
+}
+
+func TestURLPathNormalize(t *testing.T) {
+	tests := map[string]string{
+		"/a/%7euser/./b/../c d": "/a/~user/c%20d",
+		"/a/%2fb/%3a":           "/a/%2Fb/%3A",
+		"/p%C3%A4iv%C3%A4":      "/p%C3%A4iv%C3%A4",
+		"/päivä":                "/p%C3%A4iv%C3%A4",
+		"/a/%2E%2E/b":           "/b",
+		"/a;x=1/b/":             "/a;x=1/b/",
+		"/../a":                 "/a",
+		"/":                     "/",
+	}
+
+	for input, expected := range tests {
+		got, err := urlPathNormalize(input)
+		if err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+			continue
+		}
+		if again, _ := urlPathNormalize(got); again != got {
+			t.Errorf("❌ Expected %q to stay normalized, got %q", got, again)
+		}
+	}
+
+	for _, input := range []string{"/%zz", "/a%4", "/%"} {
+		if _, err := urlPathNormalize(input); err == nil {
+			t.Errorf("❌ Expected error for %q", input)
+		}
+	}
+}
+
+func TestURLPathVariables(t *testing.T) {
+	tests := []struct {
+		url       string
+		variables []string
+		template  string
+	}{
+		{"https://example.com/users/42/orders/7", []string{"42", "7"}, "/users/{id}/orders/{id}"},
+		{"https://example.com/orders/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80/items?page=2", []string{"7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80"}, "/orders/{uuid}/items"},
+		{"https://example.com/v2/item-42/2024/", []string{"2024"}, "/v2/item-42/{id}/"},
+		{"https://example.com/about/team", nil, "/about/team"},
+		{"https://example.com/a%20b/1", []string{"1"}, "/a%20b/{id}"},
+		{"https://example.com", nil, ""},
+	}
+
+	for _, test := range tests {
+		if variables := urlPathVariables(test.url); !slices.Equal(variables, test.variables) {
+			t.Errorf("❌ Expected variables %v for %q, got %v", test.variables, test.url, variables)
+		}
+		if template, err := urlPathWithoutVariables(test.url); err != nil || template != test.template {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", test.template, test.url, template, err)
+		}
+	}
+
+	if variables := urlPathVariables("://invalid"); variables != nil {
+		t.Errorf("❌ Expected no variables for invalid URL, got %v", variables)
+	}
+	if _, err := urlPathWithoutVariables("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLPathDepthLimit(t *testing.T) {
+	tests := []struct {
+		url      string
+		maxDepth int
+		expected string
+	}{
+		{"https://example.com/a/b/c?q=1", 0, "https://example.com/?q=1"},
+		{"https://example.com/a/b/c", -1, "https://example.com/"},
+		{"https://example.com/a/b/c?q=1#top", 1, "https://example.com/a?q=1#top"},
+		{"https://example.com/a/b/c", 3, "https://example.com/a/b/c"},
+		{"https://example.com/a/b/c", 2, "https://example.com/a/b"},
+		{"https://example.com/a/b/c/", 3, "https://example.com/a/b/c/"},
+		{"https://example.com/a/b/c/", 2, "https://example.com/a/b"},
+		{"https://example.com/a%20b/c%2Fd/e", 2, "https://example.com/a%20b/c%2Fd"},
+		{"https://example.com/", 0, "https://example.com/"},
+		{"https://example.com", 0, "https://example.com"},
+	}
+
+	for _, test := range tests {
+		if got, err := urlPathDepthLimit(test.url, test.maxDepth); err != nil || got != test.expected {
+			t.Errorf("❌ Expected %q for %q with depth %d, got %q (error: %v)", test.expected, test.url, test.maxDepth, got, err)
+		}
+	}
+
+	if _, err := urlPathDepthLimit("://invalid", 1); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLWellKnownSuffix(t *testing.T) {
+	tests := map[string]string{
+		"https://example.com/.well-known/robots.txt":             "robots.txt",
+		"https://example.com/.well-known/security.txt":           "security.txt",
+		"https://example.com/.well-known/openid-configuration?x": "openid-configuration",
+		"https://example.com/.well-known/acme-challenge/token":   "acme-challenge/token",
+		"https://example.com/.well-known/":                       "",
+		"https://example.com/robots.txt":                         "",
+		"https://example.com/docs/.well-known/security.txt":      "",
+		"https://example.com/":                                   "",
+		"https://example.com":                                    "",
+	}
+
+	for input, expected := range tests {
+		suffix, ok, err := urlWellKnownSuffix(input)
+		if err != nil || suffix != expected || ok != (expected != "") {
+			t.Errorf("❌ urlWellKnownSuffix(%q): expected %q, got %q, %v (error: %v)", input, expected, suffix, ok, err)
+		}
+		if isWellKnown, err := urlIsWellKnown(input); err != nil || isWellKnown != (expected != "") {
+			t.Errorf("❌ urlIsWellKnown(%q): expected %v, got %v (error: %v)", input, expected != "", isWellKnown, err)
+		}
+	}
+
+	if _, _, err := urlWellKnownSuffix("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+	if _, err := urlIsWellKnown("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLJoinPath(t *testing.T) {
+	tests := []struct {
+		base     string
+		segments []string
+		expected string
+	}{
+		{"https://api.example.com/v1", []string{"users", "42", "orders"}, "https://api.example.com/v1/users/42/orders"},
+		{"https://api.example.com/v1/", []string{"users", "42"}, "https://api.example.com/v1/users/42"},
+		{"https://api.example.com", []string{"users"}, "https://api.example.com/users"},
+		{"https://api.example.com/", []string{"users"}, "https://api.example.com/users"},
+		{"https://api.example.com/v1?key=x#top", []string{"users"}, "https://api.example.com/v1/users?key=x#top"},
+		{"https://api.example.com/a%2Fb", []string{"a/b c", "päivä", "?#%"}, "https://api.example.com/a%2Fb/a%2Fb%20c/p%C3%A4iv%C3%A4/%3F%23%25"},
+		{"https://api.example.com/v1", nil, "https://api.example.com/v1"},
+	}
+
+	for _, test := range tests {
+		if got, err := urlJoinPath(test.base, test.segments...); err != nil || got != test.expected {
+			t.Errorf("❌ Expected %q for %q and %q, got %q (error: %v)", test.expected, test.base, test.segments, got, err)
+		}
+	}
+
+	for _, segment := range []string{"", ".", ".."} {
+		if _, err := urlJoinPath("https://api.example.com/v1", "users", segment); !errors.Is(err, errInvalidPathSegment) {
+			t.Errorf("❌ Expected errInvalidPathSegment for %q, got: %v", segment, err)
+		}
+	}
+	if _, err := urlJoinPath("://invalid", "users"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,117 @@
+package main
+
+import (
+	"net/url"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// NormalizationStep transforms a parsed URL, it may modify the URL in place.
+type NormalizationStep func(u *url.URL) (*url.URL, error)
+
+// NormalizationPipeline composes normalization steps that are run in order.
+//
+// As an alternative to passing purell flags, it allows custom steps between the
+// standard ones:
+//
+//	normalize := NewNormalizationPipeline().LowercaseScheme().Step(myStep).SortQueryParams().Build()
+//	normalized, err := normalize("HTTP://example.com/?b=2&a=1")
+type NormalizationPipeline struct {
+	steps []NormalizationStep
+}
+
+func NewNormalizationPipeline() *NormalizationPipeline {
+	return &NormalizationPipeline{}
+}
+
+// Step appends a custom step to the pipeline.
+func (p *NormalizationPipeline) Step(step NormalizationStep) *NormalizationPipeline {
+	p.steps = append(p.steps, step)
+	return p
+}
+
+// Flags appends a step applying the given purell normalization flags.
+func (p *NormalizationPipeline) Flags(flags purell.NormalizationFlags) *NormalizationPipeline {
+	return p.Step(func(u *url.URL) (*url.URL, error) {
+		purell.NormalizeURL(u, flags)
+		return u, nil
+	})
+}
+
+func (p *NormalizationPipeline) LowercaseScheme() *NormalizationPipeline {
+	return p.Flags(purell.FlagLowercaseScheme)
+}
+
+func (p *NormalizationPipeline) LowercaseHost() *NormalizationPipeline {
+	return p.Flags(purell.FlagLowercaseHost)
+}
+
+func (p *NormalizationPipeline) RemoveDefaultPort() *NormalizationPipeline {
+	return p.Flags(purell.FlagRemoveDefaultPort)
+}
+
+func (p *NormalizationPipeline) RemoveDotSegments() *NormalizationPipeline {
+	return p.Flags(purell.FlagRemoveDotSegments)
+}
+
+func (p *NormalizationPipeline) RemoveFragment() *NormalizationPipeline {
+	return p.Flags(purell.FlagRemoveFragment)
+}
+
+func (p *NormalizationPipeline) SortQueryParams() *NormalizationPipeline {
+	return p.Flags(purell.FlagSortQuery)
+}
+
+// RemoveMatrixParams appends a step removing matrix parameters from path
+// segments, see urlStripMatrixParameters. Unlike the other steps, it has no
+// purell flag.
+func (p *NormalizationPipeline) RemoveMatrixParams() *NormalizationPipeline {
+	return p.Step(func(u *url.URL) (*url.URL, error) {
+		removeMatrixParams(u)
+		return u, nil
+	})
+}
+
+// SortPathSegments appends a step sorting path segments after the first one,
+// see urlSortPathSegments. Like RemoveMatrixParams, it has no purell flag.
+func (p *NormalizationPipeline) SortPathSegments() *NormalizationPipeline {
+	return p.Step(func(u *url.URL) (*url.URL, error) {
+		sortPathSegments(u)
+		return u, nil
+	})
+}
+
+// Build returns a function running the steps of the pipeline in order.
+func (p *NormalizationPipeline) Build() func(rawURL string) (string, error) {
+	steps := append([]NormalizationStep(nil), p.steps...)
+	return func(rawURL string) (string, error) {
+		u, err := parseURL(rawURL)
+		if err != nil {
+			return "", err
+		}
+		for _, step := range steps {
+			if u, err = step(u); err != nil {
+				return "", err
+			}
+		}
+		return u.String(), nil
+	}
+}
+
+// URLNormalizer holds normalization flags for reuse.
+//
+// It is a small value type, so copies can be used in parallel, and its
+// Normalize method value can be passed where a func(string) (string, error)
+// is expected, just like a built pipeline.
+type URLNormalizer struct {
+	flags purell.NormalizationFlags
+}
+
+func NewURLNormalizer(flags purell.NormalizationFlags) URLNormalizer {
+	return URLNormalizer{flags: flags}
+}
+
+// Normalize URL with the flags of the normalizer.
+func (n URLNormalizer) Normalize(rawURL string) (string, error) {
+	return normalizeURLWithFlags(rawURL, n.flags)
+}
@@ -0,0 +1,114 @@
+package main
+
+import (
+	"errors"
+	"net/url"
+	"strings"
+	"testing"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+func TestNormalizationPipeline(t *testing.T) {
+	normalize := NewNormalizationPipeline().
+		LowercaseScheme().
+		LowercaseHost().
+		RemoveDefaultPort().
+		RemoveDotSegments().
+		SortQueryParams().
+		Build()
+
+	got, err := normalize("HTTP://Example.COM:80/a/./b/../c?b=2&a=1")
+	if err != nil {
+		t.Fatalf("❌ Pipeline failed: %v", err)
+	}
+	if expected := "http://example.com/a/c?a=1&b=2"; got != expected {
+		t.Errorf("❌ Expected: %s\nGot: %s", expected, got)
+	}
+}
+
+func TestNormalizationPipelineCustomStep(t *testing.T) {
+	var order []string
+	record := func(name string) NormalizationStep {
+		return func(u *url.URL) (*url.URL, error) {
+			order = append(order, name)
+			return u, nil
+		}
+	}
+	removeWWW := func(u *url.URL) (*url.URL, error) {
+		order = append(order, "remove-www")
+		u.Host = strings.TrimPrefix(u.Host, "www.")
+		return u, nil
+	}
+
+	// Custom step only matches after the host has been lowercased
+	normalize := NewNormalizationPipeline().
+		Step(record("first")).
+		LowercaseHost().
+		Step(removeWWW).
+		SortQueryParams().
+		Step(record("last")).
+		Build()
+
+	got, err := normalize("https://WWW.Example.com/?b=2&a=1")
+	if err != nil {
+		t.Fatalf("❌ Pipeline failed: %v", err)
+	}
+	if expected := "https://example.com/?a=1&b=2"; got != expected {
+		t.Errorf("❌ Expected: %s\nGot: %s", expected, got)
+	}
+	if strings.Join(order, ",") != "first,remove-www,last" {
+		t.Errorf("❌ Unexpected step order: %v", order)
+	}
+}
+
+func TestNormalizationPipelineErrors(t *testing.T) {
+	stepErr := errors.New("rejected")
+	normalize := NewNormalizationPipeline().
+		Step(func(u *url.URL) (*url.URL, error) { return nil, stepErr }).
+		Build()
+
+	if _, err := normalize("https://example.com/"); !errors.Is(err, stepErr) {
+		t.Errorf("❌ Expected step error, got: %v", err)
+	}
+	var parseErr *URLParseError
+	if _, err := normalize("://invalid"); !errors.As(err, &parseErr) {
+		t.Errorf("❌ Expected URLParseError, got: %v", err)
+	}
+}
+
+func TestURLNormalizer(t *testing.T) {
+	inputs := []string{"HTTP://Example.COM:80/a/./b/../c?b=2&a=1", "https://example.com/#top", "://invalid"}
+	normalizer := NewURLNormalizer(defaultNormalizationFlags | purell.FlagRemoveFragment)
+	copied := normalizer
+
+	// Method value works as a plain function
+	var normalize func(string) (string, error) = copied.Normalize
+	for _, rawURL := range inputs {
+		expected, expectedErr := normalizeURLWithFlags(rawURL, defaultNormalizationFlags|purell.FlagRemoveFragment)
+		got, err := normalize(rawURL)
+		if got != expected || (err == nil) != (expectedErr == nil) {
+			t.Errorf("❌ Normalize(%q): expected %q (error: %v), got %q (error: %v)", rawURL, expected, expectedErr, got, err)
+		}
+	}
+}
+
+func TestNormalizationPipelineRemoveMatrixParams(t *testing.T) {
+	const input = "https://example.com/a;x=1/b;y=2?b=2&a=1"
+	normalize := NewNormalizationPipeline().SortQueryParams().Build()
+	if got, _ := normalize(input); got != "https://example.com/a;x=1/b;y=2?a=1&b=2" {
+		t.Errorf("❌ Expected matrix parameters to be kept by default, got %q", got)
+	}
+
+	normalize = NewNormalizationPipeline().RemoveMatrixParams().SortQueryParams().Build()
+	if got, err := normalize(input); err != nil || got != "https://example.com/a/b?a=1&b=2" {
+		t.Errorf("❌ Expected matrix parameters to be removed, got %q (error: %v)", got, err)
+	}
+}
+
+func TestNormalizationPipelineSortPathSegments(t *testing.T) {
+	normalize := NewNormalizationPipeline().RemoveDotSegments().SortPathSegments().Build()
+	if got, err := normalize("https://example.com/api/c/./b/a"); err != nil || got != "https://example.com/api/a/b/c" {
+		t.Errorf("❌ Expected sorted path segments, got %q (error: %v)", got, err)
+	}
+}
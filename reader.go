@@ -0,0 +1,107 @@
+package main
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"iter"
+	"os"
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Iterate over URLs in reader, one per line.
+//
+// Lines are trimmed, and blank lines and lines starting with "#" are skipped.
+// Reading is lazy: input is only read as far as the caller iterates, so
+// breaking out of the loop stops reading from a pipe. A read error is yielded
+// last, with an empty URL.
+func readURLLines(r io.Reader) iter.Seq2[string, error] {
+	return func(yield func(string, error) bool) {
+		scanner := bufio.NewScanner(r)
+		for scanner.Scan() {
+			line := strings.TrimSpace(scanner.Text())
+			if line == "" || strings.HasPrefix(line, "#") {
+				continue
+			}
+			if !yield(line, nil) {
+				return
+			}
+		}
+		if err := scanner.Err(); err != nil {
+			yield("", err)
+		}
+	}
+}
+
+// Iterate over normalized URLs read from reader, one per line, as with
+// readURLLines. URLs that fail to normalize are yielded with their error.
+func normalizeURLsFromReader(r io.Reader, flags purell.NormalizationFlags) iter.Seq2[string, error] {
+	return func(yield func(string, error) bool) {
+		for line, err := range readURLLines(r) {
+			if err == nil {
+				line, err = normalizeURLWithFlags(line, flags)
+			}
+			if !yield(line, err) {
+				return
+			}
+		}
+	}
+}
+
+// Normalize URLs in input file, one per line as with readURLLines, and write
+// them into output file, one per line. Returns the number of URLs written.
+//
+// Failed URLs are skipped, and logged with their error into output path with
+// an ".errors" suffix. The log is appended to, and only created if a URL
+// fails.
+func normalizeURLListToFile(inputPath, outputPath string, flags purell.NormalizationFlags) (count int, err error) {
+	in, err := os.Open(inputPath)
+	if err != nil {
+		return 0, err
+	}
+	defer in.Close()
+	out, err := os.Create(outputPath)
+	if err != nil {
+		return 0, err
+	}
+	defer func() {
+		if closeErr := out.Close(); err == nil {
+			err = closeErr
+		}
+	}()
+
+	var errorLog *os.File
+	defer func() {
+		if errorLog != nil {
+			if closeErr := errorLog.Close(); err == nil {
+				err = closeErr
+			}
+		}
+	}()
+
+	w := bufio.NewWriter(out)
+	for line, readErr := range readURLLines(in) {
+		if readErr != nil {
+			return count, readErr
+		}
+		normalized, normalizeErr := normalizeURLWithFlags(line, flags)
+		if normalizeErr != nil {
+			if errorLog == nil {
+				if errorLog, err = os.OpenFile(outputPath+".errors", os.O_WRONLY|os.O_CREATE|os.O_APPEND, 0o644); err != nil {
+					return count, err
+				}
+			}
+			if _, err = fmt.Fprintf(errorLog, "%s\t%v\n", line, normalizeErr); err != nil {
+				return count, err
+			}
+			continue
+		}
+		if _, err = w.WriteString(normalized + "\n"); err != nil {
+			return count, err
+		}
+		count++
+	}
+	return count, w.Flush()
+}
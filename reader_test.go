@@ -0,0 +1,136 @@
+package main
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+// Reader returning one line per read, counting the reads
+type lineReader struct {
+	lines []string
+	reads int
+}
+
+func (r *lineReader) Read(p []byte) (int, error) {
+	r.reads++
+	if len(r.lines) == 0 {
+		return 0, errors.New("unexpected read past the end")
+	}
+	n := copy(p, r.lines[0]+"\n")
+	r.lines = r.lines[1:]
+	return n, nil
+}
+
+func TestNormalizeURLsFromReader(t *testing.T) {
+	input := strings.NewReader(strings.Join([]string{
+		"# Comment line",
+		"  https://Example.com/b?z=1&a=2  ",
+		"",
+		"   ",
+		"://invalid",
+		"\t# Indented comment",
+		"http://example.com:80/a/../c",
+	}, "\n"))
+
+	type result struct {
+		url   string
+		isErr bool
+	}
+	var got []result
+	for normalized, err := range normalizeURLsFromReader(input, defaultNormalizationFlags) {
+		got = append(got, result{normalized, err != nil})
+	}
+
+	expected := []result{
+		{"https://example.com/b?a=2&z=1", false},
+		{"", true},
+		{"http://example.com/c", false},
+	}
+	if len(got) != len(expected) {
+		t.Fatalf("❌ Expected %d results, got %d: %v", len(expected), len(got), got)
+	}
+	for i := range expected {
+		if got[i] != expected[i] {
+			t.Errorf("❌ Result %d: expected %v, got %v", i, expected[i], got[i])
+		}
+	}
+}
+
+func TestReadURLLinesLazy(t *testing.T) {
+	r := &lineReader{lines: []string{"# header", "https://example.com/1", "https://example.com/2", "https://example.com/3"}}
+	for line, err := range readURLLines(r) {
+		if err != nil || line != "https://example.com/1" {
+			t.Errorf("❌ Unexpected first line %q (error: %v)", line, err)
+		}
+		break
+	}
+	if r.reads != 2 || len(r.lines) != 2 {
+		t.Errorf("❌ Expected reading to stop after the first URL, got %d reads with %d lines left", r.reads, len(r.lines))
+	}
+
+	// Read error is yielded at the end
+	var lines []string
+	var readErr error
+	for line, err := range readURLLines(&lineReader{lines: []string{"https://example.com/1"}}) {
+		if err != nil {
+			readErr = err
+			continue
+		}
+		lines = append(lines, line)
+	}
+	if len(lines) != 1 || readErr == nil {
+		t.Errorf("❌ Expected one line and read error, got %v (error: %v)", lines, readErr)
+	}
+}
+
+func TestNormalizeURLListToFile(t *testing.T) {
+	input := writeTestFile(t, "urls.txt",
+		"# URLs to normalize",
+		"HTTPS://Example.com/b?z=1&a=2",
+		"",
+		"://invalid",
+		"http://example.com:80/a/../c",
+	)
+	output := filepath.Join(t.TempDir(), "normalized.txt")
+
+	count, err := normalizeURLListToFile(input, output, defaultNormalizationFlags)
+	if err != nil || count != 2 {
+		t.Fatalf("❌ Expected 2 normalized URLs, got %d (error: %v)", count, err)
+	}
+	data, err := os.ReadFile(output)
+	if expected := "https://example.com/b?a=2&z=1\nhttp://example.com/c\n"; err != nil || string(data) != expected {
+		t.Errorf("❌ Expected output %q, got %q (error: %v)", expected, data, err)
+	}
+	errorLog, err := os.ReadFile(output + ".errors")
+	if err != nil || !strings.HasPrefix(string(errorLog), "://invalid\t") || strings.Count(string(errorLog), "\n") != 1 {
+		t.Errorf("❌ Expected one failed URL in error log, got %q (error: %v)", errorLog, err)
+	}
+
+	// Error log is appended to, output is truncated
+	if count, err := normalizeURLListToFile(input, output, defaultNormalizationFlags); err != nil || count != 2 {
+		t.Errorf("❌ Expected 2 normalized URLs on second run, got %d (error: %v)", count, err)
+	}
+	if errorLog, _ := os.ReadFile(output + ".errors"); strings.Count(string(errorLog), "\n") != 2 {
+		t.Errorf("❌ Expected two entries in error log, got %q", errorLog)
+	}
+	if data, _ := os.ReadFile(output); strings.Count(string(data), "\n") != 2 {
+		t.Errorf("❌ Expected output to be truncated, got %q", data)
+	}
+
+	// No error log without failed URLs
+	valid := writeTestFile(t, "valid.txt", "https://example.com/")
+	validOutput := filepath.Join(t.TempDir(), "valid-normalized.txt")
+	if count, err := normalizeURLListToFile(valid, validOutput, defaultNormalizationFlags); err != nil || count != 1 {
+		t.Errorf("❌ Expected 1 normalized URL, got %d (error: %v)", count, err)
+	}
+	if _, err := os.Stat(validOutput + ".errors"); !errors.Is(err, os.ErrNotExist) {
+		t.Errorf("❌ Expected no error log, got: %v", err)
+	}
+
+	if _, err := normalizeURLListToFile(filepath.Join(t.TempDir(), "missing.txt"), output, defaultNormalizationFlags); err == nil {
+		t.Error("❌ Expected error for missing input file")
+	}
+}
@@ -0,0 +1,50 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+var (
+	errEmptyRedirectChain = errors.New("redirect chain is empty")
+	errRedirectCycle      = errors.New("redirect chain has a cycle")
+)
+
+// RedirectChain holds normalized URLs of a redirect chain, from the first
+// requested URL to the final destination.
+type RedirectChain struct {
+	URLs  []string // Visited URLs in order, including the final one
+	Final string
+}
+
+// Normalize URLs of a recorded redirect chain, such as one from an HTTP
+// archive. No requests are made.
+//
+// Consecutive URLs normalizing to the same URL are merged, as they are
+// the same page recorded twice. A URL visited again later fails with
+// errRedirectCycle.
+func followRedirectChain(urls []string, flags purell.NormalizationFlags) (RedirectChain, error) {
+	if len(urls) == 0 {
+		return RedirectChain{}, errEmptyRedirectChain
+	}
+	var chain RedirectChain
+	visited := make(map[string]bool)
+	for _, rawURL := range urls {
+		normalized, err := normalizeURLWithFlags(rawURL, flags)
+		if err != nil {
+			return RedirectChain{}, err
+		}
+		if len(chain.URLs) > 0 && normalized == chain.Final {
+			continue
+		}
+		if visited[normalized] {
+			return RedirectChain{}, fmt.Errorf("%s: %w", normalized, errRedirectCycle)
+		}
+		visited[normalized] = true
+		chain.URLs = append(chain.URLs, normalized)
+		chain.Final = normalized
+	}
+	return chain, nil
+}
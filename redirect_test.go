@@ -0,0 +1,52 @@
+package main
+
+import (
+	"errors"
+	"slices"
+	"testing"
+)
+
+func TestFollowRedirectChain(t *testing.T) {
+	tests := []struct {
+		urls     []string
+		expected []string
+	}{
+		{
+			[]string{"http://example.com/a", "https://example.com/a", "https://www.example.com/a?b=2&a=1"},
+			[]string{"http://example.com/a", "https://example.com/a", "https://www.example.com/a?a=1&b=2"},
+		},
+		{
+			[]string{"https://example.com/"},
+			[]string{"https://example.com/"},
+		},
+		// Same page recorded twice
+		{
+			[]string{"http://t.co/x", "HTTPS://Example.com:443/a", "https://example.com/./a", "https://example.com/b"},
+			[]string{"http://t.co/x", "https://example.com/a", "https://example.com/b"},
+		},
+	}
+
+	for _, test := range tests {
+		chain, err := followRedirectChain(test.urls, defaultNormalizationFlags)
+		if err != nil || !slices.Equal(chain.URLs, test.expected) || chain.Final != test.expected[len(test.expected)-1] {
+			t.Errorf("❌ Expected %q for %q, got %+v (error: %v)", test.expected, test.urls, chain, err)
+		}
+	}
+
+	cycles := [][]string{
+		{"https://example.com/a", "https://example.com/b", "https://example.com/a"},
+		{"https://example.com/a", "https://example.com/b", "https://EXAMPLE.com:443/a", "https://example.com/c"},
+	}
+	for _, urls := range cycles {
+		if _, err := followRedirectChain(urls, defaultNormalizationFlags); !errors.Is(err, errRedirectCycle) {
+			t.Errorf("❌ Expected errRedirectCycle for %q, got: %v", urls, err)
+		}
+	}
+
+	if _, err := followRedirectChain(nil, defaultNormalizationFlags); !errors.Is(err, errEmptyRedirectChain) {
+		t.Errorf("❌ Expected errEmptyRedirectChain, got: %v", err)
+	}
+	if _, err := followRedirectChain([]string{"https://example.com/", "://invalid"}, defaultNormalizationFlags); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,60 @@
+package main
+
+import (
+	"errors"
+	"regexp"
+	"strings"
+)
+
+var errNotS3URL = errors.New("not an S3 URL")
+
+// S3 endpoint host without the ".amazonaws.com" suffix, with optional
+// virtual-hosted bucket and region, e.g. "bucket.s3.eu-north-1"
+var s3HostPattern = regexp.MustCompile(`^(?:(.+)\.)?s3(?:[.-][a-z0-9-]+)*$`)
+
+// Normalize S3 URL into the "s3://bucket/key" form.
+//
+// Accepts "s3://bucket/key", virtual-hosted style
+// "https://bucket.s3.amazonaws.com/key" and path style
+// "https://s3.amazonaws.com/bucket/key", both with or without a region in
+// the host. Query and fragment are dropped, other URLs fail with errNotS3URL.
+//
+// Experimental: The accepted forms may change, with a deprecation notice
+// before changing the result for any of them.
+func urlNormalizeAWSS3(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+	path := u.EscapedPath()
+
+	var bucket, key string
+	switch u.Scheme {
+	case "s3":
+		bucket, key = host, path
+	case "http", "https":
+		endpoint, ok := strings.CutSuffix(host, ".amazonaws.com")
+		match := s3HostPattern.FindStringSubmatch(endpoint)
+		if !ok || match == nil {
+			return "", newURLParseError(rawURL, errNotS3URL)
+		}
+		if bucket = match[1]; bucket != "" {
+			key = path
+		} else {
+			// Path style, bucket is the first path segment
+			bucket, key, _ = strings.Cut(strings.TrimPrefix(path, "/"), "/")
+			bucket, key = strings.ToLower(bucket), "/"+key
+		}
+	default:
+		return "", newURLParseError(rawURL, errNotS3URL)
+	}
+
+	if bucket == "" {
+		return "", newURLParseError(rawURL, errNotS3URL)
+	}
+	if key == "" {
+		key = "/"
+	}
+	return "s3://" + bucket + key, nil
+}
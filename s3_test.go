@@ -0,0 +1,54 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLNormalizeAWSS3(t *testing.T) {
+	const expected = "s3://my-bucket/path/to/file.txt"
+	inputs := []string{
+		"s3://my-bucket/path/to/file.txt",
+		"S3://My-Bucket/path/to/file.txt",
+		// Virtual-hosted style
+		"https://my-bucket.s3.amazonaws.com/path/to/file.txt",
+		"https://my-bucket.s3.eu-north-1.amazonaws.com/path/to/file.txt",
+		"https://my-bucket.s3-eu-west-1.amazonaws.com/path/to/file.txt",
+		"https://my-bucket.s3.dualstack.us-east-1.amazonaws.com/path/to/file.txt",
+		"http://MY-BUCKET.S3.AMAZONAWS.COM/path/to/file.txt?versionId=1#x",
+		// Path style
+		"https://s3.amazonaws.com/my-bucket/path/to/file.txt",
+		"https://s3.us-west-2.amazonaws.com/my-bucket/path/to/file.txt",
+		"https://s3-us-west-2.amazonaws.com/My-Bucket/path/to/file.txt",
+	}
+	for _, input := range inputs {
+		if got, err := urlNormalizeAWSS3(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	tests := map[string]string{
+		"https://my.dotted.bucket.s3.amazonaws.com/key": "s3://my.dotted.bucket/key",
+		"https://s3.amazonaws.com/my-bucket":            "s3://my-bucket/",
+		"s3://my-bucket":                                "s3://my-bucket/",
+		"https://my-bucket.s3.amazonaws.com/a%20b/Key":  "s3://my-bucket/a%20b/Key",
+	}
+	for input, expected := range tests {
+		if got, err := urlNormalizeAWSS3(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	for _, input := range []string{
+		"https://example.com/my-bucket/key",
+		"https://ec2.amazonaws.com/my-bucket/key",
+		"https://my-bucket.s3.amazonaws.com.attacker.com/key",
+		"https://s3.amazonaws.com/",
+		"ftp://my-bucket.s3.amazonaws.com/key",
+		"s3:///key",
+	} {
+		if _, err := urlNormalizeAWSS3(input); !errors.Is(err, errNotS3URL) {
+			t.Errorf("❌ Expected errNotS3URL for %q, got: %v", input, err)
+		}
+	}
+}
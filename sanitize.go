@@ -0,0 +1,342 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+)
+
+// Limits for URLs accepted by sanitizeURL
+const (
+	maxSanitizeURLLength = 8192
+	maxSanitizePathDepth = 50
+)
+
+// Types of dangerous patterns detected by sanitizeURL
+type DangerKind int
+
+const (
+	DangerTooLong DangerKind = iota
+	DangerNullByte
+	DangerJavascriptScheme
+	DangerDataScheme
+	DangerPathDepth
+)
+
+// DangerousURLError is returned by sanitizeURL for URLs with known dangerous patterns.
+type DangerousURLError struct {
+	Kind   DangerKind
+	Reason string
+}
+
+func (e *DangerousURLError) Error() string {
+	return "dangerous URL: " + e.Reason
+}
+
+// Reject URLs with known dangerous patterns, and normalize the rest.
+//
+// Meant for user submitted URLs. Rejects javascript: and data: schemes, null
+// bytes, excessively nested paths and overly long URLs.
+func sanitizeURL(rawURL string) (string, error) {
+	if len(rawURL) > maxSanitizeURLLength {
+		return "", &DangerousURLError{Kind: DangerTooLong, Reason: fmt.Sprintf("URL longer than %d bytes", maxSanitizeURLLength)}
+	}
+	if strings.Contains(rawURL, "\x00") || strings.Contains(rawURL, "%00") {
+		return "", &DangerousURLError{Kind: DangerNullByte, Reason: "URL contains null byte"}
+	}
+
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	switch u.Scheme {
+	case "javascript":
+		return "", &DangerousURLError{Kind: DangerJavascriptScheme, Reason: "javascript: scheme is not allowed"}
+	case "data":
+		return "", &DangerousURLError{Kind: DangerDataScheme, Reason: "data: scheme is not allowed"}
+	}
+	if strings.Count(u.Path, "/") > maxSanitizePathDepth {
+		return "", &DangerousURLError{Kind: DangerPathDepth, Reason: fmt.Sprintf("path is nested deeper than %d segments", maxSanitizePathDepth)}
+	}
+
+	return normalizeURL(rawURL)
+}
+
+var (
+	errSitemapRelativeURL = errors.New("sitemap URLs must be absolute")
+	errSitemapScheme      = errors.New("sitemap URLs must use http or https scheme")
+)
+
+// Sanitize and normalize URL for use in XML sitemaps.
+//
+// Sitemap protocol requires absolute http or https URLs. The fragment is
+// removed, and empty path is replaced with "/".
+func normalizeSitemapURL(rawURL string) (string, error) {
+	if !urlIsAbsolute(rawURL) {
+		return "", newURLParseError(rawURL, errSitemapRelativeURL)
+	}
+	sanitized, err := sanitizeURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	u, err := parseURL(sanitized)
+	if err != nil {
+		return "", err
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return "", newURLParseError(rawURL, errSitemapScheme)
+	}
+	if u.Host == "" {
+		return "", newURLParseError(rawURL, errMissingOrigin)
+	}
+	u.Fragment = ""
+	u.RawFragment = ""
+	if u.Path == "" {
+		u.Path = "/"
+	}
+	return u.String(), nil
+}
+
+// Reasons why a URL is not crawlable, returned by urlIsCrawlable
+type CrawlabilityReason int
+
+const (
+	Crawlable CrawlabilityReason = iota
+	NotCrawlableTooLong
+	NotCrawlableScheme
+	NotCrawlableEmptyHost
+	NotCrawlableCredentials
+)
+
+func (r CrawlabilityReason) String() string {
+	switch r {
+	case Crawlable:
+		return "crawlable"
+	case NotCrawlableTooLong:
+		return fmt.Sprintf("URL longer than %d bytes", maxSanitizeURLLength)
+	case NotCrawlableScheme:
+		return "scheme is not http or https"
+	case NotCrawlableEmptyHost:
+		return "host is empty"
+	case NotCrawlableCredentials:
+		return "URL contains credentials"
+	}
+	return fmt.Sprintf("CrawlabilityReason(%d)", int(r))
+}
+
+// Check if URL can be crawled: it has http or https scheme, non-empty host,
+// no credentials, and is at most maxSanitizeURLLength bytes long.
+//
+// Returns the reason when the URL is not crawlable, and an error only when it
+// can't be parsed.
+func urlIsCrawlable(rawURL string) (bool, CrawlabilityReason, error) {
+	if len(rawURL) > maxSanitizeURLLength {
+		return false, NotCrawlableTooLong, nil
+	}
+	if !urlIsAbsolute(rawURL) {
+		return false, NotCrawlableScheme, nil
+	}
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, Crawlable, err
+	}
+	switch {
+	case u.Scheme != "http" && u.Scheme != "https":
+		return false, NotCrawlableScheme, nil
+	case u.Hostname() == "":
+		return false, NotCrawlableEmptyHost, nil
+	case u.User != nil:
+		return false, NotCrawlableCredentials, nil
+	}
+	return true, Crawlable, nil
+}
+
+// Schemes providing transport security, and their insecure counterparts
+var (
+	secureSchemes  = []string{"https", "wss", "ftps"}
+	upgradeSchemes = map[string]string{"http": "https", "ws": "wss"}
+)
+
+var errSchemeNotUpgradeable = errors.New("scheme has no secure counterpart")
+
+// Check if URL uses a scheme with transport security: https, wss or ftps.
+func urlSchemeIsSecure(rawURL string) (bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	return slices.Contains(secureSchemes, u.Scheme), nil
+}
+
+// Upgrade http URL to https, and ws to wss. An explicit default port of the
+// insecure scheme is removed. Secure URLs are returned as-is, and other
+// schemes fail with errSchemeNotUpgradeable.
+func urlUpgradeScheme(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	if slices.Contains(secureSchemes, u.Scheme) {
+		return rawURL, nil
+	}
+	secure, ok := upgradeSchemes[u.Scheme]
+	if !ok {
+		return "", newURLParseError(rawURL, errSchemeNotUpgradeable)
+	}
+	if port := u.Port(); port != "" && port == defaultPorts[u.Scheme] {
+		u.Host = strings.TrimSuffix(u.Host, ":"+port)
+	}
+	u.Scheme = secure
+	return u.String(), nil
+}
+
+// InsecureSchemeError is returned by urlNormalizeEnsureHTTPS for URLs that
+// can't be upgraded to https.
+type InsecureSchemeError struct {
+	Scheme string
+}
+
+func (e *InsecureSchemeError) Error() string {
+	if e.Scheme == "" {
+		return "URL has no scheme, expected https"
+	}
+	return fmt.Sprintf("insecure scheme %q, expected https", e.Scheme)
+}
+
+// Normalize URL, requiring it to use https. Stricter version of
+// urlUpgradeScheme for "all URLs must be HTTPS" policies: http URLs are
+// upgraded, and all other schemes, including wss and ftps, fail with
+// InsecureSchemeError.
+func urlNormalizeEnsureHTTPS(rawURL string) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	switch u.Scheme {
+	case "https":
+	case "http":
+		u.Scheme = "https"
+	default:
+		return "", &InsecureSchemeError{Scheme: u.Scheme}
+	}
+	return u.String(), nil
+}
+
+// Query parameters commonly carrying redirect targets, checked in order
+var redirectParams = []string{"next", "redirect_uri", "return_to", "url"}
+
+// Replace "\" with "/" in the leading run of slashes of value, as browsers
+// treat "/\evil.example", "\/evil.example" and "\\evil.example" like
+// "//evil.example"
+func normalizeLeadingSlashes(value string) string {
+	n := len(value) - len(strings.TrimLeft(value, `/\`))
+	return strings.ReplaceAll(value[:n], `\`, "/") + value[n:]
+}
+
+// Check if value of a redirect parameter looks like a URL, absolute or
+// protocol-relative, rather than a local path
+func isRedirectURLValue(value string) bool {
+	return urlIsAbsolute(value) || strings.HasPrefix(normalizeLeadingSlashes(value), "//")
+}
+
+// Check if URL looks like an open redirect, having a common redirect
+// parameter, such as "?next=", with a URL value. Targets with a local path,
+// e.g. "?next=/account", are not redirects to other sites and don't count.
+//
+// This is a heuristic for screening user supplied URLs, and doesn't catch
+// site-specific redirect parameters.
+func urlIsRedirectTarget(rawURL string) (bool, error) {
+	_, ok, err := urlExtractRedirectTarget(rawURL)
+	return ok, err
+}
+
+// Get normalized redirect target of URL recognized by urlIsRedirectTarget.
+// Returns false if URL has no redirect parameter with a URL value. A target
+// that looks like a URL but can't be normalized is still a redirect, and is
+// returned as-is. Fails only if rawURL itself can't be parsed.
+func urlExtractRedirectTarget(rawURL string) (string, bool, error) {
+	query, err := parseQuery(rawURL)
+	if err != nil {
+		return "", false, err
+	}
+	for _, param := range redirectParams {
+		for _, value := range query[param] {
+			if !isRedirectURLValue(value) {
+				continue
+			}
+			target, err := normalizeURL(normalizeLeadingSlashes(value))
+			if err != nil {
+				return value, true, nil
+			}
+			return target, true, nil
+		}
+	}
+	return "", false, nil
+}
+
+// Lengths of URL components in bytes, as they appear in the URL, i.e.
+// escaped. Port, Query and Fragment are -1 if the URL doesn't have them,
+// to tell them apart from empty ones, as in "https://example.com/?".
+type URLComponentLengths struct {
+	Total    int
+	Scheme   int
+	Host     int // Without port and IPv6 brackets
+	Port     int
+	Path     int
+	Query    int // Without "?"
+	Fragment int // Without "#"
+}
+
+// Get lengths of URL components, without normalizing it.
+func urlComponentLengths(rawURL string) (URLComponentLengths, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return URLComponentLengths{}, err
+	}
+	lengths := URLComponentLengths{
+		Total:    len(rawURL),
+		Scheme:   len(u.Scheme),
+		Host:     len(u.Hostname()),
+		Port:     -1,
+		Path:     len(u.EscapedPath()),
+		Query:    -1,
+		Fragment: -1,
+	}
+	if port := u.Port(); port != "" {
+		lengths.Port = len(port)
+	}
+	if u.RawQuery != "" || u.ForceQuery {
+		lengths.Query = len(u.RawQuery)
+	}
+	if u.Fragment != "" || strings.HasSuffix(rawURL, "#") {
+		lengths.Fragment = len(u.EscapedFragment())
+	}
+	return lengths, nil
+}
+
+// URLLimits sets maximum lengths of URL components in bytes, see
+// URLComponentLengths. Zero means no limit.
+type URLLimits struct {
+	MaxTotal    int
+	MaxHost     int
+	MaxPath     int
+	MaxQuery    int // Web servers commonly limit query strings to 4096 bytes
+	MaxFragment int
+}
+
+// Check if any component of URL is longer than its limit.
+func urlExceedsLimit(rawURL string, limits URLLimits) (bool, error) {
+	lengths, err := urlComponentLengths(rawURL)
+	if err != nil {
+		return false, err
+	}
+	exceeds := func(length, limit int) bool {
+		return limit > 0 && length > limit
+	}
+	return exceeds(lengths.Total, limits.MaxTotal) ||
+		exceeds(lengths.Host, limits.MaxHost) ||
+		exceeds(lengths.Path, limits.MaxPath) ||
+		exceeds(lengths.Query, limits.MaxQuery) ||
+		exceeds(lengths.Fragment, limits.MaxFragment), nil
+}
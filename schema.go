@@ -0,0 +1,88 @@
+package main
+
+import (
+	"fmt"
+	"maps"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+// URLSchema describes the structure required of URLs by urlSchemaValidate.
+// Zero values leave a constraint out.
+//
+//	schema := &URLSchema{
+//		Scheme:       "https",
+//		HostSuffix:   "cdn.example.com",
+//		PathSegments: 2,
+//		SegmentPatterns: map[int]*regexp.Regexp{
+//			1: regexp.MustCompile(`^[0-9a-f]{8}\.png$`),
+//		},
+//	}
+type URLSchema struct {
+	Scheme          string                 // Required scheme
+	HostSuffix      string                 // Required host or parent domain of it
+	PathSegments    int                    // Required number of path segments, zero for any
+	SegmentPatterns map[int]*regexp.Regexp // Patterns of path segments by index, not anchored implicitly
+}
+
+// Constraints of URLSchema
+type SchemaConstraint int
+
+const (
+	SchemaScheme SchemaConstraint = iota
+	SchemaHostSuffix
+	SchemaPathSegments
+	SchemaSegmentPattern
+)
+
+// SchemaValidationError is returned by urlSchemaValidate for URLs failing a
+// constraint of the schema.
+type SchemaValidationError struct {
+	Constraint SchemaConstraint
+	Segment    int // Index of the path segment, with SchemaSegmentPattern
+	Reason     string
+}
+
+func (e *SchemaValidationError) Error() string {
+	return "URL doesn't match schema: " + e.Reason
+}
+
+// Check that URL, once normalized with the default flags, matches the
+// schema. Fails with SchemaValidationError for the first constraint not met,
+// or URLParseError if the URL can't be normalized.
+func urlSchemaValidate(rawURL string, schema *URLSchema) error {
+	normalized, err := normalizeURL(rawURL)
+	if err != nil {
+		return err
+	}
+	u, err := parseURL(normalized)
+	if err != nil {
+		return err
+	}
+
+	if schema.Scheme != "" && u.Scheme != strings.ToLower(schema.Scheme) {
+		return &SchemaValidationError{Constraint: SchemaScheme, Reason: fmt.Sprintf("expected scheme %s, got %s", schema.Scheme, u.Scheme)}
+	}
+	if suffix := strings.ToLower(strings.TrimPrefix(schema.HostSuffix, ".")); suffix != "" {
+		host := u.Hostname()
+		if host != suffix && !strings.HasSuffix(host, "."+suffix) {
+			return &SchemaValidationError{Constraint: SchemaHostSuffix, Reason: fmt.Sprintf("expected host under %s, got %s", suffix, host)}
+		}
+	}
+
+	segments := splitPathSegments(u.Path)
+	if schema.PathSegments != 0 && len(segments) != schema.PathSegments {
+		return &SchemaValidationError{Constraint: SchemaPathSegments, Reason: fmt.Sprintf("expected %d path segments, got %d", schema.PathSegments, len(segments))}
+	}
+	for _, i := range slices.Sorted(maps.Keys(schema.SegmentPatterns)) {
+		pattern := schema.SegmentPatterns[i]
+		if i < 0 || i >= len(segments) {
+			return &SchemaValidationError{Constraint: SchemaSegmentPattern, Segment: i, Reason: fmt.Sprintf("missing path segment %d", i)}
+		}
+		if !pattern.MatchString(segments[i]) {
+			return &SchemaValidationError{Constraint: SchemaSegmentPattern, Segment: i, Reason: fmt.Sprintf("path segment %d %q doesn't match %s", i, segments[i], pattern)}
+		}
+	}
+	return nil
+}
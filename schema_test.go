@@ -0,0 +1,85 @@
+package main
+
+import (
+	"errors"
+	"regexp"
+	"testing"
+)
+
+func TestURLSchemaValidate(t *testing.T) {
+	cdn := &URLSchema{
+		Scheme:       "https",
+		HostSuffix:   "cdn.example.com",
+		PathSegments: 2,
+		SegmentPatterns: map[int]*regexp.Regexp{
+			0: regexp.MustCompile(`^[a-z][a-z0-9-]*$`),
+			1: regexp.MustCompile(`^[0-9a-f]{8}\.png$`),
+		},
+	}
+
+	for _, valid := range []string{
+		"https://cdn.example.com/images/0badc0de.png",
+		"HTTPS://CDN.Example.com:443/images/./0badc0de.png?v=1",
+		"https://eu.cdn.example.com/thumbs-2/deadbeef.png",
+	} {
+		if err := urlSchemaValidate(valid, cdn); err != nil {
+			t.Errorf("❌ Expected %q to be valid, got: %v", valid, err)
+		}
+	}
+
+	tests := []struct {
+		url        string
+		constraint SchemaConstraint
+		segment    int
+	}{
+		{"http://cdn.example.com/images/0badc0de.png", SchemaScheme, 0},
+		{"https://example.com/images/0badc0de.png", SchemaHostSuffix, 0},
+		{"https://badcdn.example.com/images/0badc0de.png", SchemaHostSuffix, 0},
+		{"https://cdn.example.com.attacker.com/images/0badc0de.png", SchemaHostSuffix, 0},
+		{"https://cdn.example.com/0badc0de.png", SchemaPathSegments, 0},
+		{"https://cdn.example.com/images/a/0badc0de.png", SchemaPathSegments, 0},
+		{"https://cdn.example.com/Images/0badc0de.png", SchemaSegmentPattern, 0},
+		{"https://cdn.example.com/images/0BADC0DE.png", SchemaSegmentPattern, 1},
+		{"https://cdn.example.com/images/0badc0de.jpg", SchemaSegmentPattern, 1},
+		// Scheme is checked first when several constraints fail
+		{"http://example.com/", SchemaScheme, 0},
+	}
+	for _, test := range tests {
+		var schemaErr *SchemaValidationError
+		err := urlSchemaValidate(test.url, cdn)
+		if !errors.As(err, &schemaErr) || schemaErr.Constraint != test.constraint || schemaErr.Segment != test.segment {
+			t.Errorf("❌ Expected constraint %d (segment %d) to fail for %q, got: %v", test.constraint, test.segment, test.url, err)
+		}
+	}
+
+	var parseErr *URLParseError
+	if err := urlSchemaValidate("://invalid", cdn); !errors.As(err, &parseErr) {
+		t.Errorf("❌ Expected URLParseError, got: %v", err)
+	}
+}
+
+func TestURLSchemaValidateSingleConstraint(t *testing.T) {
+	const rawURL = "https://www.example.com/a/b"
+	tests := []struct {
+		schema URLSchema
+		valid  bool
+	}{
+		{URLSchema{}, true},
+		{URLSchema{Scheme: "HTTPS"}, true},
+		{URLSchema{Scheme: "ftp"}, false},
+		{URLSchema{HostSuffix: ".example.com"}, true},
+		{URLSchema{HostSuffix: "www.example.com"}, true},
+		{URLSchema{HostSuffix: "example.org"}, false},
+		{URLSchema{PathSegments: 2}, true},
+		{URLSchema{PathSegments: 3}, false},
+		{URLSchema{SegmentPatterns: map[int]*regexp.Regexp{1: regexp.MustCompile(`^b$`)}}, true},
+		{URLSchema{SegmentPatterns: map[int]*regexp.Regexp{0: regexp.MustCompile(`^b$`)}}, false},
+		{URLSchema{SegmentPatterns: map[int]*regexp.Regexp{2: regexp.MustCompile(`.*`)}}, false},
+	}
+
+	for _, test := range tests {
+		if err := urlSchemaValidate(rawURL, &test.schema); (err == nil) != test.valid {
+			t.Errorf("❌ Expected valid=%v with %+v, got: %v", test.valid, test.schema, err)
+		}
+	}
+}
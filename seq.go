@@ -0,0 +1,31 @@
+package main
+
+import (
+	"crypto/sha256"
+	"iter"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Iterate over SHA-256 hashes of normalized URLs, in the order of urls:
+//
+//	for digest, err := range hashURLs(slices.Values(urls), defaultNormalizationFlags) {
+//
+// Evaluation is lazy: each URL is normalized and hashed only when the caller
+// asks for the next hash, and urls is only iterated as far as the caller
+// does. URLs that fail to normalize are yielded with a zero hash and their
+// error.
+func hashURLs(urls iter.Seq[string], flags purell.NormalizationFlags) iter.Seq2[[sha256.Size]byte, error] {
+	return func(yield func([sha256.Size]byte, error) bool) {
+		for rawURL := range urls {
+			var digest [sha256.Size]byte
+			normalized, err := normalizeURLWithFlags(rawURL, flags)
+			if err == nil {
+				digest = sha256.Sum256([]byte(normalized))
+			}
+			if !yield(digest, err) {
+				return
+			}
+		}
+	}
+}
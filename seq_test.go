@@ -0,0 +1,62 @@
+package main
+
+import (
+	"crypto/sha256"
+	"slices"
+	"testing"
+)
+
+func TestHashURLs(t *testing.T) {
+	urls := []string{
+		"https://www.iltalehti.fi/kotimaa/a/7d3c5ba2-66bd-473e-9c0b-fc3ec26abe80",
+		"://invalid",
+		"HTTPS://Example.com/b?z=1&a=2",
+	}
+
+	i := 0
+	for digest, err := range hashURLs(slices.Values(urls), defaultNormalizationFlags) {
+		normalized, normalizeErr := normalizeURLWithFlags(urls[i], defaultNormalizationFlags)
+		if (err != nil) != (normalizeErr != nil) {
+			t.Errorf("❌ Expected error %v for %q, got: %v", normalizeErr, urls[i], err)
+		}
+		if err == nil && digest != sha256.Sum256([]byte(normalized)) {
+			t.Errorf("❌ Expected hash of %q, got %x", normalized, digest)
+		}
+		if err == nil && i == 0 && generateSignature(normalized) != "7e530349c32069a7dc25485ee2886f8f88e4b8560202fec1cb3200bd8c550b4c" {
+			t.Errorf("❌ Expected known signature for %q", urls[i])
+		}
+		i++
+	}
+	if i != len(urls) {
+		t.Errorf("❌ Expected %d hashes, got %d", len(urls), i)
+	}
+}
+
+func TestHashURLsLazy(t *testing.T) {
+	const limit = 2
+	requested := 0
+	// Endless sequence failing if asked for more URLs than consumed
+	urls := func(yield func(string) bool) {
+		for {
+			if requested++; requested > limit {
+				t.Fatalf("❌ Expected at most %d URLs to be requested", limit)
+			}
+			if !yield("https://example.com/") {
+				return
+			}
+		}
+	}
+
+	hashed := 0
+	for _, err := range hashURLs(urls, defaultNormalizationFlags) {
+		if err != nil {
+			t.Fatalf("❌ Unexpected error: %v", err)
+		}
+		if hashed++; hashed == limit {
+			break
+		}
+	}
+	if hashed != limit {
+		t.Errorf("❌ Expected %d hashes, got %d", limit, hashed)
+	}
+}
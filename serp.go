@@ -0,0 +1,62 @@
+package main
+
+import (
+	"encoding/base64"
+	"net/url"
+	"strings"
+)
+
+// Maximum number of nested search engine redirects unwrapped
+const maxSERPRedirects = 5
+
+// Get target of a search engine redirect URL, or false if URL is not one:
+//
+//   - Google: "https://www.google.com/url?q=https://example.com/", also
+//     with the "url" parameter
+//   - Bing: "https://www.bing.com/ck/a?u=a1aHR0cHM6Ly9leGFtcGxlLmNvbS8",
+//     where the target is Base64 encoded after the "a1" prefix
+//   - DuckDuckGo: "https://duckduckgo.com/l/?uddg=https%3A%2F%2Fexample.com%2F"
+func serpRedirectTarget(u *url.URL) (string, bool) {
+	host := strings.TrimPrefix(strings.ToLower(u.Hostname()), "www.")
+	query := u.Query()
+	var target string
+	switch {
+	case isGoogleHost(host) && u.Path == "/url":
+		target = query.Get("q")
+		if target == "" {
+			target = query.Get("url")
+		}
+	case host == "bing.com" && u.Path == "/ck/a":
+		encoded, ok := strings.CutPrefix(query.Get("u"), "a1")
+		if !ok {
+			return "", false
+		}
+		decoded, err := base64.RawURLEncoding.DecodeString(strings.TrimRight(encoded, "="))
+		if err != nil {
+			return "", false
+		}
+		target = string(decoded)
+	case host == "duckduckgo.com" && strings.TrimSuffix(u.Path, "/") == "/l":
+		target = query.Get("uddg")
+	}
+	return target, urlIsAbsolute(target)
+}
+
+// Extract and normalize the target of search engine redirects, such as
+// Google's "https://www.google.com/url?q=https://example.com/", see
+// serpRedirectTarget. Nested redirects are unwrapped too. Other URLs are
+// normalized as-is.
+func urlNormalizeSearchEngineResultPage(rawURL string) (string, error) {
+	for range maxSERPRedirects {
+		u, err := parseURL(rawURL)
+		if err != nil {
+			return "", err
+		}
+		target, ok := serpRedirectTarget(u)
+		if !ok {
+			break
+		}
+		rawURL = target
+	}
+	return normalizeURL(rawURL)
+}
@@ -0,0 +1,48 @@
+package main
+
+import (
+	"encoding/base64"
+	"net/url"
+	"testing"
+)
+
+func TestURLNormalizeSearchEngineResultPage(t *testing.T) {
+	const target = "https://example.com/a?x=1&y=2"
+	bing := "https://www.bing.com/ck/a?!&&p=abc&u=a1" + base64.RawURLEncoding.EncodeToString([]byte(target)) + "&ntb=1"
+
+	tests := []struct {
+		url      string
+		expected string
+	}{
+		// Google
+		{"https://www.google.com/url?q=https://example.com/&sa=D&ust=1", "https://example.com/"},
+		{"https://www.google.fi/url?sa=t&url=" + url.QueryEscape(target) + "&usg=abc", target},
+		{"https://www.google.com.au/url?q=" + url.QueryEscape(target), target},
+		{"https://www.google.co.uk/url?q=" + url.QueryEscape(target), target},
+		{"https://google.com.br/url?q=" + url.QueryEscape(target), target},
+		// Bing
+		{bing, target},
+		// DuckDuckGo
+		{"https://duckduckgo.com/l/?uddg=" + url.QueryEscape(target) + "&rut=abc", target},
+		// URL-encoded target, normalized after extracting
+		{"https://www.google.com/url?q=HTTPS%3A%2F%2FExample.com%3A443%2Fa%3Fy%3D2%26x%3D1", target},
+		// Nested redirects
+		{"https://www.google.com/url?q=" + url.QueryEscape("https://duckduckgo.com/l/?uddg="+url.QueryEscape(target)), target},
+		// Not redirects
+		{"https://www.google.com/search?q=https://example.com/", "https://www.google.com/search?q=https%3A%2F%2Fexample.com%2F"},
+		{"https://www.google.com/url?q=not+a+url", "https://www.google.com/url?q=not+a+url"},
+		{"https://www.bing.com/ck/a?u=notbase64!", "https://www.bing.com/ck/a?u=notbase64%21"},
+		{"HTTPS://Example.com/url?q=https://other.example/", "https://example.com/url?q=https%3A%2F%2Fother.example%2F"},
+		{"https://google.example.com/url?q=https://other.example/", "https://google.example.com/url?q=https%3A%2F%2Fother.example%2F"},
+	}
+
+	for _, test := range tests {
+		if got, err := urlNormalizeSearchEngineResultPage(test.url); err != nil || got != test.expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", test.expected, test.url, got, err)
+		}
+	}
+
+	if _, err := urlNormalizeSearchEngineResultPage("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,49 @@
+package main
+
+import (
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Quote string for POSIX shells in single quotes, where nothing is special
+// except the single quote itself, written as '\''.
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
+
+// Normalize URL for a shell command, without credentials unless included
+func normalizeURLForCommand(rawURL string, flags purell.NormalizationFlags, includeCredentials bool) (string, error) {
+	u, err := parseNormalizedURL(rawURL, flags)
+	if err != nil {
+		return "", err
+	}
+	if !includeCredentials {
+		u.User = nil
+	}
+	return shellQuote(u.String()), nil
+}
+
+// Normalize URL and format a curl command fetching it, following redirects,
+// for debugging tools and documentation:
+//
+//	urlToCurlCommand("https://example.com/path?q=hello", defaultNormalizationFlags, false) // "curl -L 'https://example.com/path?q=hello'"
+//
+// Credentials are omitted unless includeCredentials is set, so commands can
+// be shared without leaking them.
+func urlToCurlCommand(rawURL string, flags purell.NormalizationFlags, includeCredentials bool) (string, error) {
+	quoted, err := normalizeURLForCommand(rawURL, flags, includeCredentials)
+	if err != nil {
+		return "", err
+	}
+	return "curl -L " + quoted, nil
+}
+
+// Normalize URL and format a wget command fetching it, see urlToCurlCommand.
+func urlToWgetCommand(rawURL string, flags purell.NormalizationFlags, includeCredentials bool) (string, error) {
+	quoted, err := normalizeURLForCommand(rawURL, flags, includeCredentials)
+	if err != nil {
+		return "", err
+	}
+	return "wget " + quoted, nil
+}
@@ -0,0 +1,70 @@
+package main
+
+import (
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestURLSign(t *testing.T) {
+	secret := []byte("secret")
+	const expiresAt = 1700000000
+
+	signed, err := urlSignature("HTTPS://Example.com/files/../report.pdf?b=2&a=1", secret, expiresAt)
+	if err != nil {
+		t.Fatalf("❌ Failed to sign URL: %v", err)
+	}
+	prefix := "https://example.com/report.pdf?a=1&b=2&signature="
+	if !strings.HasPrefix(signed, prefix) || !strings.HasSuffix(signed, "&expires=1700000000") || len(signed) != len(prefix)+64+len("&expires=1700000000") {
+		t.Errorf("❌ Unexpected signed URL %q", signed)
+	}
+
+	tests := []struct {
+		name     string
+		url      string
+		secret   string
+		now      uint64
+		expected SignatureStatus
+	}{
+		{"valid", signed, "secret", expiresAt - 1, SignatureValid},
+		{"expired", signed, "secret", expiresAt, SignatureExpired},
+		{"wrong secret", signed, "other", expiresAt - 1, SignatureInvalid},
+		{"tampered path", strings.Replace(signed, "report", "salary", 1), "secret", expiresAt - 1, SignatureInvalid},
+		{"tampered query", strings.Replace(signed, "a=1", "a=2", 1), "secret", expiresAt - 1, SignatureInvalid},
+		{"tampered expiry", strings.Replace(signed, "expires=1700000000", "expires=1800000000", 1), "secret", expiresAt - 1, SignatureInvalid},
+		{"equivalent form", strings.Replace(signed, "https://example.com/", "HTTPS://EXAMPLE.COM:443/", 1), "secret", expiresAt - 1, SignatureValid},
+		{"unsigned", "https://example.com/report.pdf?a=1&b=2", "secret", expiresAt - 1, SignatureInvalid},
+		{"duplicate signature", signed + "&signature=00", "secret", expiresAt - 1, SignatureInvalid},
+		{"malformed expiry", "https://example.com/?signature=00&expires=soon", "secret", expiresAt - 1, SignatureInvalid},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			if got, err := urlVerifySignature(test.url, []byte(test.secret), test.now); err != nil || got != test.expected {
+				t.Errorf("❌ Expected %v, got %v (error: %v)", test.expected, got, err)
+			}
+		})
+	}
+
+	// URL without query keeps its form
+	signed, err = urlSignature("https://example.com/report.pdf", secret, expiresAt)
+	if err != nil || !strings.HasPrefix(signed, "https://example.com/report.pdf?signature=") {
+		t.Errorf("❌ Unexpected signed URL %q (error: %v)", signed, err)
+	}
+	if got, err := urlVerifySignature(signed, secret, 0); err != nil || got != SignatureValid {
+		t.Errorf("❌ Expected valid signature, got %v (error: %v)", got, err)
+	}
+
+	if _, err := urlSignature(signed, secret, expiresAt); !errors.Is(err, errURLAlreadySigned) {
+		t.Errorf("❌ Expected errURLAlreadySigned, got: %v", err)
+	}
+	if _, err := urlSignature("https://example.com/", nil, expiresAt); !errors.Is(err, errEmptyHMACKey) {
+		t.Errorf("❌ Expected errEmptyHMACKey, got: %v", err)
+	}
+	if _, err := urlSignature("://invalid", secret, expiresAt); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+	if _, err := urlVerifySignature("://invalid", secret, 0); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
@@ -0,0 +1,111 @@
+package main
+
+import (
+	"encoding/xml"
+	"errors"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// How often the page at a sitemap URL is likely to change
+type ChangeFreq int
+
+const (
+	ChangeFreqAlways ChangeFreq = iota
+	ChangeFreqHourly
+	ChangeFreqDaily
+	ChangeFreqWeekly
+	ChangeFreqMonthly
+	ChangeFreqYearly
+	ChangeFreqNever
+)
+
+// Value of <changefreq> in sitemaps
+func (f ChangeFreq) String() string {
+	switch f {
+	case ChangeFreqAlways:
+		return "always"
+	case ChangeFreqHourly:
+		return "hourly"
+	case ChangeFreqDaily:
+		return "daily"
+	case ChangeFreqWeekly:
+		return "weekly"
+	case ChangeFreqMonthly:
+		return "monthly"
+	case ChangeFreqYearly:
+		return "yearly"
+	case ChangeFreqNever:
+		return "never"
+	}
+	return fmt.Sprintf("ChangeFreq(%d)", int(f))
+}
+
+var errSitemapPriority = errors.New("sitemap priority must be between 0.0 and 1.0")
+
+// SitemapEntry is a page listed in a sitemap, see urlGenerateSitemap.
+type SitemapEntry struct {
+	URL        string
+	Priority   float32 // Between 0.0 and 1.0, relative to other pages of the site
+	ChangeFreq ChangeFreq
+}
+
+// Format priority with as many decimals as it needs, but at least one, e.g.
+// "0.25" and "1.0"
+func formatSitemapPriority(priority float32) string {
+	formatted := strconv.FormatFloat(float64(priority), 'f', -1, 32)
+	if !strings.Contains(formatted, ".") {
+		formatted += ".0"
+	}
+	return formatted
+}
+
+// Namespace of the sitemap protocol
+const sitemapNamespace = "http://www.sitemaps.org/schemas/sitemap/0.9"
+
+// Normalize URL with normalizeSitemapURL, and format it as a <url> element of
+// a sitemap:
+//
+//	urlGenerateSitemapEntry("https://example.com/?b=2&a=1", 0.8, ChangeFreqDaily)
+//	// "<url><loc>https://example.com/?a=1&amp;b=2</loc><priority>0.8</priority><changefreq>daily</changefreq></url>"
+func urlGenerateSitemapEntry(rawURL string, priority float32, changeFreq ChangeFreq) (string, error) {
+	if !(priority >= 0 && priority <= 1) { // Also rejects NaN
+		return "", newURLParseError(rawURL, errSitemapPriority)
+	}
+	if changeFreq < ChangeFreqAlways || changeFreq > ChangeFreqNever {
+		return "", newURLParseError(rawURL, fmt.Errorf("unknown sitemap change frequency %d", int(changeFreq)))
+	}
+	normalized, err := normalizeSitemapURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+
+	var b strings.Builder
+	b.WriteString("<url><loc>")
+	// Writing to strings.Builder doesn't fail
+	_ = xml.EscapeText(&b, []byte(normalized))
+	b.WriteString("</loc><priority>")
+	b.WriteString(formatSitemapPriority(priority))
+	b.WriteString("</priority><changefreq>")
+	b.WriteString(changeFreq.String())
+	b.WriteString("</changefreq></url>")
+	return b.String(), nil
+}
+
+// Generate a sitemap XML document listing entries, one <url> element per
+// line. Fails on the first invalid entry.
+func urlGenerateSitemap(entries []SitemapEntry) (string, error) {
+	var b strings.Builder
+	b.WriteString(xml.Header)
+	b.WriteString(`<urlset xmlns="` + sitemapNamespace + `">` + "\n")
+	for _, entry := range entries {
+		element, err := urlGenerateSitemapEntry(entry.URL, entry.Priority, entry.ChangeFreq)
+		if err != nil {
+			return "", err
+		}
+		b.WriteString(element + "\n")
+	}
+	b.WriteString("</urlset>\n")
+	return b.String(), nil
+}
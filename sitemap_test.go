@@ -0,0 +1,80 @@
+package main
+
+import (
+	"encoding/xml"
+	"errors"
+	"math"
+	"testing"
+)
+
+// Sitemap structure for parsing generated XML
+type testSitemap struct {
+	XMLName xml.Name `xml:"http://www.sitemaps.org/schemas/sitemap/0.9 urlset"`
+	URLs    []struct {
+		Loc        string `xml:"loc"`
+		Priority   string `xml:"priority"`
+		ChangeFreq string `xml:"changefreq"`
+	} `xml:"url"`
+}
+
+func TestURLGenerateSitemapEntry(t *testing.T) {
+	got, err := urlGenerateSitemapEntry("HTTPS://Example.com?b=2&a=1#top", 0.8, ChangeFreqDaily)
+	expected := "<url><loc>https://example.com/?a=1&amp;b=2</loc><priority>0.8</priority><changefreq>daily</changefreq></url>"
+	if err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	// Priorities keep all their decimals rather than being rounded to one
+	got, err = urlGenerateSitemapEntry("https://example.com/", 0.25, ChangeFreqWeekly)
+	expected = "<url><loc>https://example.com/</loc><priority>0.25</priority><changefreq>weekly</changefreq></url>"
+	if err != nil || got != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, got, err)
+	}
+
+	for _, priority := range []float32{-0.1, 1.5, float32(math.NaN())} {
+		if _, err := urlGenerateSitemapEntry("https://example.com/", priority, ChangeFreqDaily); !errors.Is(err, errSitemapPriority) {
+			t.Errorf("❌ Expected errSitemapPriority for %v, got: %v", priority, err)
+		}
+	}
+	if _, err := urlGenerateSitemapEntry("https://example.com/", 0.5, ChangeFreq(100)); err == nil {
+		t.Error("❌ Expected error for unknown change frequency")
+	}
+	if _, err := urlGenerateSitemapEntry("/relative", 0.5, ChangeFreqDaily); !errors.Is(err, errSitemapRelativeURL) {
+		t.Errorf("❌ Expected errSitemapRelativeURL, got: %v", err)
+	}
+}
+
+func TestURLGenerateSitemap(t *testing.T) {
+	entries := []SitemapEntry{
+		{"https://example.com/", 1.0, ChangeFreqAlways},
+		{"https://example.com/search?q=a&page=2", 0.5, ChangeFreqHourly},
+		{"https://example.com/about", 0, ChangeFreqNever},
+	}
+	sitemap, err := urlGenerateSitemap(entries)
+	if err != nil {
+		t.Fatalf("❌ Failed to generate sitemap: %v", err)
+	}
+
+	var parsed testSitemap
+	if err := xml.Unmarshal([]byte(sitemap), &parsed); err != nil {
+		t.Fatalf("❌ Generated sitemap is not well-formed: %v\n%s", err, sitemap)
+	}
+	expected := [][3]string{
+		{"https://example.com/", "1.0", "always"},
+		{"https://example.com/search?page=2&q=a", "0.5", "hourly"},
+		{"https://example.com/about", "0.0", "never"},
+	}
+	if len(parsed.URLs) != len(expected) {
+		t.Fatalf("❌ Expected %d URLs, got %d", len(expected), len(parsed.URLs))
+	}
+	for i, u := range parsed.URLs {
+		if got := [3]string{u.Loc, u.Priority, u.ChangeFreq}; got != expected[i] {
+			t.Errorf("❌ Expected %v for entry %d, got %v", expected[i], i, got)
+		}
+	}
+
+	entries = append(entries, SitemapEntry{"javascript:alert(1)", 0.5, ChangeFreqDaily})
+	if _, err := urlGenerateSitemap(entries); err == nil {
+		t.Error("❌ Expected error for invalid entry")
+	}
+}
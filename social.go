@@ -0,0 +1,168 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"regexp"
+	"slices"
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Social media platform with its own canonical URL form, see
+// urlNormalizeSocialMedia
+type SocialPlatform int
+
+const (
+	PlatformTwitter SocialPlatform = iota
+	PlatformFacebook
+	PlatformLinkedIn
+	PlatformYouTube
+	PlatformInstagram
+)
+
+func (p SocialPlatform) String() string {
+	switch p {
+	case PlatformTwitter:
+		return "twitter"
+	case PlatformFacebook:
+		return "facebook"
+	case PlatformLinkedIn:
+		return "linkedin"
+	case PlatformYouTube:
+		return "youtube"
+	case PlatformInstagram:
+		return "instagram"
+	}
+	return fmt.Sprintf("SocialPlatform(%d)", int(p))
+}
+
+var errNotPlatformURL = errors.New("URL doesn't belong to the platform")
+
+// Domains of each platform, including aliases and short link domains, with
+// the canonical host and query parameters identifying content
+var socialPlatforms = map[SocialPlatform]struct {
+	domains       []string
+	host          string
+	keepParams    []string
+	trailingSlash TrailingSlashPolicy
+}{
+	PlatformTwitter:   {[]string{"twitter.com", "x.com"}, "x.com", nil, TrailingSlashAlwaysRemove},
+	PlatformFacebook:  {[]string{"facebook.com", "fb.com"}, "www.facebook.com", []string{"id", "story_fbid", "v"}, TrailingSlashAlwaysRemove},
+	PlatformLinkedIn:  {[]string{"linkedin.com"}, "www.linkedin.com", nil, TrailingSlashAlwaysRemove},
+	PlatformYouTube:   {[]string{"youtube.com", "youtu.be"}, "www.youtube.com", []string{"v"}, TrailingSlashAlwaysRemove},
+	PlatformInstagram: {[]string{"instagram.com"}, "www.instagram.com", nil, TrailingSlashAlwaysAdd},
+}
+
+// Path prefixes of YouTube videos having their ID in the path
+var youTubeVideoPathPrefixes = []string{"/shorts/", "/embed/", "/live/", "/v/"}
+
+// Check if lowercase host belongs to the platform, as one of its domains or a
+// subdomain of them
+func isPlatformHost(host string, platform SocialPlatform) bool {
+	return slices.ContainsFunc(socialPlatforms[platform].domains, func(domain string) bool {
+		return host == domain || strings.HasSuffix(host, "."+domain)
+	})
+}
+
+// Get ID of YouTube video from the path of short links, shorts, embeds and
+// other URLs having it in the path, or an empty string
+func youTubePathVideoID(host, path string) string {
+	videoID := ""
+	if host == "youtu.be" {
+		videoID = strings.TrimPrefix(path, "/")
+	}
+	for _, prefix := range youTubeVideoPathPrefixes {
+		if id, ok := strings.CutPrefix(path, prefix); ok {
+			videoID = id
+		}
+	}
+	videoID, _, _ = strings.Cut(videoID, "/")
+	return videoID
+}
+
+// Normalize URL of a social media platform into its canonical form:
+//
+//   - https scheme, and one host for all aliases, e.g. "x.com" for
+//     "twitter.com" and "mobile.twitter.com"
+//   - query parameters other than ones identifying content, such as "v" of
+//     YouTube videos, fragment and credentials are removed
+//   - trailing slash of the path is removed, except on Instagram, which
+//     uses it in all of its URLs
+//   - YouTube short links, shorts and embeds point to "/watch?v=ID"
+//
+// URLs of other sites fail with errNotPlatformURL.
+func urlNormalizeSocialMedia(rawURL string, platform SocialPlatform) (string, error) {
+	rules, ok := socialPlatforms[platform]
+	if !ok {
+		return "", fmt.Errorf("unknown social media platform: %v", platform)
+	}
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags|purell.FlagRemoveFragment)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+	if !isPlatformHost(host, platform) {
+		return "", newURLParseError(rawURL, errNotPlatformURL)
+	}
+
+	if platform == PlatformYouTube {
+		if videoID := youTubePathVideoID(host, u.Path); videoID != "" {
+			u.Path = "/watch"
+			u.RawQuery = mapToQueryString([][2]string{{"v", videoID}})
+		}
+	}
+
+	pairs := slices.DeleteFunc(queryStringToPairs(u.RawQuery), func(kv [2]string) bool {
+		return !slices.Contains(rules.keepParams, kv[0])
+	})
+	u.RawQuery = mapToQueryString(pairs)
+	u.ForceQuery = false
+	u.Scheme = "https"
+	u.Host = rules.host
+	u.User = nil
+	u.RawPath = ""
+	applyTrailingSlashPolicy(u, rules.trailingSlash)
+	return u.String(), nil
+}
+
+// YouTube video IDs are 11 characters of URL-safe Base64
+var youTubeVideoIDPattern = regexp.MustCompile(`^[A-Za-z0-9_-]{11}$`)
+
+var errInvalidYouTubeVideoID = errors.New("missing or invalid YouTube video ID")
+
+// Normalize any form of YouTube video URL into the canonical watch URL,
+// "https://www.youtube.com/watch?v=ID": short links of youtu.be, watch URLs
+// of m.youtube.com and other subdomains, embeds, shorts and "/v/" URLs.
+//
+// Unlike urlNormalizeSocialMedia, the "list" parameter of playlists is kept,
+// so videos played as part of a playlist keep pointing to it. Other
+// parameters, such as "autoplay" and "t", are removed. URLs of other sites
+// fail with errNotPlatformURL, and YouTube URLs without a well-formed video
+// ID with errInvalidYouTubeVideoID.
+func urlNormalizeYouTube(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	host := strings.ToLower(u.Hostname())
+	if !isPlatformHost(host, PlatformYouTube) {
+		return "", newURLParseError(rawURL, errNotPlatformURL)
+	}
+
+	query := u.Query()
+	videoID := youTubePathVideoID(host, u.Path)
+	if videoID == "" && strings.TrimSuffix(u.Path, "/") == "/watch" {
+		videoID = query.Get("v")
+	}
+	if !youTubeVideoIDPattern.MatchString(videoID) {
+		return "", newURLParseError(rawURL, errInvalidYouTubeVideoID)
+	}
+
+	params := [][2]string{{"v", videoID}}
+	if list := query.Get("list"); list != "" {
+		params = append(params, [2]string{"list", list})
+	}
+	return "https://" + socialPlatforms[PlatformYouTube].host + "/watch?" + mapToQueryString(params), nil
+}
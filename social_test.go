@@ -0,0 +1,91 @@
+package main
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestURLNormalizeSocialMedia(t *testing.T) {
+	tests := []struct {
+		platform SocialPlatform
+		url      string
+		expected string
+	}{
+		{PlatformTwitter, "https://twitter.com/suola/status/123?s=20&t=abc", "https://x.com/suola/status/123"},
+		{PlatformTwitter, "https://mobile.twitter.com/suola/", "https://x.com/suola"},
+		{PlatformTwitter, "http://www.x.com/suola/status/123#reply", "https://x.com/suola/status/123"},
+		{PlatformFacebook, "https://m.facebook.com/suola/posts/123?fbclid=IwAR0", "https://www.facebook.com/suola/posts/123"},
+		{PlatformFacebook, "https://fb.com/permalink.php?story_fbid=123&id=456&mibextid=x", "https://www.facebook.com/permalink.php?id=456&story_fbid=123"},
+		{PlatformFacebook, "https://www.facebook.com/watch/?v=789&ref=sharing", "https://www.facebook.com/watch?v=789"},
+		{PlatformLinkedIn, "https://fi.linkedin.com/in/suola/?originalSubdomain=fi", "https://www.linkedin.com/in/suola"},
+		{PlatformLinkedIn, "https://linkedin.com/company/klikkikuri", "https://www.linkedin.com/company/klikkikuri"},
+		{PlatformYouTube, "https://youtu.be/dQw4w9WgXcQ?si=abc", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"},
+		{PlatformYouTube, "https://m.youtube.com/watch?v=dQw4w9WgXcQ&feature=share", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"},
+		{PlatformYouTube, "https://www.youtube.com/shorts/dQw4w9WgXcQ", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"},
+		{PlatformYouTube, "https://youtube.com/embed/dQw4w9WgXcQ?autoplay=1", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"},
+		{PlatformYouTube, "https://www.youtube.com/@klikkikuri/videos", "https://www.youtube.com/@klikkikuri/videos"},
+		{PlatformInstagram, "https://instagram.com/p/C0abc123?igshid=xyz", "https://www.instagram.com/p/C0abc123/"},
+		{PlatformInstagram, "https://www.instagram.com/suola/", "https://www.instagram.com/suola/"},
+	}
+
+	for _, test := range tests {
+		got, err := urlNormalizeSocialMedia(test.url, test.platform)
+		if err != nil || got != test.expected {
+			t.Errorf("❌ Expected %q for %v URL %q, got %q (error: %v)", test.expected, test.platform, test.url, got, err)
+		}
+		// Canonical form is stable
+		if again, err := urlNormalizeSocialMedia(got, test.platform); err != nil || again != got {
+			t.Errorf("❌ Expected %q to stay canonical, got %q (error: %v)", got, again, err)
+		}
+	}
+
+	for _, input := range []string{"https://example.com/suola", "https://nottwitter.com/suola", "https://x.com.example/suola"} {
+		if _, err := urlNormalizeSocialMedia(input, PlatformTwitter); !errors.Is(err, errNotPlatformURL) {
+			t.Errorf("❌ Expected errNotPlatformURL for %q, got: %v", input, err)
+		}
+	}
+	if _, err := urlNormalizeSocialMedia("://invalid", PlatformYouTube); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestURLNormalizeYouTube(t *testing.T) {
+	const canonical = "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+	tests := map[string]string{
+		"https://youtu.be/dQw4w9WgXcQ":                                   canonical,
+		"https://youtu.be/dQw4w9WgXcQ?si=abc&t=42":                       canonical,
+		"https://www.youtube.com/watch?v=dQw4w9WgXcQ":                    canonical,
+		"http://YouTube.com/watch/?feature=share&v=dQw4w9WgXcQ":          canonical,
+		"https://m.youtube.com/watch?v=dQw4w9WgXcQ#t=10":                 canonical,
+		"https://www.youtube.com/v/dQw4w9WgXcQ?version=3":                canonical,
+		"https://www.youtube.com/embed/dQw4w9WgXcQ?autoplay=1&mute=1":    canonical,
+		"https://www.youtube.com/shorts/dQw4w9WgXcQ":                     canonical,
+		"https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PL123&index=2": canonical + "&list=PL123",
+		"https://youtu.be/dQw4w9WgXcQ?list=PL123&autoplay=1":             canonical + "&list=PL123",
+		"https://www.youtube.com/embed/dQw4w9WgXcQ/extra":                canonical,
+	}
+
+	for input, expected := range tests {
+		if got, err := urlNormalizeYouTube(input); err != nil || got != expected {
+			t.Errorf("❌ Expected %q for %q, got %q (error: %v)", expected, input, got, err)
+		}
+	}
+
+	for _, input := range []string{
+		"https://www.youtube.com/watch?v=short",
+		"https://www.youtube.com/watch?v=dQw4w9WgXcQ!",
+		"https://youtu.be/",
+		"https://www.youtube.com/@klikkikuri/videos",
+		"https://www.youtube.com/playlist?list=PL123",
+	} {
+		if _, err := urlNormalizeYouTube(input); !errors.Is(err, errInvalidYouTubeVideoID) {
+			t.Errorf("❌ Expected errInvalidYouTubeVideoID for %q, got: %v", input, err)
+		}
+	}
+	if _, err := urlNormalizeYouTube("https://vimeo.com/123"); !errors.Is(err, errNotPlatformURL) {
+		t.Errorf("❌ Expected errNotPlatformURL, got: %v", err)
+	}
+	if _, err := urlNormalizeYouTube("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
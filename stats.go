@@ -0,0 +1,72 @@
+package main
+
+import (
+	"cmp"
+	"slices"
+	"strings"
+)
+
+// Number of hosts in URLStats.TopHosts
+const urlStatsTopHosts = 10
+
+// HostCount is the number of URLs with a host.
+type HostCount struct {
+	Host  string
+	Count int
+}
+
+// URLStats summarizes a URL corpus, see computeURLStats.
+type URLStats struct {
+	Total          int            // Number of URLs, including failed ones
+	Unique         int            // Number of distinct normalized URLs
+	Schemes        map[string]int // Number of URLs by scheme
+	AvgQueryParams float64        // Average number of query parameters
+	TopHosts       []HostCount    // Most frequent hosts, by count and then host
+	ParseErrors    int            // Number of URLs that failed to normalize
+}
+
+// Compute summary of URLs in a single pass. URLs are normalized with the
+// default flags, and the ones failing are only counted in Total and
+// ParseErrors.
+func computeURLStats(urls []string) URLStats {
+	stats := URLStats{Total: len(urls), Schemes: make(map[string]int)}
+	unique := make(map[string]struct{})
+	hosts := make(map[string]int)
+	queryParams := 0
+
+	for _, rawURL := range urls {
+		normalized, err := normalizeURL(rawURL)
+		if err != nil {
+			stats.ParseErrors++
+			continue
+		}
+		u, err := parseURL(normalized)
+		if err != nil {
+			stats.ParseErrors++
+			continue
+		}
+		unique[normalized] = struct{}{}
+		stats.Schemes[u.Scheme]++
+		if u.Host != "" {
+			hosts[u.Host]++
+		}
+		if u.RawQuery != "" {
+			queryParams += strings.Count(u.RawQuery, "&") + 1
+		}
+	}
+
+	stats.Unique = len(unique)
+	if parsed := stats.Total - stats.ParseErrors; parsed > 0 {
+		stats.AvgQueryParams = float64(queryParams) / float64(parsed)
+	}
+	for host, count := range hosts {
+		stats.TopHosts = append(stats.TopHosts, HostCount{host, count})
+	}
+	slices.SortFunc(stats.TopHosts, func(a, b HostCount) int {
+		return cmp.Or(cmp.Compare(b.Count, a.Count), strings.Compare(a.Host, b.Host))
+	})
+	if len(stats.TopHosts) > urlStatsTopHosts {
+		stats.TopHosts = stats.TopHosts[:urlStatsTopHosts]
+	}
+	return stats
+}
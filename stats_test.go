@@ -0,0 +1,67 @@
+package main
+
+import (
+	"fmt"
+	"maps"
+	"slices"
+	"testing"
+)
+
+func TestComputeURLStats(t *testing.T) {
+	urls := []string{
+		"https://example.com/a?x=1&y=2",
+		"HTTPS://Example.com:443/a?y=2&x=1",
+		"https://example.com/b",
+		"http://example.org/?q=1",
+		"https://www.example.net/",
+		"ftp://files.example.org/file.txt",
+		"://invalid",
+		"https://example.org/c?a=1&b=2&c=3",
+	}
+	stats := computeURLStats(urls)
+
+	if stats.Total != 8 || stats.Unique != 6 || stats.ParseErrors != 1 {
+		t.Errorf("❌ Expected 8 total, 6 unique and 1 error, got %+v", stats)
+	}
+	expectedSchemes := map[string]int{"https": 5, "http": 1, "ftp": 1}
+	if !maps.Equal(stats.Schemes, expectedSchemes) {
+		t.Errorf("❌ Expected schemes %v, got %v", expectedSchemes, stats.Schemes)
+	}
+	// 2 + 2 + 1 + 3 query parameters in 7 parsed URLs
+	if expected := 8.0 / 7; stats.AvgQueryParams != expected {
+		t.Errorf("❌ Expected %v query parameters on average, got %v", expected, stats.AvgQueryParams)
+	}
+	expectedHosts := []HostCount{
+		{"example.com", 3},
+		{"example.org", 2},
+		{"files.example.org", 1},
+		{"www.example.net", 1},
+	}
+	if !slices.Equal(stats.TopHosts, expectedHosts) {
+		t.Errorf("❌ Expected top hosts %v, got %v", expectedHosts, stats.TopHosts)
+	}
+}
+
+func TestComputeURLStatsTopHosts(t *testing.T) {
+	var urls []string
+	for i := range 15 {
+		for range i + 1 {
+			urls = append(urls, fmt.Sprintf("https://host%02d.example/", i))
+		}
+	}
+	stats := computeURLStats(urls)
+	if len(stats.TopHosts) != urlStatsTopHosts {
+		t.Fatalf("❌ Expected %d top hosts, got %v", urlStatsTopHosts, stats.TopHosts)
+	}
+	if first := stats.TopHosts[0]; first.Host != "host14.example" || first.Count != 15 {
+		t.Errorf("❌ Expected most frequent host first, got %v", first)
+	}
+	if last := stats.TopHosts[urlStatsTopHosts-1]; last.Host != "host05.example" || last.Count != 6 {
+		t.Errorf("❌ Expected 10th most frequent host last, got %v", last)
+	}
+
+	empty := computeURLStats(nil)
+	if empty.Total != 0 || empty.AvgQueryParams != 0 || empty.TopHosts != nil {
+		t.Errorf("❌ Expected empty stats, got %+v", empty)
+	}
+}
@@ -0,0 +1,247 @@
+package main
+
+import (
+	"fmt"
+	"net/url"
+	"strings"
+)
+
+// Types of errors returned by URLTemplate
+type TemplateErrorKind int
+
+const (
+	TemplateInvalid TemplateErrorKind = iota
+	TemplateMismatch
+	TemplateMissingVariable
+)
+
+// TemplateError is returned by URLTemplate for malformed templates, URLs
+// not matching the template, and variables missing when filling it.
+type TemplateError struct {
+	Kind   TemplateErrorKind
+	Reason string
+}
+
+func (e *TemplateError) Error() string {
+	switch e.Kind {
+	case TemplateMismatch:
+		return "URL doesn't match template: " + e.Reason
+	case TemplateMissingVariable:
+		return "missing template variable: " + e.Reason
+	}
+	return "invalid URL template: " + e.Reason
+}
+
+// Path segment or query value of a template, either literal text or a
+// variable.
+type templatePart struct {
+	literal  string
+	variable string // Variable name, empty for literals
+}
+
+type templateQueryParam struct {
+	key   string
+	value templatePart
+}
+
+// URLTemplate matches URLs against a pattern with variables, like a lite
+// version of RFC 6570 URI templates:
+//
+//	tmpl, err := parseURLTemplate("https://example.com/user/{user_id}/post/{post_id}?page={page}")
+//	vars, err := tmpl.Extract("https://example.com/user/42/post/7?page=2")
+//	// vars is {"user_id": "42", "post_id": "7", "page": "2"}
+//
+// Variables must span a whole path segment or query value. Scheme and host
+// are literal, and compared case-insensitively. Query parameters of a URL
+// not in the template are ignored.
+type URLTemplate struct {
+	scheme string
+	host   string
+	path   []templatePart
+	query  []templateQueryParam
+}
+
+// Parse template of a URL with "{name}" variables.
+func parseURLTemplate(template string) (*URLTemplate, error) {
+	u, err := url.Parse(template)
+	if err != nil {
+		return nil, &TemplateError{Kind: TemplateInvalid, Reason: err.Error()}
+	}
+	if u.Scheme == "" || u.Host == "" {
+		return nil, &TemplateError{Kind: TemplateInvalid, Reason: "template must have scheme and host"}
+	}
+	if strings.ContainsAny(u.Host, "{}") {
+		return nil, &TemplateError{Kind: TemplateInvalid, Reason: "variables are not supported in host"}
+	}
+	if u.Fragment != "" {
+		return nil, &TemplateError{Kind: TemplateInvalid, Reason: "fragments are not supported"}
+	}
+
+	tmpl := &URLTemplate{scheme: strings.ToLower(u.Scheme), host: strings.ToLower(u.Host)}
+	seen := make(map[string]bool)
+	addPart := func(s string) (templatePart, error) {
+		part, err := parseTemplatePart(s)
+		if err != nil {
+			return part, err
+		}
+		if part.variable != "" {
+			if seen[part.variable] {
+				return part, &TemplateError{Kind: TemplateInvalid, Reason: fmt.Sprintf("duplicate variable %q", part.variable)}
+			}
+			seen[part.variable] = true
+		}
+		return part, nil
+	}
+
+	// Split the path as written, and unescape segments after splitting, so
+	// escaped "/" in a literal segment doesn't split it. EscapedPath can't be
+	// used, as it re-escapes the whole path when it has "{" and "}".
+	escapedPath := u.RawPath
+	if escapedPath == "" {
+		escapedPath = u.EscapedPath()
+	}
+	for _, segment := range splitPathSegments(escapedPath) {
+		if !strings.ContainsAny(segment, "{}") {
+			if segment, err = urlDecodePathSegment(segment); err != nil {
+				return nil, &TemplateError{Kind: TemplateInvalid, Reason: err.Error()}
+			}
+		}
+		part, err := addPart(segment)
+		if err != nil {
+			return nil, err
+		}
+		tmpl.path = append(tmpl.path, part)
+	}
+	if u.RawQuery != "" {
+		for _, param := range strings.Split(u.RawQuery, "&") {
+			key, value, _ := strings.Cut(param, "=")
+			if key == "" || strings.ContainsAny(key, "{}") {
+				return nil, &TemplateError{Kind: TemplateInvalid, Reason: fmt.Sprintf("invalid query parameter %q", param)}
+			}
+			key, err := url.QueryUnescape(key)
+			if err != nil {
+				return nil, &TemplateError{Kind: TemplateInvalid, Reason: err.Error()}
+			}
+			if !strings.ContainsAny(value, "{}") {
+				if value, err = url.QueryUnescape(value); err != nil {
+					return nil, &TemplateError{Kind: TemplateInvalid, Reason: err.Error()}
+				}
+			}
+			part, err := addPart(value)
+			if err != nil {
+				return nil, err
+			}
+			tmpl.query = append(tmpl.query, templateQueryParam{key, part})
+		}
+	}
+	return tmpl, nil
+}
+
+// Parse path segment or query value as a literal, or a "{name}" variable
+func parseTemplatePart(s string) (templatePart, error) {
+	if !strings.ContainsAny(s, "{}") {
+		return templatePart{literal: s}, nil
+	}
+	name, ok := strings.CutPrefix(s, "{")
+	if ok {
+		name, ok = strings.CutSuffix(name, "}")
+	}
+	if !ok || name == "" || strings.ContainsAny(name, "{}") {
+		return templatePart{}, &TemplateError{Kind: TemplateInvalid, Reason: fmt.Sprintf("variable must span a whole path segment or query value: %q", s)}
+	}
+	return templatePart{variable: name}, nil
+}
+
+// Extract values of the template variables from URL.
+func (t *URLTemplate) Extract(rawURL string) (map[string]string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	if !strings.EqualFold(u.Scheme, t.scheme) || !strings.EqualFold(u.Host, t.host) {
+		return nil, &TemplateError{Kind: TemplateMismatch, Reason: fmt.Sprintf("expected origin %s://%s", t.scheme, t.host)}
+	}
+
+	segments := splitPathSegments(u.EscapedPath())
+	if len(segments) != len(t.path) {
+		return nil, &TemplateError{Kind: TemplateMismatch, Reason: fmt.Sprintf("expected %d path segments, got %d", len(t.path), len(segments))}
+	}
+
+	vars := make(map[string]string)
+	for i, part := range t.path {
+		segment, err := urlDecodePathSegment(segments[i])
+		if err != nil {
+			return nil, newURLParseError(rawURL, err)
+		}
+		if err := part.match(segment, vars); err != nil {
+			return nil, err
+		}
+	}
+
+	query := u.Query()
+	for _, param := range t.query {
+		if !query.Has(param.key) {
+			return nil, &TemplateError{Kind: TemplateMismatch, Reason: fmt.Sprintf("missing query parameter %q", param.key)}
+		}
+		if err := param.value.match(query.Get(param.key), vars); err != nil {
+			return nil, err
+		}
+	}
+	return vars, nil
+}
+
+// Match value against the part, storing it in vars if the part is a variable
+func (p templatePart) match(value string, vars map[string]string) error {
+	if p.variable == "" {
+		if value != p.literal {
+			return &TemplateError{Kind: TemplateMismatch, Reason: fmt.Sprintf("expected %q, got %q", p.literal, value)}
+		}
+		return nil
+	}
+	if value == "" {
+		return &TemplateError{Kind: TemplateMismatch, Reason: fmt.Sprintf("empty value for variable %q", p.variable)}
+	}
+	vars[p.variable] = value
+	return nil
+}
+
+// Fill template variables with vars, escaping the values. Variables not in
+// the template are ignored.
+func (t *URLTemplate) Fill(vars map[string]string) (string, error) {
+	value := func(p templatePart) (string, error) {
+		if p.variable == "" {
+			return p.literal, nil
+		}
+		v, ok := vars[p.variable]
+		if !ok || v == "" {
+			return "", &TemplateError{Kind: TemplateMissingVariable, Reason: p.variable}
+		}
+		return v, nil
+	}
+
+	var b strings.Builder
+	b.WriteString(t.scheme + "://" + t.host + "/")
+	for i, part := range t.path {
+		segment, err := value(part)
+		if err != nil {
+			return "", err
+		}
+		if i > 0 {
+			b.WriteByte('/')
+		}
+		b.WriteString(urlEncodePathSegment(segment))
+	}
+	for i, param := range t.query {
+		v, err := value(param.value)
+		if err != nil {
+			return "", err
+		}
+		if i == 0 {
+			b.WriteByte('?')
+		} else {
+			b.WriteByte('&')
+		}
+		b.WriteString(urlEncodeQueryValue(param.key) + "=" + urlEncodeQueryValue(v))
+	}
+	return b.String(), nil
+}
@@ -0,0 +1,137 @@
+package main
+
+import (
+	"errors"
+	"maps"
+	"strings"
+	"testing"
+)
+
+func TestURLTemplateExtract(t *testing.T) {
+	tmpl, err := parseURLTemplate("https://example.com/user/{user_id}/post/{post_id}")
+	if err != nil {
+		t.Fatalf("❌ Failed to parse template: %v", err)
+	}
+
+	tests := map[string]map[string]string{
+		"https://example.com/user/42/post/7":              {"user_id": "42", "post_id": "7"},
+		"HTTPS://Example.com/user/42/post/7":              {"user_id": "42", "post_id": "7"},
+		"https://example.com/user/a%2Fb/post/7?x=1":       {"user_id": "a/b", "post_id": "7"},
+		"https://example.com/user/p%C3%A4iv%C3%A4/post/7": {"user_id": "päivä", "post_id": "7"},
+	}
+	for input, expected := range tests {
+		vars, err := tmpl.Extract(input)
+		if err != nil || !maps.Equal(vars, expected) {
+			t.Errorf("❌ Expected %v for %q, got %v (error: %v)", expected, input, vars, err)
+		}
+	}
+
+	mismatches := []string{
+		"https://example.com/user/42/post/7/comments",
+		"https://example.com/user/42/post",
+		"https://example.com/user/42/comment/7",
+		"https://example.com/user//post/7",
+		"https://other.example/user/42/post/7",
+		"http://example.com/user/42/post/7",
+	}
+	for _, input := range mismatches {
+		var templateErr *TemplateError
+		if _, err := tmpl.Extract(input); !errors.As(err, &templateErr) || templateErr.Kind != TemplateMismatch {
+			t.Errorf("❌ Expected mismatch for %q, got: %v", input, err)
+		}
+	}
+}
+
+func TestURLTemplateEscapedLiteral(t *testing.T) {
+	// Escaped "/" is part of a literal segment, with and without variables
+	for template, input := range map[string]string{
+		"https://example.com/files/a%2Fb/{name}": "https://example.com/files/a%2Fb/report",
+		"https://example.com/files/a%2Fb":        "https://example.com/files/a%2Fb",
+	} {
+		tmpl, err := parseURLTemplate(template)
+		if err != nil {
+			t.Fatalf("❌ Failed to parse template %q: %v", template, err)
+		}
+		if _, err := tmpl.Extract(input); err != nil {
+			t.Errorf("❌ Expected %q to match %q, got: %v", input, template, err)
+		}
+		var templateErr *TemplateError
+		if _, err := tmpl.Extract(strings.Replace(input, "%2F", "/", 1)); !errors.As(err, &templateErr) || templateErr.Kind != TemplateMismatch {
+			t.Errorf("❌ Expected unescaped \"/\" not to match %q, got: %v", template, err)
+		}
+	}
+
+	tmpl, _ := parseURLTemplate("https://example.com/files/a%2Fb/{name}")
+	if filled, err := tmpl.Fill(map[string]string{"name": "report"}); err != nil || filled != "https://example.com/files/a%2Fb/report" {
+		t.Errorf("❌ Expected escaped literal to be kept when filling, got %q (error: %v)", filled, err)
+	}
+}
+
+func TestURLTemplateQuery(t *testing.T) {
+	tmpl, err := parseURLTemplate("https://example.com/search?q={query}&lang=fi&page={page}")
+	if err != nil {
+		t.Fatalf("❌ Failed to parse template: %v", err)
+	}
+
+	vars, err := tmpl.Extract("https://example.com/search?page=2&q=a+b&lang=fi&utm_source=x")
+	expected := map[string]string{"query": "a b", "page": "2"}
+	if err != nil || !maps.Equal(vars, expected) {
+		t.Errorf("❌ Expected %v, got %v (error: %v)", expected, vars, err)
+	}
+
+	for _, input := range []string{
+		"https://example.com/search?q=a&page=2",
+		"https://example.com/search?q=a&lang=sv&page=2",
+		"https://example.com/search?q=&lang=fi&page=2",
+	} {
+		var templateErr *TemplateError
+		if _, err := tmpl.Extract(input); !errors.As(err, &templateErr) || templateErr.Kind != TemplateMismatch {
+			t.Errorf("❌ Expected mismatch for %q, got: %v", input, err)
+		}
+	}
+}
+
+func TestURLTemplateFill(t *testing.T) {
+	tmpl, err := parseURLTemplate("https://example.com/user/{user_id}/post/{post_id}?page={page}")
+	if err != nil {
+		t.Fatalf("❌ Failed to parse template: %v", err)
+	}
+
+	vars := map[string]string{"user_id": "a/b c", "post_id": "7", "page": "1&2", "unused": "x"}
+	filled, err := tmpl.Fill(vars)
+	if expected := "https://example.com/user/a%2Fb%20c/post/7?page=1%262"; err != nil || filled != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, filled, err)
+	}
+
+	// Filled URL extracts back to the same variables
+	extracted, err := tmpl.Extract(filled)
+	delete(vars, "unused")
+	if err != nil || !maps.Equal(extracted, vars) {
+		t.Errorf("❌ Expected %v, got %v (error: %v)", vars, extracted, err)
+	}
+
+	var templateErr *TemplateError
+	if _, err := tmpl.Fill(map[string]string{"user_id": "42", "page": "1"}); !errors.As(err, &templateErr) || templateErr.Kind != TemplateMissingVariable || templateErr.Reason != "post_id" {
+		t.Errorf("❌ Expected missing post_id, got: %v", err)
+	}
+}
+
+func TestParseURLTemplateInvalid(t *testing.T) {
+	tests := []string{
+		"/user/{id}",
+		"https://{host}/",
+		"https://example.com/user/{}",
+		"https://example.com/user/{id",
+		"https://example.com/user/id-{id}",
+		"https://example.com/{id}/{id}",
+		"https://example.com/?{key}=1",
+		"https://example.com/{id}#{section}",
+	}
+
+	for _, template := range tests {
+		var templateErr *TemplateError
+		if _, err := parseURLTemplate(template); !errors.As(err, &templateErr) || templateErr.Kind != TemplateInvalid {
+			t.Errorf("❌ Expected invalid template error for %q, got: %v", template, err)
+		}
+	}
+}
@@ -0,0 +1,54 @@
+//go:build timeout
+// +build timeout
+
+package main
+
+import (
+	"errors"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Timeout used by normalizeURLTimeout when none is given
+const defaultNormalizeTimeout = 100 * time.Millisecond
+
+var errNormalizeTimeout = errors.New("normalization timed out")
+
+// Normalize URL, failing with errNormalizeTimeout if it takes longer than
+// timeout. Zero or negative timeout uses defaultNormalizeTimeout.
+//
+// Guards against adversarial inputs that are slow to parse. Normalization
+// can't be interrupted, so on timeout it keeps running in the background
+// until it finishes.
+func normalizeURLTimeout(rawURL string, flags purell.NormalizationFlags, timeout time.Duration) (string, error) {
+	return runWithTimeout(rawURL, timeout, func() (string, error) {
+		return normalizeURLWithFlags(rawURL, flags)
+	})
+}
+
+// Run normalize in a goroutine, and wait for its result until timeout
+func runWithTimeout(rawURL string, timeout time.Duration, normalize func() (string, error)) (string, error) {
+	if timeout <= 0 {
+		timeout = defaultNormalizeTimeout
+	}
+	type result struct {
+		normalized string
+		err        error
+	}
+	// Buffered, so that the goroutine can exit after a timeout
+	done := make(chan result, 1)
+	go func() {
+		normalized, err := normalize()
+		done <- result{normalized, err}
+	}()
+
+	timer := time.NewTimer(timeout)
+	defer timer.Stop()
+	select {
+	case r := <-done:
+		return r.normalized, r.err
+	case <-timer.C:
+		return "", newURLParseError(rawURL, errNormalizeTimeout)
+	}
+}
@@ -0,0 +1,45 @@
+//go:build timeout
+// +build timeout
+
+package main
+
+import (
+	"errors"
+	"testing"
+	"time"
+)
+
+func TestNormalizeURLTimeout(t *testing.T) {
+	start := time.Now()
+	normalized, err := normalizeURLTimeout("HTTPS://Example.com:443/a/../b?z=1&y=2", defaultNormalizationFlags, 0)
+	if expected := "https://example.com/b?y=2&z=1"; err != nil || normalized != expected {
+		t.Errorf("❌ Expected %q, got %q (error: %v)", expected, normalized, err)
+	}
+	if elapsed := time.Since(start); elapsed >= defaultNormalizeTimeout {
+		t.Errorf("❌ Expected normalization to complete within %v, took %v", defaultNormalizeTimeout, elapsed)
+	}
+
+	// Errors from normalization are returned as-is
+	var parseErr *URLParseError
+	if _, err := normalizeURLTimeout("://invalid", defaultNormalizationFlags, time.Second); !errors.As(err, &parseErr) || errors.Is(err, errNormalizeTimeout) {
+		t.Errorf("❌ Expected parse error, got: %v", err)
+	}
+}
+
+func TestRunWithTimeoutExceeded(t *testing.T) {
+	release := make(chan struct{})
+	defer close(release)
+	slow := func() (string, error) {
+		<-release
+		return "https://example.com/", nil
+	}
+
+	start := time.Now()
+	_, err := runWithTimeout("https://example.com/", 10*time.Millisecond, slow)
+	if !errors.Is(err, errNormalizeTimeout) {
+		t.Errorf("❌ Expected timeout error, got: %v", err)
+	}
+	if elapsed := time.Since(start); elapsed >= time.Second {
+		t.Errorf("❌ Expected to fail fast, took %v", elapsed)
+	}
+}
@@ -0,0 +1,45 @@
+//go:build tracing
+// +build tracing
+
+package main
+
+import (
+	"context"
+	"log/slog"
+	"time"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+func init() {
+	normalizeHooks = append(normalizeHooks, traceNormalize)
+	signatureHooks = append(signatureHooks, traceSignature)
+}
+
+// Log normalization with the default slog logger at debug level, along with
+// each step that changed the URL.
+func traceNormalize(rawURL string, flags purell.NormalizationFlags, normalized string, elapsed time.Duration, err error) {
+	logger := slog.Default()
+	ctx := context.Background()
+	if !logger.Enabled(ctx, slog.LevelDebug) {
+		return
+	}
+
+	if err != nil {
+		logger.DebugContext(ctx, "normalize url", "url", rawURL, "elapsed", elapsed, "error", err)
+		return
+	}
+	// Steps are only replayed when debug logging is enabled
+	if steps, err := normalizationSteps(rawURL, flags); err == nil {
+		for _, step := range steps {
+			if step.Before != step.After {
+				logger.DebugContext(ctx, "normalization step", "step", step.Name, "before", step.Before, "after", step.After)
+			}
+		}
+	}
+	logger.DebugContext(ctx, "normalize url", "url", rawURL, "normalized", normalized, "elapsed", elapsed)
+}
+
+func traceSignature(input, algorithm, signature string, elapsed time.Duration) {
+	slog.Debug("generate signature", "input", input, "algorithm", algorithm, "signature", signature, "elapsed", elapsed)
+}
@@ -0,0 +1,84 @@
+//go:build tracing
+// +build tracing
+
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"log/slog"
+	"strings"
+	"testing"
+)
+
+// Capture debug events from the default logger while running f
+func captureDebugEvents(t *testing.T, f func()) []map[string]any {
+	t.Helper()
+	var buf bytes.Buffer
+	previous := slog.Default()
+	slog.SetDefault(slog.New(slog.NewJSONHandler(&buf, &slog.HandlerOptions{Level: slog.LevelDebug})))
+	defer slog.SetDefault(previous)
+
+	f()
+
+	var events []map[string]any
+	for _, line := range strings.Split(strings.TrimSpace(buf.String()), "\n") {
+		var event map[string]any
+		if err := json.Unmarshal([]byte(line), &event); err != nil {
+			t.Fatalf("❌ Invalid log line %q: %v", line, err)
+		}
+		events = append(events, event)
+	}
+	return events
+}
+
+func TestTracingEvents(t *testing.T) {
+	const rawURL = "https://Example.com:443/a/../b?b=2&a=1"
+	events := captureDebugEvents(t, func() {
+		normalized, _ := normalizeURL(rawURL)
+		generateSignature(normalized)
+	})
+
+	var steps []string
+	for _, event := range events[:len(events)-2] {
+		if event["msg"] != "normalization step" {
+			t.Fatalf("❌ Expected step event, got: %v", event)
+		}
+		steps = append(steps, event["step"].(string))
+	}
+	if expected := "sort query,remove dot segments,lowercase host,remove default port"; strings.Join(steps, ",") != expected {
+		t.Errorf("❌ Expected steps %s, got %v", expected, steps)
+	}
+
+	normalize, signature := events[len(events)-2], events[len(events)-1]
+	if normalize["msg"] != "normalize url" || normalize["url"] != rawURL || normalize["normalized"] != "https://example.com/b?a=1&b=2" {
+		t.Errorf("❌ Unexpected normalize event: %v", normalize)
+	}
+	if signature["msg"] != "generate signature" || signature["input"] != "https://example.com/b?a=1&b=2" || signature["algorithm"] != "sha256" || len(signature["signature"].(string)) != 64 {
+		t.Errorf("❌ Unexpected signature event: %v", signature)
+	}
+
+	// Other algorithms and the raw hash path are traced too
+	events = captureDebugEvents(t, func() {
+		generateSignatureWith("https://example.com/", "sha512")
+		hashURLToWriter(io.Discard, rawURL, defaultNormalizationFlags)
+	})
+	var signatures []map[string]any
+	for _, event := range events {
+		if event["msg"] == "generate signature" {
+			signatures = append(signatures, event)
+		}
+	}
+	if len(signatures) != 2 || signatures[0]["algorithm"] != "sha512" || len(signatures[0]["signature"].(string)) != 128 {
+		t.Fatalf("❌ Expected sha512 and sha256 signature events, got: %v", signatures)
+	}
+	if signatures[1]["algorithm"] != "sha256" || signatures[1]["input"] != "https://example.com/b?a=1&b=2" || len(signatures[1]["signature"].(string)) != 64 {
+		t.Errorf("❌ Unexpected raw hash event: %v", signatures[1])
+	}
+
+	events = captureDebugEvents(t, func() { normalizeURL("://invalid") })
+	if len(events) != 1 || events[0]["error"] == nil {
+		t.Errorf("❌ Expected single event with error, got: %v", events)
+	}
+}
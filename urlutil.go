@@ -0,0 +1,798 @@
+package main
+
+import (
+	"cmp"
+	"errors"
+	"maps"
+	"math"
+	"net/url"
+	"regexp"
+	"slices"
+	"strings"
+
+	"github.com/PuerkitoBio/purell"
+)
+
+// Parse query parameters of a URL
+func parseQuery(rawURL string) (url.Values, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	return url.ParseQuery(u.RawQuery)
+}
+
+// Count key-value pairs in the URL query string
+func countQueryParams(rawURL string) (int, error) {
+	query, err := parseQuery(rawURL)
+	if err != nil {
+		return 0, err
+	}
+	count := 0
+	for _, values := range query {
+		count += len(values)
+	}
+	return count, nil
+}
+
+// Check if the URL query string contains the given (unescaped) key
+func hasQueryParam(rawURL string, key string) (bool, error) {
+	query, err := parseQuery(rawURL)
+	if err != nil {
+		return false, err
+	}
+	return query.Has(key), nil
+}
+
+// Get distinct query parameter keys of the URL, sorted
+func queryParamKeys(rawURL string) ([]string, error) {
+	query, err := parseQuery(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	return slices.Sorted(maps.Keys(query)), nil
+}
+
+// Parse query string, with or without the leading "?", into decoded values
+// by key. Malformed pairs, such as ones with invalid escapes, are skipped.
+func queryStringToMap(query string) url.Values {
+	values, _ := url.ParseQuery(strings.TrimPrefix(query, "?"))
+	return values
+}
+
+// Parse query string into decoded key-value pairs sorted by key. Values of
+// the same key keep their order.
+func queryStringToSortedPairs(query string) [][2]string {
+	values := queryStringToMap(query)
+	var pairs [][2]string
+	for _, key := range slices.Sorted(maps.Keys(values)) {
+		for _, value := range values[key] {
+			pairs = append(pairs, [2]string{key, value})
+		}
+	}
+	return pairs
+}
+
+// Encode key-value pairs in order into a query string, without the leading
+// "?". Keys and values are form encoded, so the result is safe to append to
+// a URL after "?".
+func mapToQueryString(pairs [][2]string) string {
+	params := make([]string, 0, len(pairs))
+	for _, kv := range pairs {
+		params = append(params, url.QueryEscape(kv[0])+"="+url.QueryEscape(kv[1]))
+	}
+	return strings.Join(params, "&")
+}
+
+// Encode key-value pairs into a query string sorted by key. Values of the
+// same key keep their order.
+func sortedMapToQueryString(pairs [][2]string) string {
+	sorted := slices.Clone(pairs)
+	slices.SortStableFunc(sorted, func(a, b [2]string) int {
+		return strings.Compare(a[0], b[0])
+	})
+	return mapToQueryString(sorted)
+}
+
+// Parse query string into decoded key-value pairs in their order. Malformed
+// pairs are skipped, as in queryStringToMap.
+func queryStringToPairs(query string) [][2]string {
+	var pairs [][2]string
+	for _, param := range strings.Split(strings.TrimPrefix(query, "?"), "&") {
+		if param == "" {
+			continue
+		}
+		key, value, _ := strings.Cut(param, "=")
+		key, keyErr := url.QueryUnescape(key)
+		value, valueErr := url.QueryUnescape(value)
+		if keyErr == nil && valueErr == nil {
+			pairs = append(pairs, [2]string{key, value})
+		}
+	}
+	return pairs
+}
+
+// Normalize URL and sort its query parameters by key. Unlike
+// purell.FlagSortQuery, parameters with equal keys are guaranteed to keep
+// their order, so the result can be relied on when the order of repeated
+// parameters matters.
+func urlNormalizeSortQueryStable(rawURL string) (string, error) {
+	return urlNormalizeSortQueryBy(rawURL, func(a, b [2]string) int {
+		return strings.Compare(a[0], b[0])
+	})
+}
+
+// Normalize URL and sort its decoded query key-value pairs with cmp, e.g. to
+// put known parameters first. Pairs cmp considers equal keep their order.
+func urlNormalizeSortQueryBy(rawURL string, cmp func(a, b [2]string) int) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags&^purell.FlagSortQuery)
+	if err != nil {
+		return "", err
+	}
+	if u.RawQuery != "" {
+		pairs := queryStringToPairs(u.RawQuery)
+		slices.SortStableFunc(pairs, cmp)
+		u.RawQuery = mapToQueryString(pairs)
+	}
+	return u.String(), nil
+}
+
+// Normalize URL, keeping only the last value of each query parameter and
+// sorting the parameters by key:
+//
+//	urlNormalizeRemoveDuplicateParamsKeepLast("https://example.com/?a=1&b=3&a=2") // "https://example.com/?a=2&b=3"
+//
+// Many web frameworks, such as PHP and Express, read the last value of a
+// repeated key, so the dropped values have no effect on those sites. Note
+// that url.Values.Get and other first-wins parsers read the first value
+// instead, and plain normalization keeps all values.
+func urlNormalizeRemoveDuplicateParamsKeepLast(rawURL string) (string, error) {
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags&^purell.FlagSortQuery)
+	if err != nil {
+		return "", err
+	}
+	if u.RawQuery != "" {
+		last := make(map[string]string)
+		for _, kv := range queryStringToPairs(u.RawQuery) {
+			last[kv[0]] = kv[1]
+		}
+		pairs := make([][2]string, 0, len(last))
+		for _, key := range slices.Sorted(maps.Keys(last)) {
+			pairs = append(pairs, [2]string{key, last[key]})
+		}
+		u.RawQuery = mapToQueryString(pairs)
+	}
+	return u.String(), nil
+}
+
+// Remove query parameter key from URL if its value is defaultValue, e.g.
+// "format=html" when HTML is the default format anyway. If the key occurs
+// several times, it is removed only when all its values are defaultValue.
+// Other parameters keep their order and encoding.
+func urlStripQueryParamIfDefault(rawURL, key, defaultValue string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	if u.RawQuery == "" {
+		return u.String(), nil
+	}
+
+	params := strings.Split(u.RawQuery, "&")
+	matches := func(param string) (isKey, isDefault bool) {
+		k, v, _ := strings.Cut(param, "=")
+		k, keyErr := url.QueryUnescape(k)
+		v, valueErr := url.QueryUnescape(v)
+		return keyErr == nil && k == key, valueErr == nil && v == defaultValue
+	}
+	for _, param := range params {
+		if isKey, isDefault := matches(param); isKey && !isDefault {
+			return u.String(), nil
+		}
+	}
+	params = slices.DeleteFunc(params, func(param string) bool {
+		isKey, _ := matches(param)
+		return isKey
+	})
+	u.RawQuery = strings.Join(params, "&")
+	return u.String(), nil
+}
+
+// Get the URL with the query string removed.
+//
+// This is a structural operation and does not normalize the URL. Callers
+// wanting a normalized result should use normalizeURLWithFlags, which
+// can also drop the fragment with purell.FlagRemoveFragment.
+func urlWithoutQuery(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	u.RawQuery = ""
+	u.ForceQuery = false
+	return u.String(), nil
+}
+
+// Get the URL with the fragment removed, without normalizing it.
+func urlWithoutFragment(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	u.Fragment = ""
+	u.RawFragment = ""
+	return u.String(), nil
+}
+
+// Get the URL with username and password removed, without normalizing it.
+// Works with any scheme, e.g. for redacting credentials from log lines.
+func urlWithoutCredentials(rawURL string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	u.User = nil
+	return u.String(), nil
+}
+
+// Check if URL contains user info, i.e. a username, password, or both.
+func urlHasCredentials(rawURL string) (bool, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return false, err
+	}
+	return u.User != nil, nil
+}
+
+// Get the percent-decoded username and password of URL. Returns nil if the
+// URL has no user info, and Userinfo.Password reports whether a password was
+// set, even an empty one.
+func urlCredentials(rawURL string) (*url.Userinfo, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	return u.User, nil
+}
+
+// FailureMode defines what to do with URLs that fail to normalize.
+type FailureMode int
+
+const (
+	FailureKeepOriginal     FailureMode = iota // Keep the original URL
+	FailureReplaceWithEmpty                    // Replace the URL with an empty string
+	FailureRemove                              // Remove the URL from the list
+)
+
+// Normalize URLs, replacing each element of the slice with its normalized form.
+//
+// The backing array is reused, so the returned slice must be used instead of
+// the original when FailureRemove drops URLs. Also returns the count of URLs
+// that changed when normalized; failed URLs are not counted.
+func normalizeManyInPlace(urls []string, flags purell.NormalizationFlags, mode FailureMode) ([]string, int) {
+	changed := 0
+	kept := urls[:0]
+	for _, rawURL := range urls {
+		normalized, err := normalizeURLWithFlags(rawURL, flags)
+		switch {
+		case err == nil:
+			if normalized != rawURL {
+				changed++
+			}
+			kept = append(kept, normalized)
+		case mode == FailureKeepOriginal:
+			kept = append(kept, rawURL)
+		case mode == FailureReplaceWithEmpty:
+			kept = append(kept, "")
+		}
+	}
+	clear(urls[len(kept):])
+	return kept, changed
+}
+
+// Normalize URLs and remove duplicates of the same canonical form.
+//
+// Returns the distinct normalized URLs in order of first appearance, and for
+// each input URL the index of its canonical form in them. URLs that fail to
+// normalize are kept as-is, as with FailureKeepOriginal.
+func normalizeURLListDedup(urls []string, flags purell.NormalizationFlags) ([]string, []int) {
+	var unique []string
+	provenance := make([]int, len(urls))
+	seen := make(map[string]int, len(urls))
+	for i, rawURL := range urls {
+		normalized, err := normalizeURLWithFlags(rawURL, flags)
+		if err != nil {
+			normalized = rawURL
+		}
+		index, ok := seen[normalized]
+		if !ok {
+			index = len(unique)
+			seen[normalized] = index
+			unique = append(unique, normalized)
+		}
+		provenance[i] = index
+	}
+	return unique, provenance
+}
+
+// Check if URL starts with a scheme, e.g. "https:" or "mailto:".
+//
+// Only scans the scheme, without parsing or allocating.
+func urlIsAbsolute(rawURL string) bool {
+	for i := 0; i < len(rawURL); i++ {
+		c := rawURL[i]
+		switch {
+		case 'a' <= c && c <= 'z' || 'A' <= c && c <= 'Z':
+		case '0' <= c && c <= '9' || c == '+' || c == '-' || c == '.':
+			if i == 0 {
+				return false
+			}
+		case c == ':':
+			return i > 0
+		default:
+			return false
+		}
+	}
+	return false
+}
+
+// Check if URL is a relative reference, e.g. "//host/path" or "/path".
+// The empty string is a relative (same-document) reference.
+func urlIsRelative(rawURL string) bool {
+	return !urlIsAbsolute(rawURL)
+}
+
+// Check if URL has a query component, even an empty one
+func urlHasQuery(rawURL string) bool {
+	i := strings.IndexByte(rawURL, '?')
+	if i < 0 {
+		return false
+	}
+	fragment := strings.IndexByte(rawURL, '#')
+	return fragment < 0 || i < fragment
+}
+
+// Check if URL has a fragment component, even an empty one
+func urlHasFragment(rawURL string) bool {
+	return strings.IndexByte(rawURL, '#') >= 0
+}
+
+// Normalization flags for comparing URLs without fragments
+const samePageNormalizationFlags = defaultNormalizationFlags | purell.FlagRemoveFragment
+
+// Normalize URL with given flags and parse the result. Data URIs are kept
+// as-is, as by normalizeURLWithFlags: everything after the scheme is left
+// opaque, so changes to the path or query of the result don't apply, and
+// String returns the data URI unchanged.
+func parseNormalizedURL(rawURL string, flags purell.NormalizationFlags) (*url.URL, error) {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return nil, err
+	}
+	if urlIsDataURI(normalized) {
+		return &url.URL{Scheme: normalized[:len("data")], Opaque: normalized[len("data:"):]}, nil
+	}
+	return parseURL(normalized)
+}
+
+// Check if URLs point to the same page, ignoring fragments
+func urlIsSamePage(a, b string) (bool, error) {
+	normalizedA, err := normalizeURLWithFlags(a, samePageNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	normalizedB, err := normalizeURLWithFlags(b, samePageNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	return normalizedA == normalizedB, nil
+}
+
+// Check if URLs are in the same directory, ignoring last path segment, query and fragment
+func urlIsSameDirectory(a, b string) (bool, error) {
+	directory := func(rawURL string) (string, error) {
+		u, err := parseNormalizedURL(rawURL, samePageNormalizationFlags)
+		if err != nil {
+			return "", err
+		}
+		path := u.EscapedPath()
+		return u.Scheme + "://" + u.Host + path[:strings.LastIndex(path, "/")+1], nil
+	}
+	directoryA, err := directory(a)
+	if err != nil {
+		return false, err
+	}
+	directoryB, err := directory(b)
+	if err != nil {
+		return false, err
+	}
+	return directoryA == directoryB, nil
+}
+
+// Check if URLs have the same scheme, host and port
+func urlIsSameDomain(a, b string) (bool, error) {
+	u1, err := parseNormalizedURL(a, defaultNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	u2, err := parseNormalizedURL(b, defaultNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	return u1.Scheme == u2.Scheme && u1.Host == u2.Host, nil
+}
+
+// Check if sub is under superURL in the URL hierarchy: same origin, and path
+// of sub is the path of superURL or extends it by whole segments, so "/a/b"
+// is under "/a" but "/abc" isn't. With checkQuery, query parameters of
+// superURL must also all be present in sub.
+func urlIsSubsetOf(sub, superURL string, checkQuery bool) (bool, error) {
+	u1, err := parseNormalizedURL(sub, samePageNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	u2, err := parseNormalizedURL(superURL, samePageNormalizationFlags)
+	if err != nil {
+		return false, err
+	}
+	if u1.Scheme != u2.Scheme || u1.Host != u2.Host {
+		return false, nil
+	}
+
+	subPath, superPath := u1.EscapedPath(), strings.TrimSuffix(u2.EscapedPath(), "/")
+	if subPath != superPath && !strings.HasPrefix(subPath, superPath+"/") {
+		return false, nil
+	}
+
+	if checkQuery {
+		subQuery := u1.Query()
+		for key, values := range u2.Query() {
+			for _, value := range values {
+				if !slices.Contains(subQuery[key], value) {
+					return false, nil
+				}
+			}
+		}
+	}
+	return true, nil
+}
+
+// URLDiff describes which components differ between two normalized URLs.
+// Before and after values are only set for changed components.
+type URLDiff struct {
+	SchemeChanged   bool
+	HostChanged     bool
+	PortChanged     bool
+	PathChanged     bool
+	QueryChanged    bool
+	FragmentChanged bool
+
+	BeforeScheme, AfterScheme     string
+	BeforeHost, AfterHost         string
+	BeforePort, AfterPort         string
+	BeforePath, AfterPath         string
+	BeforeQuery, AfterQuery       string
+	BeforeFragment, AfterFragment string
+}
+
+// Check if any component differs
+func (d URLDiff) Changed() bool {
+	return d.SchemeChanged || d.HostChanged || d.PortChanged || d.PathChanged || d.QueryChanged || d.FragmentChanged
+}
+
+// Normalize both URLs and compute which of their components differ, e.g. for
+// logging what normalization did to a URL. Host is compared without port.
+func urlDiff(a, b string) (URLDiff, error) {
+	var diff URLDiff
+	before, err := parseNormalizedURL(a, defaultNormalizationFlags)
+	if err != nil {
+		return diff, err
+	}
+	after, err := parseNormalizedURL(b, defaultNormalizationFlags)
+	if err != nil {
+		return diff, err
+	}
+
+	compare := func(changed *bool, beforeValue, afterValue *string, x, y string) {
+		if x != y {
+			*changed = true
+			*beforeValue, *afterValue = x, y
+		}
+	}
+	compare(&diff.SchemeChanged, &diff.BeforeScheme, &diff.AfterScheme, before.Scheme, after.Scheme)
+	compare(&diff.HostChanged, &diff.BeforeHost, &diff.AfterHost, before.Hostname(), after.Hostname())
+	compare(&diff.PortChanged, &diff.BeforePort, &diff.AfterPort, before.Port(), after.Port())
+	compare(&diff.PathChanged, &diff.BeforePath, &diff.AfterPath, before.EscapedPath(), after.EscapedPath())
+	compare(&diff.QueryChanged, &diff.BeforeQuery, &diff.AfterQuery, before.RawQuery, after.RawQuery)
+	compare(&diff.FragmentChanged, &diff.BeforeFragment, &diff.AfterFragment, before.EscapedFragment(), after.EscapedFragment())
+	return diff, nil
+}
+
+// Distance returned by urlPathEditDistance for URLs of different origin
+const differentOriginDistance = math.MaxInt
+
+// Compute edit distance between path segments of normalized URLs.
+//
+// Each insert, delete or substitute of one path segment counts as one edit.
+// If scheme or host differ, differentOriginDistance is returned instead.
+func urlPathEditDistance(a, b string) (int, error) {
+	u1, err := parseNormalizedURL(a, defaultNormalizationFlags)
+	if err != nil {
+		return 0, err
+	}
+	u2, err := parseNormalizedURL(b, defaultNormalizationFlags)
+	if err != nil {
+		return 0, err
+	}
+	if u1.Scheme != u2.Scheme || u1.Host != u2.Host {
+		return differentOriginDistance, nil
+	}
+
+	segments := func(u *url.URL) []string {
+		if path := strings.Trim(u.EscapedPath(), "/"); path != "" {
+			return strings.Split(path, "/")
+		}
+		return nil
+	}
+	s1, s2 := segments(u1), segments(u2)
+
+	// Wagner-Fischer, keeping only the previous row
+	previous := make([]int, len(s2)+1)
+	current := make([]int, len(s2)+1)
+	for j := range previous {
+		previous[j] = j
+	}
+	for i := 1; i <= len(s1); i++ {
+		current[0] = i
+		for j := 1; j <= len(s2); j++ {
+			substitution := previous[j-1]
+			if s1[i-1] != s2[j-1] {
+				substitution++
+			}
+			current[j] = min(previous[j]+1, current[j-1]+1, substitution)
+		}
+		previous, current = current, previous
+	}
+	return previous[len(s2)], nil
+}
+
+// Default ports by scheme, removed from canonical roots
+var defaultPorts = map[string]string{"http": "80", "https": "443", "ws": "80", "wss": "443"}
+
+var errMissingOrigin = errors.New("missing scheme or host")
+
+// Get the root of the URL: scheme, host and port with a trailing slash.
+//
+// Default port for the scheme is removed when flags contain
+// purell.FlagRemoveDefaultPort. Only the scheme and host are lowercased,
+// which is cheaper than full normalization when only the origin is needed.
+func urlCanonicalRoot(rawURL string, flags purell.NormalizationFlags) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	if u.Scheme == "" || u.Host == "" {
+		return "", newURLParseError(rawURL, errMissingOrigin)
+	}
+	scheme := strings.ToLower(u.Scheme)
+	host := strings.TrimSuffix(strings.ToLower(u.Host), ":")
+	if port := u.Port(); port != "" && port == defaultPorts[scheme] && flags&purell.FlagRemoveDefaultPort != 0 {
+		host = strings.TrimSuffix(host, ":"+port)
+	}
+	return scheme + "://" + host + "/", nil
+}
+
+// Get URL of the robots.txt of the site of URL, e.g.
+// "https://example.com:8443/robots.txt". Default ports are removed.
+func urlRobotsTxtURL(rawURL string) (string, error) {
+	root, err := urlCanonicalRoot(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	return root + "robots.txt", nil
+}
+
+// Get URL of the conventional sitemap.xml of the site of URL, as with
+// urlRobotsTxtURL. Sites can place sitemaps elsewhere too, and list them in
+// robots.txt.
+func urlSitemapXMLURL(rawURL string) (string, error) {
+	root, err := urlCanonicalRoot(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	return root + "sitemap.xml", nil
+}
+
+var errRelativeURLWithoutBase = errors.New("relative URL without base URL")
+
+// Normalize URL, resolving it against base URL first if it is relative.
+//
+// Absolute URLs are normalized as-is and base is ignored. Relative URLs
+// without a base fail with errRelativeURLWithoutBase. Meant for link
+// extractors, which see both kinds of hrefs.
+func normalizeURLWithContext(rawURL, baseURL string, flags purell.NormalizationFlags) (string, error) {
+	if urlIsAbsolute(rawURL) {
+		return normalizeURLWithFlags(rawURL, flags)
+	}
+	if baseURL == "" {
+		return "", newURLParseError(rawURL, errRelativeURLWithoutBase)
+	}
+	if !urlIsAbsolute(baseURL) {
+		return "", newURLParseError(baseURL, errMissingOrigin)
+	}
+
+	base, err := parseURL(baseURL)
+	if err != nil {
+		return "", err
+	}
+	ref, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	return normalizeURLWithFlags(base.ResolveReference(ref).String(), flags)
+}
+
+// Schemes treating "\" as "/" in paths, as browsers do
+var specialSchemes = []string{"http", "https", "ws", "wss", "ftp", "file"}
+
+// Resolve link href found in an HTML document the way browsers do, against
+// the document base URL htmlBase. That is the href of the document's <base>
+// element, resolved against the page URL with this function too, or the page
+// URL when there is no <base>.
+//
+// As in the URL standard of WHATWG, leading and trailing whitespace and all
+// tabs and newlines of href are removed, "\" is read as "/" in paths of
+// http and other special schemes, and an empty href points to the base
+// without its fragment. Absolute hrefs ignore the base. The result is not
+// normalized, and a relative htmlBase fails with errMissingOrigin.
+//
+//	urlExtractLinkContext("https://example.com/a/b?q#top", "../c d") // "https://example.com/c%20d"
+func urlExtractLinkContext(htmlBase, href string) (string, error) {
+	if !urlIsAbsolute(htmlBase) {
+		return "", newURLParseError(htmlBase, errMissingOrigin)
+	}
+	base, err := parseURL(htmlBase)
+	if err != nil {
+		return "", err
+	}
+
+	// Strip leading and trailing C0 control characters and spaces
+	href = strings.TrimFunc(href, func(r rune) bool { return r <= ' ' })
+	href = strings.NewReplacer("\t", "", "\n", "", "\r", "").Replace(href)
+	if !urlIsAbsolute(href) && slices.Contains(specialSchemes, base.Scheme) {
+		end := strings.IndexAny(href, "?#")
+		if end < 0 {
+			end = len(href)
+		}
+		href = strings.ReplaceAll(href[:end], "\\", "/") + href[end:]
+	}
+
+	ref, err := parseURL(href)
+	if err != nil {
+		return "", err
+	}
+	resolved := base.ResolveReference(ref)
+	// Fragment always comes from href, url.URL keeps the one of the base for
+	// an empty href
+	if ref.Fragment == "" {
+		resolved.Fragment = ""
+		resolved.RawFragment = ""
+	}
+	return resolved.String(), nil
+}
+
+// Normalize protocol-relative URL, e.g. "//example.com/path" from an HTML
+// link, by giving it defaultScheme, typically "https". Other URLs are
+// normalized as-is.
+func urlNormalizeProtocolRelative(rawURL, defaultScheme string) (string, error) {
+	if strings.HasPrefix(rawURL, "//") {
+		rawURL = defaultScheme + ":" + rawURL
+	}
+	return normalizeURL(rawURL)
+}
+
+// Mistyped separator after http or https scheme, such as "http//",
+// "https:/" or "http:::"
+var mistypedSchemeSeparatorPattern = regexp.MustCompile(`^(?i)(https?)(?:[:;]+/*|/+)`)
+
+// Normalize URL typed by a user, correcting common mistakes first:
+//
+//	urlNormalizeLenient("example.com/page")    // "https://example.com/page"
+//	urlNormalizeLenient("http//example.com")   // "http://example.com"
+//	urlNormalizeLenient(`https:\\example.com`) // "https://example.com"
+//
+// Backslashes before the query are read as slashes, separators after http
+// and https schemes are fixed, and "https://" is prepended if there is no
+// scheme. Other schemes, such as "mailto:", are not corrected. Valid http and
+// https URLs with a host are normalized as-is. If the corrected URL fails
+// too, the error of the original input is returned.
+func urlNormalizeLenient(input string) (string, error) {
+	u, strictErr := parseURL(input)
+	if strictErr == nil {
+		if (u.Scheme == "http" || u.Scheme == "https") && u.Host != "" {
+			return normalizeURL(input)
+		}
+		strictErr = newURLParseError(input, errMissingOrigin)
+	}
+
+	fixed := strings.TrimSpace(input)
+	end := strings.IndexAny(fixed, "?#")
+	if end < 0 {
+		end = len(fixed)
+	}
+	fixed = strings.ReplaceAll(fixed[:end], "\\", "/") + fixed[end:]
+	fixed = mistypedSchemeSeparatorPattern.ReplaceAllString(fixed, "$1://")
+	// Hosts followed by a port, as in "example.com:8080/page", parse as scheme
+	noScheme := u == nil || u.Scheme == "" || u.Scheme == "localhost" || strings.Contains(u.Scheme, ".")
+	if noScheme && !strings.Contains(fixed, "://") {
+		fixed = "https://" + strings.TrimLeft(fixed, "/")
+	}
+
+	normalized, err := parseNormalizedURL(fixed, defaultNormalizationFlags)
+	if err != nil || normalized.Host == "" {
+		return "", strictErr
+	}
+	return normalized.String(), nil
+}
+
+// Session ID query parameters of common web frameworks, lowercase
+var sessionQueryParams = []string{"jsessionid", "phpsessid", "aspsessionid", "sessionid", "session_id", "sid", "cfid", "cftoken"}
+
+// Normalize URL with every step that helps finding duplicates: the default
+// normalization, and removing the fragment, credentials, trailing slash,
+// "utm_" parameters, session ID parameters and repeated parameters with the
+// same value.
+//
+// The result is lossy. Fragments of single page apps, repeated values and
+// their order, and parameters named like session IDs may matter for some
+// sites, so it is only meant for comparing URLs, not for requesting them.
+func urlCanonicalizeForDeduplication(rawURL string) (string, error) {
+	u, err := parseNormalizedURL(rawURL, samePageNormalizationFlags)
+	if err != nil {
+		return "", err
+	}
+	u.User = nil
+	applyTrailingSlashPolicy(u, TrailingSlashAlwaysRemove)
+
+	if u.RawQuery != "" {
+		pairs := slices.DeleteFunc(queryStringToPairs(u.RawQuery), func(kv [2]string) bool {
+			key := strings.ToLower(kv[0])
+			return strings.HasPrefix(key, "utm_") || slices.Contains(sessionQueryParams, key)
+		})
+		slices.SortFunc(pairs, func(a, b [2]string) int {
+			return cmp.Or(strings.Compare(a[0], b[0]), strings.Compare(a[1], b[1]))
+		})
+		u.RawQuery = mapToQueryString(slices.Compact(pairs))
+	}
+	return u.String(), nil
+}
+
+// Check if URL is already in normalized form, i.e. normalizing it with flags
+// doesn't change it.
+func urlIsNormalized(rawURL string, flags purell.NormalizationFlags) (bool, error) {
+	normalized, err := normalizeURLWithFlags(rawURL, flags)
+	if err != nil {
+		return false, err
+	}
+	return normalized == rawURL, nil
+}
+
+// Remove fragment of URL, unless it starts with any of the anchor prefixes.
+//
+// Keeps hash routes of single-page apps, e.g. "#/route/" with prefix "/" or
+// "#!/route" with "!", while removing plain anchors such as "#section". With
+// no prefixes, the fragment is always removed.
+func removeFragmentPreservingAnchor(rawURL string, anchorWhitelist []string) (string, error) {
+	u, err := parseURL(rawURL)
+	if err != nil {
+		return "", err
+	}
+	keep := slices.ContainsFunc(anchorWhitelist, func(prefix string) bool {
+		return strings.HasPrefix(u.Fragment, prefix)
+	})
+	if !keep {
+		u.Fragment = ""
+		u.RawFragment = ""
+	}
+	return u.String(), nil
+}
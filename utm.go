@@ -0,0 +1,65 @@
+package main
+
+import (
+	"net/url"
+	"slices"
+	"strings"
+)
+
+// UTM parameters of a URL, for recording campaign attribution separately
+// from the canonical URL.
+type UTMParams struct {
+	Source   string `json:"source,omitempty"`   // utm_source
+	Medium   string `json:"medium,omitempty"`   // utm_medium
+	Campaign string `json:"campaign,omitempty"` // utm_campaign
+	Term     string `json:"term,omitempty"`     // utm_term
+	Content  string `json:"content,omitempty"`  // utm_content
+}
+
+// Check if no UTM parameters are set
+func (p UTMParams) IsEmpty() bool {
+	return p == UTMParams{}
+}
+
+// Normalize URL and remove its UTM parameters, returning the known ones:
+//
+//	urlNormalizeUTMPreserving("https://example.com/?utm_source=news&id=1")
+//	// "https://example.com/?id=1", UTMParams{Source: "news"}
+//
+// All "utm_" prefixed parameters are removed, but only the standard five are
+// returned. If a parameter is repeated, the first value is returned.
+func urlNormalizeUTMPreserving(rawURL string) (string, UTMParams, error) {
+	var utm UTMParams
+	u, err := parseNormalizedURL(rawURL, defaultNormalizationFlags)
+	if err != nil {
+		return "", utm, err
+	}
+	if u.RawQuery == "" {
+		return u.String(), utm, nil
+	}
+
+	fields := map[string]*string{
+		"utm_source":   &utm.Source,
+		"utm_medium":   &utm.Medium,
+		"utm_campaign": &utm.Campaign,
+		"utm_term":     &utm.Term,
+		"utm_content":  &utm.Content,
+	}
+	params := slices.DeleteFunc(strings.Split(u.RawQuery, "&"), func(param string) bool {
+		key, value, _ := strings.Cut(param, "=")
+		key, err := url.QueryUnescape(key)
+		if err != nil || !strings.HasPrefix(key, "utm_") {
+			return false
+		}
+		if field, ok := fields[key]; ok && *field == "" {
+			// Undecodable values are recorded as-is
+			if decoded, err := url.QueryUnescape(value); err == nil {
+				value = decoded
+			}
+			*field = value
+		}
+		return true
+	})
+	u.RawQuery = strings.Join(params, "&")
+	return u.String(), utm, nil
+}
@@ -0,0 +1,59 @@
+package main
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestURLNormalizeUTMPreserving(t *testing.T) {
+	tests := []struct {
+		name     string
+		url      string
+		expected string
+		utm      UTMParams
+	}{
+		{
+			"full",
+			"https://Example.com/article?utm_source=newsletter&utm_medium=email&utm_campaign=spring+sale&utm_term=suola&utm_content=header&id=1",
+			"https://example.com/article?id=1",
+			UTMParams{Source: "newsletter", Medium: "email", Campaign: "spring sale", Term: "suola", Content: "header"},
+		},
+		{
+			"partial",
+			"https://example.com/?utm_source=twitter&b=2&utm_id=42&a=1#top",
+			"https://example.com/?a=1&b=2#top",
+			UTMParams{Source: "twitter"},
+		},
+		{"none", "https://example.com/?a=1", "https://example.com/?a=1", UTMParams{}},
+		{"only utm", "https://example.com/page?utm_medium=social", "https://example.com/page", UTMParams{Medium: "social"}},
+	}
+
+	for _, test := range tests {
+		t.Run(test.name, func(t *testing.T) {
+			got, utm, err := urlNormalizeUTMPreserving(test.url)
+			if err != nil || got != test.expected || utm != test.utm {
+				t.Errorf("❌ Expected %q and %+v, got %q and %+v (error: %v)", test.expected, test.utm, got, utm, err)
+			}
+			if utm.IsEmpty() != (test.utm == UTMParams{}) {
+				t.Errorf("❌ Expected IsEmpty() %v for %+v", test.utm == UTMParams{}, utm)
+			}
+		})
+	}
+
+	// Stripped URL gets the same signature as one without UTM parameters
+	stripped, _, _ := urlNormalizeUTMPreserving("https://example.com/?utm_source=x&id=1")
+	if plain, _ := normalizeURL("https://example.com/?id=1"); stripped != plain {
+		t.Errorf("❌ Expected %q, got %q", plain, stripped)
+	}
+
+	if _, _, err := urlNormalizeUTMPreserving("://invalid"); err == nil {
+		t.Error("❌ Expected error for invalid URL")
+	}
+}
+
+func TestUTMParamsJSON(t *testing.T) {
+	data, err := json.Marshal(UTMParams{Source: "newsletter", Campaign: "spring"})
+	if expected := `{"source":"newsletter","campaign":"spring"}`; err != nil || string(data) != expected {
+		t.Errorf("❌ Expected %s, got %s (error: %v)", expected, data, err)
+	}
+}
@@ -18,8 +18,8 @@
 //
 // Memory Management:
 //   - Malloc/Free: Used by host to manage input buffers
-//   - stringToPtr: Used internally to return results, stores in memoryArena
-//   - memoryArena: Prevents GC of allocations until explicitly freed
+//   - stringToPtr: Used internally to return results, stores in the memory arena
+//   - arena: Prevents GC of allocations until explicitly freed
 //   - Result pointers from GetSignature are NOT freed by the host
 //
 // Example Python code:
@@ -50,20 +50,42 @@
 package main
 
 import (
+	"errors"
 	"fmt"
 	"os"
-	"sync"
 	"unsafe"
+
+	"github.com/PuerkitoBio/purell"
 )
 
 // Prevent excessive memory access
 const maxUrlLength = 64 * 1024 // 64KB
-// Prevent excessive allocations
-const maxAllocSize = 1024 * 1024 // 1MB
 
-// Memory arena to prevent garbage collection of allocations
-// Using a sync.Map for better concurrent performance
-var memoryArena sync.Map // map[uint32][]byte
+// Compile-time checks on the limits, so unusual values fail the build instead
+// of silently producing odd sized allocations. Array index is out of range
+// unless the size is a power of two, and unsigned constant overflows when
+// below the minimum.
+var (
+	_ = [1]struct{}{}[maxUrlLength&(maxUrlLength-1)]
+	_ = [1]struct{}{}[maxAllocSize&(maxAllocSize-1)]
+)
+
+const (
+	_ uint = maxUrlLength - 256
+	_ uint = maxAllocSize - maxUrlLength
+)
+
+// Memory arena to prevent garbage collection of allocations, see memoryArena
+var arena memoryArena
+
+// Error codes of exports writing into a host provided output buffer, such as
+// NormalizeURL, GetSignatureRaw, HashURLHMAC and EncodeComponent. On success
+// these return the length of the result instead.
+const (
+	wasmErrInvalidInput   = -1 // Input or output pointer, or other input, is invalid
+	wasmErrParse          = -2 // URL or string can't be parsed, normalized or decoded
+	wasmErrBufferTooSmall = -3 // Output buffer is too small for the result
+)
 
 // GetSignature processes a URL and returns a signature.
 //
@@ -83,11 +105,114 @@ var memoryArena sync.Map // map[uint32][]byte
 //
 //go:wasmexport GetSignature
 func GetSignature(urlPtr, urlLen uint32) uint64 {
+	countExportCall("GetSignature")
 	// Read the URL string from WASM memory
 	url := ptrToString(urlPtr, urlLen)
 
 	signature, err := getSignature(url)
+	return packSignatureResult(signature, err)
+}
 
+// GetSignatureWith processes a URL and returns a signature using the named
+// hash algorithm, such as "sha256" or "sha512". SHA-3 algorithms "sha3-256"
+// and "sha3-512" are available when built with the sha3 tag.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes
+//   - algorithmPtr: Pointer to algorithm name in WASM memory (allocated by caller with Malloc)
+//   - algorithmLen: Length of the algorithm name in bytes
+//
+// Returns: uint64 packed as in GetSignature.
+//
+//go:wasmexport GetSignatureWith
+func GetSignatureWith(urlPtr, urlLen, algorithmPtr, algorithmLen uint32) uint64 {
+	countExportCall("GetSignatureWith")
+	url := ptrToString(urlPtr, urlLen)
+	algorithm := ptrToString(algorithmPtr, algorithmLen)
+
+	formattedURL, err := processURL(url)
+	if err != nil {
+		return packSignatureResult("", err)
+	}
+	return packSignatureResult(generateSignatureWith(formattedURL, algorithm))
+}
+
+// GetSignatureRaw processes a URL like GetSignature, and writes the 32 raw
+// bytes of its SHA-256 signature into a host provided output buffer, instead
+// of returning 64 hex characters.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes, at least 32
+//
+// Returns: 32, the number of bytes written, or -1 for invalid pointers, -2 if
+// the URL can't be processed, and -3 if the output buffer is too small.
+//
+//go:wasmexport GetSignatureRaw
+func GetSignatureRaw(urlPtr, urlLen, outPtr, outMax uint32) int32 {
+	countExportCall("GetSignatureRaw")
+	url := ptrToString(urlPtr, urlLen)
+	if url == "" {
+		return wasmErrInvalidInput
+	}
+	digest, err := getSignatureRaw(url)
+	if err != nil {
+		return wasmErrParse
+	}
+	if outMax < uint32(len(digest)) {
+		return wasmErrBufferTooSmall
+	}
+	if !copyToPtr(outPtr, outMax, string(digest[:])) {
+		return wasmErrInvalidInput
+	}
+	return int32(len(digest))
+}
+
+// Normalization flags used by NormalizeAndHashURL, see SetNormalizationFlags
+var wasmNormalizationFlags = defaultNormalizationFlags
+
+// SetNormalizationFlags sets normalization steps used by subsequent calls.
+//
+// Parameters:
+//   - flags: Bit set of normalization steps, see FlagBit* constants:
+//     bit 0 = sort query, bit 1 = remove dot segments, bit 2 = lowercase scheme,
+//     bit 3 = lowercase host, bit 4 = remove default port, bit 5 = remove fragment,
+//     bit 6 = remove empty query separator, bit 7 = uppercase escapes,
+//     bit 8 = decode unnecessary escapes, bit 9 = encode necessary escapes.
+//     Default is 0x3DF, everything except removing the fragment.
+//
+//go:wasmexport SetNormalizationFlags
+func SetNormalizationFlags(flags uint32) {
+	wasmNormalizationFlags = normalizationFlagsFromBits(flags)
+}
+
+// NormalizeAndHashURL normalizes a URL without site rules, using flags set
+// with SetNormalizationFlags, and returns the SHA-256 signature of it.
+// GetSignature and GetSignatureWith ignore the flags, so their signatures
+// stay the same for existing hosts.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes
+//
+// Returns: uint64 packed as in GetSignature.
+//
+//go:wasmexport NormalizeAndHashURL
+func NormalizeAndHashURL(urlPtr, urlLen uint32) uint64 {
+	countExportCall("NormalizeAndHashURL")
+	url := ptrToString(urlPtr, urlLen)
+	normalized, err := normalizeURLWithFlags(url, wasmNormalizationFlags)
+	if err != nil {
+		return packSignatureResult("", err)
+	}
+	return packSignatureResult(generateSignature(normalized), nil)
+}
+
+// Helper to pack signature or error message as returned by GetSignature
+func packSignatureResult(signature string, err error) uint64 {
 	if err != nil {
 		// Return error indicator: pointer to error message with error bit set
 		errMsg := err.Error()
@@ -103,7 +228,285 @@ func GetSignature(urlPtr, urlLen uint32) uint64 {
 	return uint64(sigPtr)<<32 | uint64(sigLen)
 }
 
-// Helper to convert pointer and length to Go string
+// HashURLHMAC normalizes a URL without site rules, and writes HMAC of it
+// with a host provided key into a host provided output buffer.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes, less than maxUrlLength
+//   - keyPtr: Pointer to the key in WASM memory (allocated by caller with Malloc)
+//   - keyLen: Length of the key in bytes, must not be zero
+//   - algorithmPtr: Pointer to hash algorithm name in WASM memory, as in GetSignatureWith
+//   - algorithmLen: Length of the algorithm name in bytes
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes, the digest size of the algorithm
+//
+// Returns: Length of the raw MAC written into the output buffer, or -1 for
+// invalid pointers, an empty key or an unknown algorithm, -2 if the URL can't
+// be normalized, and -3 if the output buffer is too small.
+//
+//go:wasmexport HashURLHMAC
+func HashURLHMAC(urlPtr, urlLen, keyPtr, keyLen, algorithmPtr, algorithmLen, outPtr, outMax uint32) int32 {
+	countExportCall("HashURLHMAC")
+	url := ptrToString(urlPtr, urlLen)
+	key := ptrToString(keyPtr, keyLen)
+	algorithm := ptrToString(algorithmPtr, algorithmLen)
+	if url == "" || key == "" {
+		return wasmErrInvalidInput
+	}
+	if _, ok := hashAlgorithms[algorithm]; !ok {
+		return wasmErrInvalidInput
+	}
+
+	mac, err := hashURLSaltedHMAC(url, []byte(key), algorithm)
+	if err != nil {
+		return wasmErrParse
+	}
+	if uint32(len(mac)) > outMax {
+		return wasmErrBufferTooSmall
+	}
+	if !copyToPtr(outPtr, outMax, string(mac)) {
+		return wasmErrInvalidInput
+	}
+	return int32(len(mac))
+}
+
+// Error codes returned by SanitizeURL, in addition to the wasmErr* codes
+const (
+	sanitizeErrTooLong    = -10 // URL is longer than 8192 bytes
+	sanitizeErrNullByte   = -11 // URL contains a null byte
+	sanitizeErrJavascript = -12 // URL uses javascript: scheme
+	sanitizeErrData       = -13 // URL uses data: scheme
+	sanitizeErrPathDepth  = -14 // URL path is nested deeper than 50 segments
+)
+
+// SanitizeURL rejects URLs with known dangerous patterns, and normalizes the rest.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes
+//
+// Returns: Length of the sanitized URL written into the output buffer, or a
+// negative error code (see wasmErr* and sanitizeErr* constants) if the URL is rejected.
+//
+//go:wasmexport SanitizeURL
+func SanitizeURL(urlPtr, urlLen, outPtr, outMax uint32) int32 {
+	countExportCall("SanitizeURL")
+	url := ptrToString(urlPtr, urlLen)
+	if url == "" {
+		return wasmErrInvalidInput
+	}
+
+	sanitized, err := sanitizeURL(url)
+	if err != nil {
+		var dangerErr *DangerousURLError
+		if !errors.As(err, &dangerErr) {
+			return wasmErrParse
+		}
+		switch dangerErr.Kind {
+		case DangerTooLong:
+			return sanitizeErrTooLong
+		case DangerNullByte:
+			return sanitizeErrNullByte
+		case DangerJavascriptScheme:
+			return sanitizeErrJavascript
+		case DangerDataScheme:
+			return sanitizeErrData
+		default:
+			return sanitizeErrPathDepth
+		}
+	}
+
+	if uint32(len(sanitized)) > outMax {
+		return wasmErrBufferTooSmall
+	}
+	if !copyToPtr(outPtr, outMax, sanitized) {
+		return wasmErrInvalidInput
+	}
+	return int32(len(sanitized))
+}
+
+// Length of the result of the last NormalizeURL call, see GetLastOutputLen
+var lastOutputLen uint32
+
+// NormalizeURL normalizes a URL without site rules, using flags set with
+// SetNormalizationFlags, and writes it into a host provided output buffer.
+//
+// Parameters:
+//   - urlPtr: Pointer to URL string in WASM memory (allocated by caller with Malloc)
+//   - urlLen: Length of the URL string in bytes, less than maxUrlLength
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes
+//
+// Returns: Length of the normalized URL written into the output buffer, or a
+// negative error code: -1 for invalid input or output pointer, -2 if the URL
+// can't be normalized, and -3 if the output buffer is too small. The length of
+// the normalized URL is also available from GetLastOutputLen, so a host can
+// retry with a large enough buffer after -3.
+//
+// Safety: Input and output buffers must not overlap, and must stay allocated
+// for the duration of the call. Nothing is written outside of outMax bytes.
+//
+//go:wasmexport NormalizeURL
+func NormalizeURL(urlPtr, urlLen, outPtr, outMax uint32) int32 {
+	countExportCall("NormalizeURL")
+	return normalizeURLToPtr(urlPtr, urlLen, wasmNormalizationFlags, outPtr, outMax)
+}
+
+// NormalizeURLWithFlags is NormalizeURL with the normalization flags given in
+// the same call, as a bit set like in SetNormalizationFlags. The flags set
+// with SetNormalizationFlags are neither used nor changed.
+//
+// A WASM instance runs one call at a time, and instances in separate workers
+// don't share memory, so passing the flags inline is enough for hosts using
+// one instance per worker. Hosts sharing one instance between workers must
+// still serialize all calls to it.
+//
+//	const len = exports.NormalizeURLWithFlags(urlPtr, urlLen, 0x3FF, outPtr, outMax);
+//	if (len >= 0) {
+//	  const normalized = new TextDecoder().decode(new Uint8Array(exports.memory.buffer, outPtr, len));
+//	}
+//
+//go:wasmexport NormalizeURLWithFlags
+func NormalizeURLWithFlags(urlPtr, urlLen, flags, outPtr, outMax uint32) int32 {
+	countExportCall("NormalizeURLWithFlags")
+	return normalizeURLToPtr(urlPtr, urlLen, normalizationFlagsFromBits(flags), outPtr, outMax)
+}
+
+// Normalize URL with flags into the output buffer, see NormalizeURL
+func normalizeURLToPtr(urlPtr, urlLen uint32, flags purell.NormalizationFlags, outPtr, outMax uint32) int32 {
+	lastOutputLen = 0
+	if urlLen >= maxUrlLength {
+		return wasmErrInvalidInput
+	}
+	url := ptrToString(urlPtr, urlLen)
+	if url == "" {
+		return wasmErrInvalidInput
+	}
+
+	normalized, err := normalizeURLWithFlags(url, flags)
+	if err != nil {
+		return wasmErrParse
+	}
+	lastOutputLen = uint32(len(normalized))
+	if lastOutputLen > outMax {
+		return wasmErrBufferTooSmall
+	}
+	if !copyToPtr(outPtr, outMax, normalized) {
+		return wasmErrInvalidInput
+	}
+	return int32(lastOutputLen)
+}
+
+// GetLastOutputLen returns the length of the normalized URL from the last
+// NormalizeURL call, or 0 if it failed before normalizing.
+//
+//go:wasmexport GetLastOutputLen
+func GetLastOutputLen() uint32 {
+	return lastOutputLen
+}
+
+// EncodeComponent percent-encodes all but the unreserved characters of RFC
+// 3986 in a string, and writes it into a host provided output buffer. This is
+// stricter than JavaScript's encodeURIComponent, see encodeURLComponent.
+//
+// Parameters:
+//   - inPtr: Pointer to the string in WASM memory (allocated by caller with Malloc)
+//   - inLen: Length of the string in bytes, less than maxUrlLength
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes, up to three times inLen
+//
+// Returns: Length of the encoded string written into the output buffer, or
+// -1 for invalid input or output pointer, and -3 if the output buffer is too
+// small.
+//
+//go:wasmexport EncodeComponent
+func EncodeComponent(inPtr, inLen, outPtr, outMax uint32) int32 {
+	s, ok := componentInput(inPtr, inLen)
+	if !ok {
+		return wasmErrInvalidInput
+	}
+	return writeComponentOutput(outPtr, outMax, encodeURLComponent(s))
+}
+
+// DecodeComponent decodes all percent-encoded sequences of a string, like
+// JavaScript's decodeURIComponent, and writes it into a host provided output
+// buffer.
+//
+// Parameters:
+//   - inPtr: Pointer to the string in WASM memory (allocated by caller with Malloc)
+//   - inLen: Length of the string in bytes, less than maxUrlLength
+//   - outPtr: Pointer to output buffer in WASM memory (allocated by caller with Malloc)
+//   - outMax: Size of the output buffer in bytes, at most inLen is needed
+//
+// Returns: Length of the decoded string written into the output buffer, or
+// -1 for invalid input or output pointer, -2 if the string has malformed
+// escapes or doesn't decode into valid UTF-8, and -3 if the output buffer is
+// too small.
+//
+//go:wasmexport DecodeComponent
+func DecodeComponent(inPtr, inLen, outPtr, outMax uint32) int32 {
+	s, ok := componentInput(inPtr, inLen)
+	if !ok {
+		return wasmErrInvalidInput
+	}
+	decoded, err := decodeURLComponent(s)
+	if err != nil {
+		return wasmErrParse
+	}
+	return writeComponentOutput(outPtr, outMax, decoded)
+}
+
+// Read input of component exports, empty input is valid
+func componentInput(ptr, length uint32) (string, bool) {
+	if length >= maxUrlLength {
+		return "", false
+	}
+	s := ptrToString(ptr, length)
+	return s, s != "" || length == 0
+}
+
+// Write result of component exports, returning its length or an error code
+func writeComponentOutput(outPtr, outMax uint32, s string) int32 {
+	if uint32(len(s)) > outMax {
+		return wasmErrBufferTooSmall
+	}
+	if len(s) > 0 && !copyToPtr(outPtr, outMax, s) {
+		return wasmErrInvalidInput
+	}
+	return int32(len(s))
+}
+
+// GetNormalizeCallCount returns the number of calls to normalizing exports,
+// SanitizeURL, NormalizeURL and NormalizeURLWithFlags, since start or the
+// last ResetCounters.
+//
+//go:wasmexport GetNormalizeCallCount
+func GetNormalizeCallCount() uint64 {
+	return normalizeCallCount.Load()
+}
+
+// GetHashCallCount returns the number of calls to signature exports, such as
+// GetSignature, since start or the last ResetCounters.
+//
+//go:wasmexport GetHashCallCount
+func GetHashCallCount() uint64 {
+	return hashCallCount.Load()
+}
+
+// ResetCounters resets the call counters to zero.
+//
+//go:wasmexport ResetCounters
+func ResetCounters() {
+	resetCallCounters()
+}
+
+// Helper to convert pointer and length to Go string. Input of all exports is
+// an explicit-length byte slice, not a null-terminated string: the bytes are
+// copied as-is, including null bytes, and aren't required to be valid UTF-8,
+// so hosts working with raw bytes don't need to validate them first.
 func ptrToString(ptr, length uint32) string {
 	if length == 0 {
 		return ""
@@ -119,8 +522,20 @@ func ptrToString(ptr, length uint32) string {
 	return string(bytes)
 }
 
+// Helper to copy string into a host provided buffer of given size
+func copyToPtr(ptr, size uint32, s string) bool {
+	if ptr == 0 || ptr > 0xFFFFFF || uint32(len(s)) > size {
+		return false
+	}
+	if len(s) == 0 {
+		return true
+	}
+	copy(unsafe.Slice((*byte)(unsafe.Pointer(uintptr(ptr))), len(s)), s)
+	return true
+}
+
 // Helper to allocate string in WASM memory and return pointer + length
-// Keeps the allocation alive by storing it in memoryArena
+// Keeps the allocation alive by storing it in the memory arena
 func stringToPtr(s string) (uint32, uint32) {
 	if len(s) == 0 {
 		return 0, 0
@@ -129,42 +544,30 @@ func stringToPtr(s string) (uint32, uint32) {
 	if len(s) > 0x7FFFFFFF {
 		return 0, 0
 	}
-	bytes := []byte(s)
-	if len(bytes) == 0 {
-		return 0, 0
-	}
-
-	ptr := uint32(uintptr(unsafe.Pointer(&bytes[0])))
-
 	// Store in memory arena to prevent GC
-	memoryArena.Store(ptr, bytes)
-
-	return ptr, uint32(len(bytes))
+	return uint32(arena.keep([]byte(s))), uint32(len(s))
 }
 
+// Malloc allocates a buffer of size bytes for the host to write input into.
+//
+// Returns a pointer to the buffer, or 0 if size is 0 or larger than
+// maxAllocSize. The buffer is zeroed and 8-byte aligned. It stays valid until
+// passed to Free, and must not be accessed after that.
+//
 //go:wasmexport Malloc
 func Malloc(size uint32) uint32 {
-	if size == 0 || size > maxAllocSize {
-		return 0
-	}
-	// Allocate memory that can be accessed from host
-	buf := make([]byte, size)
-	if len(buf) == 0 {
-		return 0
-	}
-
-	ptr := uint32(uintptr(unsafe.Pointer(&buf[0])))
-
-	// Store in memory arena to prevent GC
-	memoryArena.Store(ptr, buf)
-
-	return ptr
+	// Allocate memory that can be accessed from host, kept in the memory arena
+	// to prevent GC
+	return uint32(arena.alloc(size))
 }
 
+// Free releases a buffer allocated with Malloc. Freeing the same pointer
+// twice, or a pointer not returned by Malloc, is a no-op.
+//
 //go:wasmexport Free
 func Free(ptr uint32) {
 	// Remove from memory arena to allow GC
-	memoryArena.Delete(ptr)
+	arena.free(uintptr(ptr))
 }
 
 func main() {